@@ -0,0 +1,285 @@
+//! Custom derive for the `Packed` trait of [bytepack](https://docs.rs/bytepack).
+//!
+//! `#[derive(Packed)]` implements `Packed` and `EndianSwitch` for a structure whose fields all
+//! implement `Packed`, switching the endianness of every field. The structure must not have any
+//! padding bytes, which is checked at compile time: reorder the fields or add explicit reserved
+//! fields until the size of the structure is the sum of the sizes of its fields.
+//!
+//! With the `#[packed(bytemuck)]` attribute, the derive also implements `bytemuck::Zeroable` and
+//! `bytemuck::Pod` for a `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]` structure
+//! implementing `Copy`, whose fields all implement `Pod`.
+//!
+//! `#[derive(PackedValidate)]` implements `PackedValidate` for a structure whose fields all
+//! implement `PackedValidate`, validating every field. The `#[validate(...)]` attribute adds checks
+//! to a field of a primitive type: `non_zero` rejects the value 0 and `range = "..."` rejects the
+//! values outside of a range expression.
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate bytepack_derive;
+//!
+//! #[derive(Packed, PackedValidate)]
+//! struct Point {
+//!     x: f32,
+//!     y: f32,
+//!     #[validate(range = "0..=0x0fff")]
+//!     flags: u16,
+//!     reserved: u16,
+//! }
+//! ```
+
+extern crate proc_macro;
+extern crate syn;
+#[macro_use]
+extern crate quote;
+
+use proc_macro::TokenStream;
+use quote::Tokens;
+
+#[proc_macro_derive(Packed, attributes(packed))]
+pub fn derive_packed(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_derive_input(&input.to_string()).unwrap();
+    impl_packed(&ast).parse().unwrap()
+}
+
+#[proc_macro_derive(PackedValidate, attributes(validate))]
+pub fn derive_packed_validate(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_derive_input(&input.to_string()).unwrap();
+    impl_packed_validate(&ast).parse().unwrap()
+}
+
+fn impl_packed(ast: &syn::DeriveInput) -> Tokens {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, _) = ast.generics.split_for_impl();
+    let fields = fields(ast);
+    let names: Vec<&Tokens> = fields.iter().map(|(name, _)| name).collect();
+    let tys: Vec<&syn::Ty> = fields.iter().map(|(_, field)| &field.ty).collect();
+    let sizes = tys.iter();
+    let packed_where = where_clause(ast, &tys, quote!(::bytepack::Packed));
+    let switch_where = where_clause(ast, &tys, quote!(::bytepack::EndianSwitch));
+    let message = format!("{} has padding bytes and can not implement Packed", name);
+    // The fields of a packed structure can not be borrowed, they are switched in a copy.
+    let switches = if is_packed(ast) {
+        quote! {
+            #(
+                // safe because the field is a packed value, which has no drop glue.
+                unsafe {
+                    let field = ::std::ptr::addr_of_mut!(self.#names);
+                    let mut value = field.read_unaligned();
+                    ::bytepack::EndianSwitch::switch_endianness(&mut value);
+                    field.write_unaligned(value);
+                }
+            )*
+        }
+    } else {
+        quote!(#(::bytepack::EndianSwitch::switch_endianness(&mut self.#names);)*)
+    };
+    let pod = impl_pod(ast, &tys);
+    // The size of a generic structure can only be checked once it is instantiated, when the
+    // methods of the crate read its SIZE.
+    let check = if is_generic(ast) {
+        quote!()
+    } else {
+        quote!(const _: usize = <#name as ::bytepack::Packed>::SIZE;)
+    };
+    quote! {
+        unsafe impl #impl_generics ::bytepack::Packed for #name #ty_generics #packed_where {
+            const SIZE: usize = {
+                let size = 0 #(+ <#sizes as ::bytepack::Packed>::SIZE)*;
+                assert!(size == ::std::mem::size_of::<Self>(), #message);
+                size
+            };
+        }
+
+        impl #impl_generics ::bytepack::EndianSwitch for #name #ty_generics #switch_where {
+            fn switch_endianness(&mut self) {
+                #switches
+            }
+        }
+
+        #check
+
+        #pod
+    }
+}
+
+/// Implement the `bytemuck` traits if the structure has a `#[packed(bytemuck)]` attribute.
+fn impl_pod(ast: &syn::DeriveInput, tys: &[&syn::Ty]) -> Tokens {
+    if !packed_options(ast).iter().any(|option| option == "bytemuck") {
+        return quote!();
+    }
+    if !has_repr(ast, &["C", "transparent", "packed"]) {
+        panic!("#[packed(bytemuck)] requires {} to be #[repr(C)], #[repr(transparent)] or #[repr(packed)]", ast.ident);
+    }
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, _) = ast.generics.split_for_impl();
+    let mut predicates: Vec<Tokens> = ast.generics.where_clause.predicates.iter()
+        .map(|predicate| quote!(#predicate))
+        .collect();
+    // Bounding the fields of a non-generic structure too checks that they are `Pod`.
+    predicates.extend(tys.iter().map(|ty| quote!(#ty: ::bytemuck::Pod)));
+    let pod_where = quote!(where #(#predicates),*);
+    quote! {
+        // Safe because the derive of `Packed` checks that there is no padding and the fields are
+        // `Pod`.
+        unsafe impl #impl_generics ::bytemuck::Zeroable for #name #ty_generics #pod_where {}
+
+        unsafe impl #impl_generics ::bytemuck::Pod for #name #ty_generics #pod_where {}
+    }
+}
+
+fn impl_packed_validate(ast: &syn::DeriveInput) -> Tokens {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, _) = ast.generics.split_for_impl();
+    let fields = fields(ast);
+    let tys: Vec<&syn::Ty> = fields.iter().map(|(_, field)| &field.ty).collect();
+    let validate_where = where_clause(ast, &tys, quote!(::bytepack::PackedValidate));
+    let packed = is_packed(ast);
+    let checks = fields.iter().map(|(name, field)| {
+        let label = format!("field {}", name);
+        // The fields of a packed structure can not be borrowed, they are checked in a copy.
+        let value = if packed {
+            quote! {
+                // safe because the field is a packed value, which has no drop glue.
+                &unsafe { ::std::ptr::addr_of!(self.#name).read_unaligned() }
+            }
+        } else {
+            quote!(&self.#name)
+        };
+        let mut checks = vec![quote! {
+            ::bytepack::PackedValidate::validate(value).map_err(|reason| format!("{}: {}", #label, reason))?;
+        }];
+        for check in validate_checks(field) {
+            checks.push(match check {
+                Check::NonZero => quote! {
+                    if *value == 0 {
+                        return Err(format!("{}: must not be zero", #label));
+                    }
+                },
+                Check::Range(range) => {
+                    let expr = syn::Ident::new(range.clone());
+                    quote! {
+                        if !(#expr).contains(value) {
+                            return Err(format!("{}: {} is not in {}", #label, value, #range));
+                        }
+                    }
+                }
+            });
+        }
+        quote! {
+            {
+                let value = #value;
+                #(#checks)*
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics ::bytepack::PackedValidate for #name #ty_generics #validate_where {
+            fn validate(&self) -> ::std::result::Result<(), ::std::string::String> {
+                #(#checks)*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A check added to a field by a `#[validate(...)]` attribute.
+enum Check {
+    NonZero,
+    Range(String)
+}
+
+/// Return the checks of the `#[validate(...)]` attributes of a field.
+fn validate_checks(field: &syn::Field) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for attr in &field.attrs {
+        if let syn::MetaItem::List(ref ident, ref items) = attr.value {
+            if ident != "validate" {
+                continue;
+            }
+            for item in items {
+                checks.push(match *item {
+                    syn::NestedMetaItem::MetaItem(syn::MetaItem::Word(ref word)) if word == "non_zero" => Check::NonZero,
+                    syn::NestedMetaItem::MetaItem(syn::MetaItem::NameValue(ref key, syn::Lit::Str(ref range, _))) if key == "range" => {
+                        Check::Range(range.clone())
+                    },
+                    _ => panic!("unknown check {} in #[validate(...)], expected non_zero or range = \"...\"", quote!(#item))
+                });
+            }
+        }
+    }
+    checks
+}
+
+/// Return the fields of a structure, as they are accessed from `self`.
+fn fields(ast: &syn::DeriveInput) -> Vec<(Tokens, &syn::Field)> {
+    let fields = match ast.body {
+        syn::Body::Struct(ref data) => data.fields(),
+        syn::Body::Enum(_) => panic!("bytepack_derive only supports structures, {} is an enum", ast.ident)
+    };
+    fields.iter().enumerate().map(|(i, field)| {
+        let name = match field.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let index = syn::Ident::new(i.to_string());
+                quote!(#index)
+            }
+        };
+        (name, field)
+    }).collect()
+}
+
+/// Return the names listed by the `#[name(...)]` attributes of the structure.
+fn attribute_words(ast: &syn::DeriveInput, name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for attr in &ast.attrs {
+        if let syn::MetaItem::List(ref ident, ref items) = attr.value {
+            if ident == name {
+                words.extend(items.iter().filter_map(|item| match *item {
+                    syn::NestedMetaItem::MetaItem(ref item) => Some(item.name().to_string()),
+                    syn::NestedMetaItem::Literal(_) => None
+                }));
+            }
+        }
+    }
+    words
+}
+
+/// Return the options of the `#[packed(...)]` attributes of the structure.
+fn packed_options(ast: &syn::DeriveInput) -> Vec<String> {
+    let options = attribute_words(ast, "packed");
+    if let Some(option) = options.iter().find(|option| *option != "bytemuck") {
+        panic!("unknown option {} in #[packed(...)] of {}", option, ast.ident);
+    }
+    options
+}
+
+/// Return whether the structure has a `#[repr(...)]` attribute with one of `reprs`.
+fn has_repr(ast: &syn::DeriveInput, reprs: &[&str]) -> bool {
+    attribute_words(ast, "repr").iter().any(|repr| reprs.contains(&repr.as_str()))
+}
+
+/// Return whether the structure has a `#[repr(packed)]` attribute.
+fn is_packed(ast: &syn::DeriveInput) -> bool {
+    has_repr(ast, &["packed"])
+}
+
+fn is_generic(ast: &syn::DeriveInput) -> bool {
+    !ast.generics.ty_params.is_empty() || !ast.generics.lifetimes.is_empty()
+}
+
+/// Return the where clause of the structure, requiring `bound` from the types of its fields when
+/// the structure is generic.
+fn where_clause(ast: &syn::DeriveInput, tys: &[&syn::Ty], bound: Tokens) -> Tokens {
+    let mut predicates: Vec<Tokens> = ast.generics.where_clause.predicates.iter()
+        .map(|predicate| quote!(#predicate))
+        .collect();
+    if is_generic(ast) {
+        predicates.extend(tys.iter().map(|ty| quote!(#ty: #bound)));
+    }
+    if predicates.is_empty() {
+        quote!()
+    } else {
+        quote!(where #(#predicates),*)
+    }
+}
@@ -0,0 +1,323 @@
+//! Adapters between slices of packed values and the `Read` and `Write` traits.
+
+use std::cmp::min;
+use std::io::{self, Read, Write, ErrorKind};
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::slice;
+
+use {order, Packed, Endianness, Error, Result};
+use order::CHUNK_SIZE;
+
+/// A reader serving the bytes of a slice of packed values in a chosen byte order, so a typed
+/// buffer can be handed to any API consuming `io::Read`, such as a compressor or a hasher,
+/// without copying it into a `Vec<u8>` first.
+///
+/// In the native byte order the bytes are copied straight from the slice, otherwise each value is
+/// converted on the fly.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+/// use bytepack::{PackedReader, Endianness};
+///
+/// let values = [1u16, 2];
+/// let mut bytes = Vec::new();
+/// PackedReader::with_endianness(&values[..], Endianness::Big).read_to_end(&mut bytes).unwrap();
+/// assert_eq!(bytes, [0, 1, 0, 2]);
+/// ```
+pub struct PackedReader<'a, T: 'a> {
+    values: &'a [T],
+    endianness: Endianness,
+    // number of bytes already read
+    position: usize
+}
+
+impl<'a, T: Packed> PackedReader<'a, T> {
+    /// Serve the bytes of `values` in the native byte order.
+    pub fn new(values: &'a [T]) -> PackedReader<'a, T> {
+        PackedReader::with_endianness(values, Endianness::native())
+    }
+
+    /// Serve the bytes of `values` in the `endianness` byte order.
+    pub fn with_endianness(values: &'a [T], endianness: Endianness) -> PackedReader<'a, T> {
+        PackedReader {
+            values,
+            endianness,
+            position: 0
+        }
+    }
+
+    /// Return the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.values.len() * T::SIZE - self.position
+    }
+}
+
+impl<'a, T: Packed> Read for PackedReader<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = min(buf.len(), self.remaining());
+        if self.endianness.is_native() {
+            buf[..len].copy_from_slice(&order::raw_bytes(self.values)[self.position..self.position + len]);
+            self.position += len;
+            return Ok(len);
+        }
+        let size = T::SIZE;
+        let mut read = 0;
+        while read < len {
+            let (index, offset) = (self.position / size, self.position % size);
+            // safe because the bitwise copy of a packed value is a valid value and the copy is
+            // never dropped.
+            let mut value = ManuallyDrop::new(unsafe { ptr::read(&self.values[index]) });
+            value.switch_endianness();
+            let bytes = order::raw_bytes(slice::from_ref(&*value));
+            let count = min(size - offset, len - read);
+            buf[read..read + count].copy_from_slice(&bytes[offset..offset + count]);
+            read += count;
+            self.position += count;
+        }
+        Ok(len)
+    }
+}
+
+/// A writer decoding the bytes written to it as values of type `T` stored in a chosen byte order
+/// and appending them to an internal `Vec<T>`, so the output of a decompressor, for example, can
+/// be piped directly into typed storage.
+///
+/// The bytes of an incomplete value are kept until the rest of the value is written.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Write;
+/// use bytepack::{PackedSink, Endianness};
+///
+/// let mut sink = PackedSink::<u16>::with_endianness(Endianness::Little);
+/// sink.write_all(&[1, 0, 2]).unwrap();
+/// sink.write_all(&[0]).unwrap();
+/// assert_eq!(sink.into_values().unwrap(), [1, 2]);
+/// ```
+pub struct PackedSink<T> {
+    values: Vec<T>,
+    endianness: Endianness,
+    // bytes of an incomplete value
+    partial: Vec<u8>
+}
+
+impl<T: Packed> PackedSink<T> {
+    /// Create an empty sink decoding values stored in the native byte order.
+    pub fn new() -> PackedSink<T> {
+        PackedSink::with_endianness(Endianness::native())
+    }
+
+    /// Create an empty sink decoding values stored in the `endianness` byte order.
+    pub fn with_endianness(endianness: Endianness) -> PackedSink<T> {
+        PackedSink {
+            values: Vec::new(),
+            endianness,
+            partial: Vec::with_capacity(T::SIZE)
+        }
+    }
+
+    /// Return the values decoded so far.
+    pub fn values(&self) -> &[T] {
+        &self.values[..]
+    }
+
+    /// Return the number of bytes of the incomplete value written last.
+    pub fn partial_len(&self) -> usize {
+        self.partial.len()
+    }
+
+    /// Return the decoded values. A `TrailingBytes` error is returned if the bytes written do not
+    /// end on a value boundary.
+    pub fn into_values(self) -> Result<Vec<T>> {
+        if !self.partial.is_empty() {
+            return Err(Error::trailing_bytes::<T>(self.partial.len()));
+        }
+        Ok(self.values)
+    }
+}
+
+/// Append to `values` the values stored in the `endianness` byte order in `bytes`, whose length is
+/// a multiple of the size of `T`.
+fn decode<T: Packed>(values: &mut Vec<T>, bytes: &[u8], endianness: Endianness) {
+    let count = bytes.len() / T::SIZE;
+    values.reserve(count);
+    let length = values.len();
+    // safe because exactly count values are copied in the spare capacity and any bit pattern is a
+    // valid packed value.
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), values.as_mut_ptr().add(length) as *mut u8, bytes.len());
+        values.set_len(length + count);
+    }
+    if !endianness.is_native() {
+        order::switch_endianness_slice(&mut values[length..]);
+    }
+}
+
+impl<T: Packed> Default for PackedSink<T> {
+    fn default() -> PackedSink<T> {
+        PackedSink::new()
+    }
+}
+
+impl<T: Packed> Write for PackedSink<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = T::SIZE;
+        if size == 0 {
+            return Err(Error::invalid_input::<T, _>("cannot decode zero-sized values").into());
+        }
+        let mut rest = buf;
+        if !self.partial.is_empty() {
+            let count = min(size - self.partial.len(), rest.len());
+            self.partial.extend_from_slice(&rest[..count]);
+            rest = &rest[count..];
+            if self.partial.len() < size {
+                return Ok(buf.len());
+            }
+            decode(&mut self.values, &self.partial[..], self.endianness);
+            self.partial.clear();
+        }
+        let whole = rest.len() - rest.len() % size;
+        decode(&mut self.values, &rest[..whole], self.endianness);
+        self.partial.extend_from_slice(&rest[whole..]);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader and writer wrapper presenting data stored in a foreign byte order as if it were in
+/// the native one, converting every value of type `T` on the fly. Code written against the plain
+/// [`Unpacker`](trait.Unpacker.html) and [`Packer`](trait.Packer.html) traits can then be used
+/// unchanged on foreign-endian data.
+///
+/// Bytes are only served once a whole value has been read from the inner reader, and written to
+/// the inner writer once a whole value has been written. `flush` fails while the bytes of an
+/// incomplete value are buffered, and they are lost when the adapter is dropped.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{ByteOrderAdapter, Endianness, Unpacker};
+///
+/// let data = [0u8, 0, 0, 1, 0, 0, 0, 2];
+/// let mut reader = ByteOrderAdapter::<_, u32>::new(&data[..], Endianness::Big);
+/// assert_eq!(reader.unpack_n::<u32>(2).unwrap(), [1, 2]);
+/// ```
+pub struct ByteOrderAdapter<I, T> {
+    inner: I,
+    endianness: Endianness,
+    // bytes read from inner but not converted yet, or written but not forming a whole value yet
+    raw: Vec<u8>,
+    // values converted and not entirely served yet
+    ready: Vec<T>,
+    // number of bytes of ready already served
+    position: usize
+}
+
+impl<I, T: Packed> ByteOrderAdapter<I, T> {
+    /// Wrap `inner`, whose data is stored in the `endianness` byte order.
+    pub fn new(inner: I, endianness: Endianness) -> ByteOrderAdapter<I, T> {
+        ByteOrderAdapter {
+            inner,
+            endianness,
+            raw: Vec::new(),
+            ready: Vec::new(),
+            position: 0
+        }
+    }
+
+    /// Return a reference to the inner reader or writer.
+    pub fn get_ref(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap the inner reader or writer, dropping any buffered byte.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<R: Read, T: Packed> ByteOrderAdapter<R, T> {
+    /// Read from the inner reader until at least one whole value has been converted or `EOF` is
+    /// reached.
+    fn refill(&mut self) -> io::Result<()> {
+        let size = T::SIZE;
+        self.ready.clear();
+        self.position = 0;
+        if size == 0 {
+            return Ok(());
+        }
+        loop {
+            let start = self.raw.len();
+            self.raw.resize(start + CHUNK_SIZE.max(size), 0);
+            match self.inner.read(&mut self.raw[start..]) {
+                Ok(read) => self.raw.truncate(start + read),
+                Err(e) => {
+                    self.raw.truncate(start);
+                    if e.kind() == ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(e);
+                }
+            }
+            if self.raw.len() == start {
+                if self.raw.is_empty() {
+                    return Ok(());
+                }
+                return Err(Error::short_read::<T>(size, self.raw.len()).into());
+            }
+            let whole = self.raw.len() - self.raw.len() % size;
+            if whole > 0 {
+                decode(&mut self.ready, &self.raw[..whole], self.endianness);
+                self.raw.drain(..whole);
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<R: Read, T: Packed> Read for ByteOrderAdapter<R, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.ready.len() * T::SIZE {
+            self.refill()?;
+        }
+        let bytes = &order::raw_bytes(&self.ready[..])[self.position..];
+        let len = min(buf.len(), bytes.len());
+        buf[..len].copy_from_slice(&bytes[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+impl<W: Write, T: Packed> Write for ByteOrderAdapter<W, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = T::SIZE;
+        if size == 0 {
+            return Err(Error::invalid_input::<T, _>("cannot convert zero-sized values").into());
+        }
+        let len = min(buf.len(), CHUNK_SIZE);
+        self.raw.extend_from_slice(&buf[..len]);
+        let whole = self.raw.len() - self.raw.len() % size;
+        self.ready.clear();
+        decode(&mut self.ready, &self.raw[..whole], self.endianness);
+        self.raw.drain(..whole);
+        self.inner.write_all(order::raw_bytes(&self.ready[..]))?;
+        Ok(len)
+    }
+
+    /// Flush the inner writer. An `InvalidData` error is returned if the bytes of an incomplete
+    /// value are still buffered, since they cannot be written until the value is complete.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        if !self.raw.is_empty() {
+            return Err(Error::trailing_bytes::<T>(self.raw.len()).into());
+        }
+        Ok(())
+    }
+}
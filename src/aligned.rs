@@ -0,0 +1,180 @@
+//! Byte buffers with a guaranteed alignment.
+
+use std::fmt;
+use std::io::{Write, Result};
+use std::mem::{size_of, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+/// A growable byte buffer whose content is always aligned like the type `A`, so it can be
+/// viewed as values of any type with the same or a smaller alignment using
+/// [`unpack_slice_ref`](fn.unpack_slice_ref.html) and
+/// [`unpack_slice_mut`](fn.unpack_slice_mut.html), which a `Vec<u8>` does not guarantee.
+///
+/// `AlignedVec` dereferences to `[u8]` and implements `Write`, so it can be filled by the packer
+/// traits.
+///
+/// # Panics
+///
+/// The constructors panic if `A` is zero-sized.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{AlignedVec, Packer, unpack_slice_ref};
+///
+/// let mut buffer = AlignedVec::<f64>::new();
+/// buffer.pack_all(&[1.5f64, 2.5]).unwrap();
+/// let samples = unpack_slice_ref::<f64>(&buffer).unwrap();
+/// assert_eq!(samples, &[1.5, 2.5]);
+/// ```
+pub struct AlignedVec<A> {
+    words: Vec<MaybeUninit<A>>,
+    len: usize
+}
+
+impl<A> AlignedVec<A> {
+    /// Create a new empty buffer.
+    pub fn new() -> AlignedVec<A> {
+        AlignedVec::with_capacity(0)
+    }
+
+    /// Create a new empty buffer able to hold at least `capacity` bytes without reallocating.
+    pub fn with_capacity(capacity: usize) -> AlignedVec<A> {
+        assert!(size_of::<A>() != 0, "AlignedVec cannot be aligned on a zero-sized type");
+        AlignedVec {
+            words: Vec::with_capacity(capacity.div_ceil(size_of::<A>())),
+            len: 0
+        }
+    }
+
+    /// Create a new buffer holding `len` zero bytes.
+    pub fn zeroed(len: usize) -> AlignedVec<A> {
+        let mut buffer = AlignedVec::with_capacity(len);
+        buffer.resize(len, 0);
+        buffer
+    }
+
+    /// Create a new buffer holding a copy of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> AlignedVec<A> {
+        let mut buffer = AlignedVec::with_capacity(bytes.len());
+        buffer.extend_from_slice(bytes);
+        buffer
+    }
+
+    /// Return the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the buffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.words.capacity() * size_of::<A>()
+    }
+
+    /// Remove all the bytes of the buffer, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Resize the buffer to `len` bytes, filling the new bytes with `value`.
+    pub fn resize(&mut self, len: usize, value: u8) {
+        if len > self.len {
+            let start = self.len;
+            self.grow(len);
+            // safe because grow made room for len bytes
+            unsafe {
+                ptr::write_bytes((self.words.as_mut_ptr() as *mut u8).add(start), value, len - start);
+            }
+        }
+        self.len = len;
+    }
+
+    /// Append a copy of `bytes` to the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let start = self.len;
+        self.grow(start + bytes.len());
+        // safe because grow made room for bytes.len() bytes after the current ones
+        unsafe {
+            ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                (self.words.as_mut_ptr() as *mut u8).add(start),
+                bytes.len()
+            );
+        }
+        self.len = start + bytes.len();
+    }
+
+    /// Return the bytes of the buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        // safe because the first len bytes are initialized
+        unsafe {
+            slice::from_raw_parts(self.words.as_ptr() as *const u8, self.len)
+        }
+    }
+
+    /// Return the bytes of the buffer as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // safe because the first len bytes are initialized
+        unsafe {
+            slice::from_raw_parts_mut(self.words.as_mut_ptr() as *mut u8, self.len)
+        }
+    }
+
+    /// Make the words of the buffer span at least `len` bytes. The new bytes are uninitialized.
+    fn grow(&mut self, len: usize) {
+        let words = len.div_ceil(size_of::<A>());
+        if words > self.words.len() {
+            self.words.resize_with(words, MaybeUninit::uninit);
+        }
+    }
+}
+
+impl<A> Default for AlignedVec<A> {
+    fn default() -> AlignedVec<A> {
+        AlignedVec::new()
+    }
+}
+
+impl<A> Clone for AlignedVec<A> {
+    fn clone(&self) -> AlignedVec<A> {
+        AlignedVec::from_bytes(self)
+    }
+}
+
+impl<A> fmt::Debug for AlignedVec<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<A> Deref for AlignedVec<A> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<A> DerefMut for AlignedVec<A> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<A> Write for AlignedVec<A> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Interoperability with the `ByteOrder` trait of the `byteorder` crate.
+
+use std::io::{Read, Write};
+
+use byteorder::ByteOrder;
+
+use {Unpacker, Packer, Packed, Endianness, Result};
+
+/// Return the [`Endianness`](enum.Endianness.html) corresponding to the `byteorder` type `B`. It
+/// is only available with the `byteorder` feature.
+///
+/// ```edition2018
+/// use bytepack::{endianness_of, Endianness};
+///
+/// assert_eq!(endianness_of::<byteorder::NetworkEndian>(), Endianness::Big);
+/// ```
+pub fn endianness_of<B: ByteOrder>() -> Endianness {
+    // ByteOrder is sealed and only implemented by the little and big endian types
+    if B::read_u16(&[1, 0]) == 1 {
+        Endianness::Little
+    }
+    else {
+        Endianness::Big
+    }
+}
+
+/// Unpacking methods taking the byte order as a `byteorder::ByteOrder` type parameter, so code
+/// already generic over `ByteOrder` can use them directly. It is implemented for all the types
+/// implementing `Read` and only available with the `byteorder` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use byteorder::{ByteOrder, BigEndian};
+/// use bytepack::ByteOrderUnpacker;
+///
+/// fn read_header<B: ByteOrder>(mut data: &[u8]) -> (u32, u16) {
+///     let length = data.unpack_with_order::<u32, B>().unwrap();
+///     let kind = data.unpack_with_order::<u16, B>().unwrap();
+///     (length, kind)
+/// }
+///
+/// assert_eq!(read_header::<BigEndian>(&[0, 0, 0, 8, 0, 1]), (8, 1));
+/// ```
+pub trait ByteOrderUnpacker {
+    /// Same as [`Unpacker::unpack_with`](trait.Unpacker.html#tymethod.unpack_with) with the byte
+    /// order given by `B`.
+    fn unpack_with_order<T: Packed, B: ByteOrder>(&mut self) -> Result<T>;
+    /// Same as [`Unpacker::unpack_exact_with`](trait.Unpacker.html#tymethod.unpack_exact_with)
+    /// with the byte order given by `B`.
+    fn unpack_exact_with_order<T: Packed, B: ByteOrder>(&mut self, buf: &mut [T]) -> Result<()>;
+}
+
+/// Packing methods taking the byte order as a `byteorder::ByteOrder` type parameter, so code
+/// already generic over `ByteOrder` can use them directly. It is implemented for all the types
+/// implementing `Write` and only available with the `byteorder` feature.
+pub trait ByteOrderPacker {
+    /// Same as [`Packer::pack_with`](trait.Packer.html#tymethod.pack_with) with the byte order
+    /// given by `B`.
+    fn pack_with_order<T: Packed, B: ByteOrder>(&mut self, t: T) -> Result<()>;
+    /// Same as [`Packer::pack_all_with`](trait.Packer.html#tymethod.pack_all_with) with the byte
+    /// order given by `B`.
+    fn pack_all_with_order<T: Packed, B: ByteOrder>(&mut self, buf: &[T]) -> Result<()>;
+}
+
+impl<R: Read> ByteOrderUnpacker for R {
+    fn unpack_with_order<T: Packed, B: ByteOrder>(&mut self) -> Result<T> {
+        self.unpack_with(endianness_of::<B>())
+    }
+
+    fn unpack_exact_with_order<T: Packed, B: ByteOrder>(&mut self, buf: &mut [T]) -> Result<()> {
+        self.unpack_exact_with(buf, endianness_of::<B>())
+    }
+}
+
+impl<W: Write> ByteOrderPacker for W {
+    fn pack_with_order<T: Packed, B: ByteOrder>(&mut self, t: T) -> Result<()> {
+        self.pack_with(t, endianness_of::<B>())
+    }
+
+    fn pack_all_with_order<T: Packed, B: ByteOrder>(&mut self, buf: &[T]) -> Result<()> {
+        self.pack_all_with(buf, endianness_of::<B>())
+    }
+}
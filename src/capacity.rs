@@ -0,0 +1,99 @@
+//! Unpacking into collections with a fixed capacity.
+
+use std::mem::MaybeUninit;
+#[cfg(feature = "arrayvec")]
+use std::slice;
+
+#[cfg(feature = "arrayvec")]
+use arrayvec::ArrayVec;
+
+use Packed;
+
+/// A collection of packed values whose storage cannot grow, which can be filled by the
+/// `unpack_to_end_fixed` and `unpack_n_fixed` methods of the unpacker traits without any
+/// allocation. Those methods return a `LimitExceeded` error when the input holds more values than
+/// the collection has room for. It is implemented for `arrayvec::ArrayVec` with the `arrayvec`
+/// feature and can be implemented for any other fixed-capacity collection.
+///
+/// # Safety
+///
+/// `spare_capacity_mut` must return the storage following the first `len()` values, and after
+/// `n` values of this storage have been initialized, `set_len(len() + n)` must make them the
+/// last values of the collection.
+///
+/// # Example
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use bytepack::{FixedCapacity, LEUnpacker};
+///
+/// struct Samples {
+///     values: [MaybeUninit<i16>; 8],
+///     len: usize
+/// }
+///
+/// unsafe impl FixedCapacity for Samples {
+///     type Item = i16;
+///
+///     fn len(&self) -> usize {
+///         self.len
+///     }
+///
+///     fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<i16>] {
+///         &mut self.values[self.len..]
+///     }
+///
+///     unsafe fn set_len(&mut self, len: usize) {
+///         self.len = len;
+///     }
+/// }
+///
+/// let mut samples = Samples { values: [MaybeUninit::uninit(); 8], len: 0 };
+/// let mut data: &[u8] = &[1, 0, 2, 0, 3, 0];
+/// assert_eq!(data.unpack_to_end_fixed(&mut samples).unwrap(), 3);
+/// let mut data: &[u8] = &[0; 18];
+/// assert!(data.unpack_to_end_fixed(&mut samples).is_err());
+/// assert_eq!(samples.len(), 3);
+/// ```
+pub unsafe trait FixedCapacity {
+    /// Type of the values held by the collection.
+    type Item: Packed;
+
+    /// Return the number of values held by the collection.
+    fn len(&self) -> usize;
+
+    /// Return `true` if the collection holds no value.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the storage available after the values held by the collection.
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::Item>];
+
+    /// Set the number of values held by the collection.
+    ///
+    /// # Safety
+    ///
+    /// All the values up to `len` must have been initialized.
+    unsafe fn set_len(&mut self, len: usize);
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<T: Packed, const CAP: usize> FixedCapacity for ArrayVec<T, CAP> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        ArrayVec::len(self)
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = ArrayVec::len(self);
+        // safe because the storage of an ArrayVec holds CAP values and the slice borrows it
+        // mutably
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr().add(len) as *mut MaybeUninit<T>, CAP - len) }
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        ArrayVec::set_len(self, len)
+    }
+}
@@ -0,0 +1,176 @@
+//! Rendering of C declarations equivalent to [`Packed`](../trait.Packed.html) types, so binary
+//! formats shared with C code can be kept in sync with their Rust definition.
+
+use Packed;
+
+/// `CDecl` renders the C declaration equivalent to a [`Packed`](trait.Packed.html) type using the
+/// fixed-width types of `<stdint.h>`.
+///
+/// It is implemented for all the primitive types implementing `Packed`, for arrays of types
+/// implementing `CDecl` and for [`Le`](type.Le.html) and [`Be`](type.Be.html) values, which are
+/// rendered as arrays of bytes because they are not aligned. Structures can implement it with the
+/// [`c_struct!`](macro.c_struct.html) macro.
+///
+/// ```
+/// use bytepack::{CDecl, Be};
+///
+/// assert_eq!(u32::c_decl("length"), "uint32_t length");
+/// assert_eq!(<[[u8; 4]; 2]>::c_decl("ip"), "uint8_t ip[2][4]");
+/// assert_eq!(<[Be<u32>; 2]>::c_decl("lengths"), "uint8_t lengths[2][4]");
+/// ```
+pub trait CDecl: Packed {
+    /// Render the declaration of a variable called `name` of this type.
+    fn c_decl(name: &str) -> String;
+
+    /// Render the complete C definition of this type. This is `None` for types which do not need
+    /// a definition like primitive types and arrays.
+    fn c_definition() -> Option<String> {
+        None
+    }
+}
+
+impl CDecl for u8 {
+    fn c_decl(name: &str) -> String {
+        format!("uint8_t {}", name)
+    }
+}
+
+impl CDecl for i8 {
+    fn c_decl(name: &str) -> String {
+        format!("int8_t {}", name)
+    }
+}
+
+impl CDecl for u16 {
+    fn c_decl(name: &str) -> String {
+        format!("uint16_t {}", name)
+    }
+}
+
+impl CDecl for i16 {
+    fn c_decl(name: &str) -> String {
+        format!("int16_t {}", name)
+    }
+}
+
+impl CDecl for u32 {
+    fn c_decl(name: &str) -> String {
+        format!("uint32_t {}", name)
+    }
+}
+
+impl CDecl for i32 {
+    fn c_decl(name: &str) -> String {
+        format!("int32_t {}", name)
+    }
+}
+
+impl CDecl for u64 {
+    fn c_decl(name: &str) -> String {
+        format!("uint64_t {}", name)
+    }
+}
+
+impl CDecl for i64 {
+    fn c_decl(name: &str) -> String {
+        format!("int64_t {}", name)
+    }
+}
+
+impl CDecl for f32 {
+    fn c_decl(name: &str) -> String {
+        format!("float {}", name)
+    }
+}
+
+impl CDecl for f64 {
+    fn c_decl(name: &str) -> String {
+        format!("double {}", name)
+    }
+}
+
+impl<T, const N: usize> CDecl for [T; N] where T: CDecl, [T; N]: Packed {
+    fn c_decl(name: &str) -> String {
+        T::c_decl(&format!("{}[{}]", name, N))
+    }
+}
+
+/// Render a C `struct` definition called `name` from the declarations of its fields. This is used
+/// by [`c_struct!`](macro.c_struct.html) but can also be called directly by manual
+/// implementations of [`CDecl`](trait.CDecl.html).
+///
+/// ```
+/// use bytepack::{CDecl, c_struct_definition};
+///
+/// let definition = c_struct_definition("point", &[f32::c_decl("x"), f32::c_decl("y")]);
+/// assert_eq!(definition, "struct point {\n    float x;\n    float y;\n};\n");
+/// ```
+pub fn c_struct_definition(name: &str, fields: &[String]) -> String {
+    let mut definition = format!("struct {} {{\n", name);
+    for field in fields {
+        definition.push_str("    ");
+        definition.push_str(field);
+        definition.push_str(";\n");
+    }
+    definition.push_str("};\n");
+    definition
+}
+
+/// Define a structure and implement [`CDecl`](trait.CDecl.html) for it from the same definition,
+/// so the C declaration can never get out of sync with the Rust one.
+///
+/// The structure is made `#[repr(C)]` so the rendered definition has the same layout as the Rust
+/// one, and must not be given another representation. Structures used as fields need to
+/// implement `CDecl` themselves and their definition has to be emitted before the one using them.
+///
+/// ```
+/// #[macro_use]
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// use bytepack::{CDecl, Packed};
+///
+/// c_struct! {
+///     #[derive(Packed)]
+///     pub struct Header {
+///         pub magic: [u8; 4],
+///         pub length: u32,
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(
+///         Header::c_definition().unwrap(),
+///         "struct Header {\n    uint8_t magic[4];\n    uint32_t length;\n};\n"
+///     );
+/// }
+/// ```
+#[macro_export]
+macro_rules! c_struct {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        impl $crate::CDecl for $name {
+            fn c_decl(name: &str) -> String {
+                format!("struct {} {}", stringify!($name), name)
+            }
+
+            fn c_definition() -> Option<String> {
+                Some($crate::c_struct_definition(
+                    stringify!($name),
+                    &[$(<$ty as $crate::CDecl>::c_decl(stringify!($field))),*]
+                ))
+            }
+        }
+    };
+}
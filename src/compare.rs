@@ -0,0 +1,66 @@
+//! Comparison of two streams of packed values.
+
+use std::io::Read;
+
+use {order, Order, Packed, Endianness, NativeEndian, LittleEndian, BigEndian, Result};
+
+/// First difference between two streams found by [`compare_packed`](fn.compare_packed.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference<T> {
+    /// Index of the first value which differs.
+    pub index: u64,
+    /// Value of the first stream, or `None` if it ended first.
+    pub a: Option<T>,
+    /// Value of the second stream, or `None` if it ended first.
+    pub b: Option<T>
+}
+
+/// Walk `a` and `b` value by value and return the first pair of values of type `T` which differ,
+/// with its index, or `None` if both streams hold the same values. A stream ending before the
+/// other is reported as a difference with a `None` value. A `ShortRead` error is returned if a
+/// stream ends in the middle of a value. As the values are read one by one, buffered readers
+/// should be used.
+///
+/// ```
+/// use bytepack::{compare_packed, Difference, Packer};
+///
+/// let (mut golden, mut generated) = (Vec::new(), Vec::new());
+/// golden.pack_all(&[1u16, 2, 3][..]).unwrap();
+/// generated.pack_all(&[1u16, 2, 4, 5][..]).unwrap();
+/// let difference = compare_packed::<u16, _, _>(&golden[..], &generated[..]).unwrap();
+/// assert_eq!(difference, Some(Difference { index: 2, a: Some(3), b: Some(4) }));
+/// ```
+pub fn compare_packed<T: Packed + PartialEq, A: Read, B: Read>(a: A, b: B) -> Result<Option<Difference<T>>> {
+    compare::<NativeEndian, T, A, B>(a, b)
+}
+
+/// Same as [`compare_packed`](fn.compare_packed.html) but both streams hold values in the byte
+/// order given by `endianness`.
+///
+/// ```
+/// use bytepack::{compare_packed_with, Endianness};
+///
+/// let golden: &[u8] = &[0, 1, 0, 2];
+/// let generated: &[u8] = &[0, 1];
+/// let difference = compare_packed_with::<u16, _, _>(golden, generated, Endianness::Big).unwrap().unwrap();
+/// assert_eq!((difference.index, difference.a, difference.b), (1, Some(2), None));
+/// ```
+pub fn compare_packed_with<T: Packed + PartialEq, A: Read, B: Read>(a: A, b: B, endianness: Endianness) -> Result<Option<Difference<T>>> {
+    match endianness {
+        Endianness::Little => compare::<LittleEndian, T, A, B>(a, b),
+        Endianness::Big => compare::<BigEndian, T, A, B>(a, b)
+    }
+}
+
+fn compare<O: Order, T: Packed + PartialEq, A: Read, B: Read>(mut a: A, mut b: B) -> Result<Option<Difference<T>>> {
+    let mut index = 0;
+    loop {
+        let value_a = order::unpack_maybe::<O, T, A>(&mut a).map_err(|e| e.with_offset(index * T::SIZE as u64))?;
+        let value_b = order::unpack_maybe::<O, T, B>(&mut b).map_err(|e| e.with_offset(index * T::SIZE as u64))?;
+        match (value_a, value_b) {
+            (None, None) => return Ok(None),
+            (Some(ref x), Some(ref y)) if x == y => index += 1,
+            (a, b) => return Ok(Some(Difference { index, a, b }))
+        }
+    }
+}
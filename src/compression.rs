@@ -0,0 +1,52 @@
+//! Packing and unpacking through deflate streams.
+
+use std::io::{self, BufRead, Read, Write};
+
+use flate2::Compression;
+use flate2::bufread::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+
+use {Error, Result};
+
+/// Call `f` with a deflate encoder wrapping `w`, then finish the compressed stream and return the
+/// result of `f` with `w`. The values packed by `f` are compressed with the `level` compression
+/// level. It is only available with the `flate2` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{pack_deflated, unpack_deflated, LEPacker, LEUnpacker};
+/// use flate2::Compression;
+///
+/// let samples = vec![0.5f32; 1024];
+/// let ((), compressed) = pack_deflated(Vec::new(), Compression::default(), |w| {
+///     w.pack(samples.len() as u32)?;
+///     w.pack_all(&samples[..])
+/// }).unwrap();
+///
+/// let unpacked = unpack_deflated(&compressed[..], |r| {
+///     let len : u32 = r.unpack()?;
+///     r.unpack_n::<f32>(len as usize)
+/// }).unwrap();
+/// assert_eq!(unpacked, samples);
+/// ```
+pub fn pack_deflated<W: Write, U, F: FnOnce(&mut DeflateEncoder<W>) -> Result<U>>(w: W, level: Compression, f: F) -> Result<(U, W)> {
+    let mut encoder = DeflateEncoder::new(w, level);
+    let u = f(&mut encoder)?;
+    Ok((u, encoder.finish()?))
+}
+
+/// Call `f` with a deflate decoder wrapping `r` and return its result. Once `f` returns, the rest
+/// of the compressed stream is read so `r` is positioned right after it, and a `TrailingBytes`
+/// error is returned if it held more data than what `f` unpacked. Since `r` is a `BufRead`, no
+/// byte following the compressed stream is consumed. It is only available with the `flate2`
+/// feature.
+pub fn unpack_deflated<R: BufRead, U, F: FnOnce(&mut DeflateDecoder<R>) -> Result<U>>(r: R, f: F) -> Result<U> {
+    let mut decoder = DeflateDecoder::new(r);
+    let u = f(&mut decoder)?;
+    let trailing = io::copy(&mut decoder.by_ref().take(u64::MAX), &mut io::sink())?;
+    if trailing != 0 {
+        return Err(Error::trailing_bytes::<u8>(trailing as usize));
+    }
+    Ok(u)
+}
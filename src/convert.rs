@@ -0,0 +1,49 @@
+//! Conversions between integer widths.
+
+use std::convert::TryFrom;
+
+use Packed;
+
+/// The policy applied by the `unpack_as` methods when a value does not fit in the target type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Return an `InvalidValue` error.
+    Error,
+    /// Use the closest value of the target type.
+    Saturate,
+    /// Keep the low order bits, like an `as` cast.
+    Wrap
+}
+
+/// A primitive integer type, which the `unpack_as` methods of the unpacker traits can convert
+/// between. It is implemented for all the 8 to 64 bits integer types.
+pub trait Integer: Packed + Copy {
+    /// Return the value widened to an `i128`, which holds any value of the implementing types.
+    fn to_i128(self) -> i128;
+
+    /// Convert `value` following the `overflow` policy, or return `None` if it does not fit and
+    /// the policy is `Overflow::Error`.
+    fn from_i128(value: i128, overflow: Overflow) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($ty:ident),*) => {
+        $(
+            impl Integer for $ty {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128, overflow: Overflow) -> Option<$ty> {
+                    match overflow {
+                        Overflow::Error => $ty::try_from(value).ok(),
+                        Overflow::Saturate => Some(value.clamp($ty::MIN as i128, $ty::MAX as i128) as $ty),
+                        Overflow::Wrap => Some(value as $ty)
+                    }
+                }
+            }
+        )*
+    }
+}
+
+integer!(i8, u8, i16, u16, i32, u32, i64, u64);
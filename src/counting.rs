@@ -0,0 +1,160 @@
+//! Reader and writer wrapper gathering statistics about the data going through it.
+
+use std::any::type_name;
+use std::collections::HashMap;
+use std::io::{self, Read, BufRead, Write, Seek, SeekFrom};
+use std::mem::size_of;
+
+use Result;
+
+/// Totals gathered by [`Counting`](struct.Counting.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    /// Number of calls.
+    pub calls: u64,
+    /// Number of values, only meaningful for the totals of a type.
+    pub items: u64,
+    /// Number of bytes.
+    pub bytes: u64
+}
+
+impl Counts {
+    fn add(&mut self, items: u64, bytes: u64) {
+        self.calls += 1;
+        self.items += items;
+        self.bytes += bytes;
+    }
+}
+
+/// A reader or writer wrapper counting the calls and the bytes going through it, which helps
+/// verifying the framing of a protocol or feeding performance dashboards without instrumenting
+/// every call site.
+///
+/// The totals of the underlying reads and writes are always kept. Totals per type are gathered by
+/// running the operations through [`count`](#method.count), which attributes the bytes they
+/// transferred to values of the given type.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Counting, LEUnpacker};
+///
+/// let mut reader = Counting::new(&[1u8, 0, 2, 0, 3, 0, 0, 0][..]);
+/// let mut samples = [0u16; 2];
+/// reader.count::<u16, _, _>(|r| r.unpack_exact(&mut samples)).unwrap();
+/// reader.count::<u32, _, _>(|r| r.unpack::<u32>()).unwrap();
+/// assert_eq!(reader.read_counts().bytes, 8);
+/// assert_eq!(reader.type_counts("u16").unwrap().items, 2);
+/// assert_eq!(reader.type_counts("u32").unwrap().calls, 1);
+/// ```
+pub struct Counting<T> {
+    inner: T,
+    reads: Counts,
+    writes: Counts,
+    types: HashMap<&'static str, Counts>
+}
+
+impl<T> Counting<T> {
+    /// Wrap `inner` with all the counts at zero.
+    pub fn new(inner: T) -> Counting<T> {
+        Counting {
+            inner,
+            reads: Counts::default(),
+            writes: Counts::default(),
+            types: HashMap::new()
+        }
+    }
+
+    /// Call `f` with this wrapper and attribute the bytes it read and wrote to values of type
+    /// `U`, counting one call for that type.
+    pub fn count<U, V, F: FnOnce(&mut Counting<T>) -> Result<V>>(&mut self, f: F) -> Result<V> {
+        let before = self.reads.bytes + self.writes.bytes;
+        let result = f(self);
+        let bytes = self.reads.bytes + self.writes.bytes - before;
+        let items = bytes / size_of::<U>().max(1) as u64;
+        self.types.entry(type_name::<U>()).or_default().add(items, bytes);
+        result
+    }
+
+    /// Return the totals of the underlying reads: the number of `read` calls and of bytes read.
+    pub fn read_counts(&self) -> Counts {
+        self.reads
+    }
+
+    /// Return the totals of the underlying writes: the number of `write` calls and of bytes
+    /// written.
+    pub fn write_counts(&self) -> Counts {
+        self.writes
+    }
+
+    /// Return the totals gathered by [`count`](#method.count) for the type named `name`, as
+    /// given by `std::any::type_name`.
+    pub fn type_counts(&self, name: &str) -> Option<Counts> {
+        self.types.get(name).cloned()
+    }
+
+    /// Return an iterator over the names of the counted types and their totals.
+    pub fn types(&self) -> impl Iterator<Item = (&'static str, Counts)> + '_ {
+        self.types.iter().map(|(name, counts)| (*name, *counts))
+    }
+
+    /// Reset all the counts to zero.
+    pub fn reset(&mut self) {
+        self.reads = Counts::default();
+        self.writes = Counts::default();
+        self.types.clear();
+    }
+
+    /// Return a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader or writer. Bytes read or written through
+    /// it are not counted.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Counting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        self.reads.add(0, size as u64);
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for Counting<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.reads.add(0, amt as u64);
+    }
+}
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.writes.add(0, size as u64);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: Seek> Seek for Counting<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -0,0 +1,173 @@
+//! Reader and writer wrappers computing the CRC-32 of the data going through them.
+
+use std::io::{self, Read, Write};
+
+/// Lookup table of the reflected CRC-32 polynomial used by zlib, PNG and Ethernet.
+const TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Running CRC-32 state, kept inverted between updates.
+#[derive(Clone, Copy)]
+struct Crc32(u32);
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32(0xFFFFFFFF)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = TABLE[((self.0 ^ byte as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    fn digest(&self) -> u32 {
+        !self.0
+    }
+}
+
+/// A reader wrapper computing the CRC-32 (as used by zlib, PNG and Ethernet) of all the bytes
+/// read through it, so the checksum of a payload can be verified while it is unpacked instead of
+/// reading the data twice.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Crc32Reader, LEUnpacker};
+///
+/// let mut reader = Crc32Reader::new(&b"123456789"[..]);
+/// let mut payload = [0u8; 9];
+/// reader.unpack_exact(&mut payload).unwrap();
+/// assert_eq!(reader.digest(), 0xCBF43926);
+/// ```
+pub struct Crc32Reader<R> {
+    inner: R,
+    crc: Crc32
+}
+
+impl<R> Crc32Reader<R> {
+    /// Wrap `inner` with an empty checksum.
+    pub fn new(inner: R) -> Crc32Reader<R> {
+        Crc32Reader {
+            inner,
+            crc: Crc32::new()
+        }
+    }
+
+    /// Return the CRC-32 of the bytes read so far.
+    pub fn digest(&self) -> u32 {
+        self.crc.digest()
+    }
+
+    /// Restart the checksum from the next byte read.
+    pub fn reset(&mut self) {
+        self.crc = Crc32::new();
+    }
+
+    /// Return a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader. Bytes read through it are not included
+    /// in the checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Crc32Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        self.crc.update(&buf[..size]);
+        Ok(size)
+    }
+}
+
+/// A writer wrapper computing the CRC-32 (as used by zlib, PNG and Ethernet) of all the bytes
+/// written through it, so a checksum can be appended to a payload without packing it twice.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Crc32Writer, LEPacker};
+///
+/// let mut writer = Crc32Writer::new(Vec::new());
+/// writer.pack_all(&b"123456789"[..]).unwrap();
+/// let crc = writer.digest();
+/// let mut payload = writer.into_inner();
+/// payload.pack(crc).unwrap();
+/// assert_eq!(crc, 0xCBF43926);
+/// ```
+pub struct Crc32Writer<W> {
+    inner: W,
+    crc: Crc32
+}
+
+impl<W> Crc32Writer<W> {
+    /// Wrap `inner` with an empty checksum.
+    pub fn new(inner: W) -> Crc32Writer<W> {
+        Crc32Writer {
+            inner,
+            crc: Crc32::new()
+        }
+    }
+
+    /// Return the CRC-32 of the bytes written so far.
+    pub fn digest(&self) -> u32 {
+        self.crc.digest()
+    }
+
+    /// Restart the checksum from the next byte written.
+    pub fn reset(&mut self) {
+        self.crc = Crc32::new();
+    }
+
+    /// Return a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped writer. Bytes written through it are not
+    /// included in the checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for Crc32Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.crc.update(&buf[..size]);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,126 @@
+//! Object-safe reading and writing traits. The generic methods of [`Unpacker`](trait.Unpacker.html)
+//! and [`Packer`](trait.Packer.html) prevent them from being used as trait objects, so heterogeneous
+//! sources are stored as `Box<dyn ReadUnpacker>` or `Box<dyn WritePacker>` instead and the values
+//! are read or written with the free functions of this module.
+
+use std::io::{Read, Write};
+
+use {order, Packed, Endianness, NativeEndian, LittleEndian, BigEndian, Result};
+
+/// Object-safe byte-level reader, implemented for every `Read` type. Values are unpacked from a
+/// `&mut dyn ReadUnpacker` with [`unpack_dyn`](fn.unpack_dyn.html) and the related functions.
+///
+/// ```
+/// use bytepack::{ReadUnpacker, unpack_dyn};
+/// use std::io::Cursor;
+///
+/// let mut sources: Vec<Box<dyn ReadUnpacker>> = vec![
+///     Box::new(Cursor::new(vec![1u8, 0])),
+///     Box::new(&[2u8, 0][..])
+/// ];
+/// for source in sources.iter_mut() {
+///     let value = unpack_dyn::<u16>(&mut **source).unwrap();
+///     assert!(value == 1 || value == 2);
+/// }
+/// ```
+pub trait ReadUnpacker: Read {
+    /// Read exactly `buf.len()` bytes. A `ShortRead` error is returned if not enough bytes could
+    /// be read.
+    fn unpack_bytes(&mut self, buf: &mut [u8]) -> Result<()>;
+
+    /// Read bytes until `EOF` is reached and append them to `buf`. Returns the number of bytes
+    /// read.
+    fn unpack_bytes_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize>;
+}
+
+/// Object-safe byte-level writer, implemented for every `Write` type. Values are packed into a
+/// `&mut dyn WritePacker` with [`pack_dyn`](fn.pack_dyn.html) and the related functions.
+pub trait WritePacker: Write {
+    /// Write all the bytes of `buf`.
+    fn pack_bytes(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+impl<R: Read + ?Sized> ReadUnpacker for R {
+    fn unpack_bytes(&mut self, buf: &mut [u8]) -> Result<()> {
+        order::unpack_exact::<NativeEndian, u8, R>(self, buf)
+    }
+
+    fn unpack_bytes_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        order::unpack_to_end::<NativeEndian, u8, R>(self, buf)
+    }
+}
+
+impl<W: Write + ?Sized> WritePacker for W {
+    fn pack_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        order::pack_all::<NativeEndian, u8, W>(self, buf)
+    }
+}
+
+/// Unpack a single value of type `T` from `r` in the native byte order.
+pub fn unpack_dyn<T: Packed>(r: &mut dyn ReadUnpacker) -> Result<T> {
+    order::unpack::<NativeEndian, T, _>(r)
+}
+
+/// Same as [`unpack_dyn`](fn.unpack_dyn.html) but in the byte order given by `endianness`.
+pub fn unpack_dyn_with<T: Packed>(r: &mut dyn ReadUnpacker, endianness: Endianness) -> Result<T> {
+    match endianness {
+        Endianness::Little => order::unpack::<LittleEndian, T, _>(r),
+        Endianness::Big => order::unpack::<BigEndian, T, _>(r)
+    }
+}
+
+/// Unpack exactly `buf.len()` values of type `T` from `r` in the native byte order.
+pub fn unpack_exact_dyn<T: Packed>(r: &mut dyn ReadUnpacker, buf: &mut [T]) -> Result<()> {
+    order::unpack_exact::<NativeEndian, T, _>(r, buf)
+}
+
+/// Same as [`unpack_exact_dyn`](fn.unpack_exact_dyn.html) but in the byte order given by
+/// `endianness`.
+pub fn unpack_exact_dyn_with<T: Packed>(r: &mut dyn ReadUnpacker, buf: &mut [T], endianness: Endianness) -> Result<()> {
+    match endianness {
+        Endianness::Little => order::unpack_exact::<LittleEndian, T, _>(r, buf),
+        Endianness::Big => order::unpack_exact::<BigEndian, T, _>(r, buf)
+    }
+}
+
+/// Unpack values of type `T` from `r` in the native byte order until `EOF` is reached and append
+/// them to `buf`. Returns the number of values read.
+pub fn unpack_to_end_dyn<T: Packed>(r: &mut dyn ReadUnpacker, buf: &mut Vec<T>) -> Result<usize> {
+    order::unpack_to_end::<NativeEndian, T, _>(r, buf)
+}
+
+/// Same as [`unpack_to_end_dyn`](fn.unpack_to_end_dyn.html) but in the byte order given by
+/// `endianness`.
+pub fn unpack_to_end_dyn_with<T: Packed>(r: &mut dyn ReadUnpacker, buf: &mut Vec<T>, endianness: Endianness) -> Result<usize> {
+    match endianness {
+        Endianness::Little => order::unpack_to_end::<LittleEndian, T, _>(r, buf),
+        Endianness::Big => order::unpack_to_end::<BigEndian, T, _>(r, buf)
+    }
+}
+
+/// Pack `t` into `w` in the native byte order. The value is passed by reference since `pack`
+/// and `pack_ref` can not be told apart on a trait object.
+pub fn pack_dyn<T: Packed>(w: &mut dyn WritePacker, t: &T) -> Result<()> {
+    order::pack_ref::<NativeEndian, T, _>(w, t)
+}
+
+/// Same as [`pack_dyn`](fn.pack_dyn.html) but in the byte order given by `endianness`.
+pub fn pack_dyn_with<T: Packed>(w: &mut dyn WritePacker, t: &T, endianness: Endianness) -> Result<()> {
+    match endianness {
+        Endianness::Little => order::pack_ref::<LittleEndian, T, _>(w, t),
+        Endianness::Big => order::pack_ref::<BigEndian, T, _>(w, t)
+    }
+}
+
+/// Pack all the values of `buf` into `w` in the native byte order.
+pub fn pack_all_dyn<T: Packed>(w: &mut dyn WritePacker, buf: &[T]) -> Result<()> {
+    order::pack_all::<NativeEndian, T, _>(w, buf)
+}
+
+/// Same as [`pack_all_dyn`](fn.pack_all_dyn.html) but in the byte order given by `endianness`.
+pub fn pack_all_dyn_with<T: Packed>(w: &mut dyn WritePacker, buf: &[T], endianness: Endianness) -> Result<()> {
+    match endianness {
+        Endianness::Little => order::pack_all::<LittleEndian, T, _>(w, buf),
+        Endianness::Big => order::pack_all::<BigEndian, T, _>(w, buf)
+    }
+}
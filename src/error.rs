@@ -0,0 +1,290 @@
+//! Error type returned by the packer and unpacker traits.
+
+use std::any::type_name;
+use std::error;
+use std::fmt;
+use std::io;
+use std::mem::align_of;
+use std::result;
+
+use Packed;
+
+/// Result type of the packer and unpacker traits.
+pub type Result<T> = result::Result<T, Error>;
+
+/// The error returned by the packer and unpacker traits. Besides errors of the underlying reader
+/// or writer, which are kept as an `io::Error`, it tells which type was being processed, how many
+/// bytes were expected and available, and at which offset when it is known.
+///
+/// `Error` converts to and from `io::Error`, so the `?` operator works in functions returning
+/// either, and [`kind`](#method.kind) gives the `io::ErrorKind` the error corresponds to.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Unpacker, Error};
+///
+/// let mut reader = &[1u8, 2, 3][..];
+/// match reader.unpack::<u32>() {
+///     Err(Error::ShortRead { type_name, expected, actual, .. }) => {
+///         assert_eq!(type_name, "u32");
+///         assert_eq!((expected, actual), (4, 3));
+///     },
+///     _ => unreachable!()
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error of the underlying reader or writer.
+    Io(io::Error),
+    /// The input ended in the middle of the values being unpacked.
+    ShortRead {
+        /// Name of the type being unpacked.
+        type_name: &'static str,
+        /// Number of bytes needed.
+        expected: usize,
+        /// Number of bytes available.
+        actual: usize,
+        /// Offset where the values start, if known.
+        offset: Option<u64>
+    },
+    /// The output is too small for the values being packed.
+    ShortWrite {
+        /// Name of the type being packed.
+        type_name: &'static str,
+        /// Number of bytes needed.
+        expected: usize,
+        /// Number of bytes available.
+        actual: usize,
+        /// Offset where the values start, if known.
+        offset: Option<u64>
+    },
+    /// Bytes remain after the last whole value, because the input size is not a multiple of the
+    /// size of the type.
+    TrailingBytes {
+        /// Name of the type being unpacked.
+        type_name: &'static str,
+        /// Size of the type.
+        size: usize,
+        /// Number of bytes remaining after the last whole value.
+        trailing: usize,
+        /// Offset where the values start, if known.
+        offset: Option<u64>
+    },
+    /// A buffer is not aligned for the type it is viewed as.
+    Misaligned {
+        /// Name of the type being viewed.
+        type_name: &'static str,
+        /// Alignment required by the type.
+        align: usize
+    },
+    /// The bytes do not form a valid value of the type.
+    InvalidValue {
+        /// Name of the type being unpacked.
+        type_name: &'static str,
+        /// Description of the problem.
+        reason: String,
+        /// Offset of the value, if known.
+        offset: Option<u64>
+    },
+    /// More values, or bytes, than allowed by a limit were found in the input.
+    LimitExceeded {
+        /// Name of the type being unpacked.
+        type_name: &'static str,
+        /// Maximum number of values, or bytes, allowed.
+        limit: u64,
+        /// Whether `limit` is a number of bytes instead of a number of values.
+        in_bytes: bool,
+        /// Offset where the values start, if known.
+        offset: Option<u64>
+    },
+    /// An argument given to the method cannot be used with the type, like a count overflowing
+    /// the address space.
+    InvalidInput {
+        /// Name of the type being processed.
+        type_name: &'static str,
+        /// Description of the problem.
+        reason: String
+    }
+}
+
+impl Error {
+    pub(crate) fn short_read<T: ?Sized>(expected: usize, actual: usize) -> Error {
+        Error::ShortRead {
+            type_name: type_name::<T>(),
+            expected,
+            actual,
+            offset: None
+        }
+    }
+
+    pub(crate) fn short_write<T: ?Sized>(expected: usize, actual: usize) -> Error {
+        Error::ShortWrite {
+            type_name: type_name::<T>(),
+            expected,
+            actual,
+            offset: None
+        }
+    }
+
+    pub(crate) fn trailing_bytes<T: Packed>(trailing: usize) -> Error {
+        Error::TrailingBytes {
+            type_name: type_name::<T>(),
+            size: T::SIZE,
+            trailing,
+            offset: None
+        }
+    }
+
+    pub(crate) fn misaligned<T>() -> Error {
+        Error::Misaligned {
+            type_name: type_name::<T>(),
+            align: align_of::<T>()
+        }
+    }
+
+    pub(crate) fn invalid_value<T: ?Sized, S: Into<String>>(reason: S) -> Error {
+        Error::InvalidValue {
+            type_name: type_name::<T>(),
+            reason: reason.into(),
+            offset: None
+        }
+    }
+
+    pub(crate) fn limit_exceeded<T: ?Sized>(limit: u64) -> Error {
+        Error::LimitExceeded {
+            type_name: type_name::<T>(),
+            limit,
+            in_bytes: false,
+            offset: None
+        }
+    }
+
+    pub(crate) fn byte_limit_exceeded<T: ?Sized>(limit: u64) -> Error {
+        Error::LimitExceeded {
+            type_name: type_name::<T>(),
+            limit,
+            in_bytes: true,
+            offset: None
+        }
+    }
+
+    pub(crate) fn invalid_input<T: ?Sized, S: Into<String>>(reason: S) -> Error {
+        Error::InvalidInput {
+            type_name: type_name::<T>(),
+            reason: reason.into()
+        }
+    }
+
+    /// Return the `io::ErrorKind` corresponding to the error.
+    pub fn kind(&self) -> io::ErrorKind {
+        match *self {
+            Error::Io(ref e) => e.kind(),
+            Error::ShortRead { .. } => io::ErrorKind::UnexpectedEof,
+            Error::ShortWrite { .. } => io::ErrorKind::WriteZero,
+            Error::TrailingBytes { .. } | Error::Misaligned { .. } | Error::InvalidValue { .. } |
+            Error::LimitExceeded { .. } => io::ErrorKind::InvalidData,
+            Error::InvalidInput { .. } => io::ErrorKind::InvalidInput
+        }
+    }
+
+    /// Return the name of the type being processed, or `None` for I/O errors.
+    pub fn type_name(&self) -> Option<&'static str> {
+        match *self {
+            Error::Io(_) => None,
+            Error::ShortRead { type_name, .. } | Error::ShortWrite { type_name, .. } |
+            Error::TrailingBytes { type_name, .. } | Error::Misaligned { type_name, .. } |
+            Error::InvalidValue { type_name, .. } | Error::LimitExceeded { type_name, .. } |
+            Error::InvalidInput { type_name, .. } => Some(type_name)
+        }
+    }
+
+    /// Return the offset where the failing values start, if known.
+    pub fn offset(&self) -> Option<u64> {
+        match *self {
+            Error::ShortRead { offset, .. } | Error::ShortWrite { offset, .. } |
+            Error::TrailingBytes { offset, .. } | Error::InvalidValue { offset, .. } |
+            Error::LimitExceeded { offset, .. } => offset,
+            _ => None
+        }
+    }
+
+    /// Set the offset where the failing values start, unless it is already known.
+    pub fn with_offset(self, position: u64) -> Error {
+        let mut error = self;
+        match error {
+            Error::ShortRead { ref mut offset, .. } | Error::ShortWrite { ref mut offset, .. } |
+            Error::TrailingBytes { ref mut offset, .. } | Error::InvalidValue { ref mut offset, .. } |
+            Error::LimitExceeded { ref mut offset, .. } => {
+                offset.get_or_insert(position);
+            },
+            _ => {}
+        }
+        error
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => return e.fmt(f),
+            Error::ShortRead { type_name, expected, actual, .. } => write!(
+                f, "reached EOF after {} of the {} bytes of {}", actual, expected, type_name
+            )?,
+            Error::ShortWrite { type_name, expected, actual, .. } => write!(
+                f, "only {} of the {} bytes of {} could be written", actual, expected, type_name
+            )?,
+            Error::TrailingBytes { type_name, size, trailing, .. } => write!(
+                f, "{} bytes remain after the last {} of {} bytes", trailing, type_name, size
+            )?,
+            Error::Misaligned { type_name, align } => write!(
+                f, "buffer is not aligned on {} bytes for {}", align, type_name
+            )?,
+            Error::InvalidValue { type_name, ref reason, .. } => write!(
+                f, "invalid {}: {}", type_name, reason
+            )?,
+            Error::LimitExceeded { type_name, limit, in_bytes: true, .. } => write!(
+                f, "{} is longer than the limit of {} bytes", type_name, limit
+            )?,
+            Error::LimitExceeded { type_name, limit, .. } => write!(
+                f, "more than {} values of {} were found", limit, type_name
+            )?,
+            Error::InvalidInput { type_name, ref reason } => write!(
+                f, "invalid input for {}: {}", type_name, reason
+            )?
+        }
+        if let Some(offset) = self.offset() {
+            write!(f, " at offset {}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    /// Wrap `e`, or recover the `Error` it carries if it was converted from one.
+    fn from(e: io::Error) -> Error {
+        if e.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            return *e.into_inner().unwrap().downcast::<Error>().unwrap();
+        }
+        Error::Io(e)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> io::Error {
+        match e {
+            Error::Io(e) => e,
+            e => io::Error::new(e.kind(), e)
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Unpacking and packing methods with the byte order in their name, for code mixing several byte
+//! orders.
+
+use std::io::{Read, Write};
+
+use {order, Packed, NativeEndian, LittleEndian, BigEndian, Result};
+
+/// Unpacking methods with explicit `_le`, `_be` and `_ne` (native endian) suffixes, so code
+/// reading values in several byte orders does not depend on which trait family is in scope. It is
+/// implemented for all the types implementing `Read`.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::ExplicitUnpacker;
+///
+/// let mut data = &[0x12u8, 0x34, 0x12, 0x34][..];
+/// assert_eq!(data.unpack_be::<u16>().unwrap(), 0x1234);
+/// assert_eq!(data.unpack_le::<u16>().unwrap(), 0x3412);
+/// ```
+pub trait ExplicitUnpacker {
+    /// Unpack a single value of type `T` in little endian.
+    fn unpack_le<T: Packed>(&mut self) -> Result<T>;
+    /// Unpack a single value of type `T` in big endian.
+    fn unpack_be<T: Packed>(&mut self) -> Result<T>;
+    /// Unpack a single value of type `T` in the native byte order.
+    fn unpack_ne<T: Packed>(&mut self) -> Result<T>;
+
+    /// Unpack exactly `buf.len()` values of type `T` in little endian.
+    fn unpack_exact_le<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    /// Unpack exactly `buf.len()` values of type `T` in big endian.
+    fn unpack_exact_be<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    /// Unpack exactly `buf.len()` values of type `T` in the native byte order.
+    fn unpack_exact_ne<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+
+    /// Unpack `count` values of type `T` in little endian into a new `Vec`.
+    fn unpack_n_le<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+    /// Unpack `count` values of type `T` in big endian into a new `Vec`.
+    fn unpack_n_be<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+    /// Unpack `count` values of type `T` in the native byte order into a new `Vec`.
+    fn unpack_n_ne<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+
+    /// Unpack values of type `T` in little endian until `EOF` is reached and append them to
+    /// `buf`. Returns the number of values read.
+    fn unpack_to_end_le<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
+    /// Unpack values of type `T` in big endian until `EOF` is reached and append them to `buf`.
+    /// Returns the number of values read.
+    fn unpack_to_end_be<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
+    /// Unpack values of type `T` in the native byte order until `EOF` is reached and append them
+    /// to `buf`. Returns the number of values read.
+    fn unpack_to_end_ne<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
+}
+
+/// Packing methods with explicit `_le`, `_be` and `_ne` (native endian) suffixes, so code
+/// writing values in several byte orders does not depend on which trait family is in scope. It is
+/// implemented for all the types implementing `Write`.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::ExplicitPacker;
+///
+/// let mut data = Vec::new();
+/// data.pack_be(0x1234u16).unwrap();
+/// data.pack_all_le(&[0x1234u16]).unwrap();
+/// assert_eq!(data, [0x12, 0x34, 0x34, 0x12]);
+/// ```
+pub trait ExplicitPacker {
+    /// Pack a single value in little endian.
+    fn pack_le<T: Packed>(&mut self, t: T) -> Result<()>;
+    /// Pack a single value in big endian.
+    fn pack_be<T: Packed>(&mut self, t: T) -> Result<()>;
+    /// Pack a single value in the native byte order.
+    fn pack_ne<T: Packed>(&mut self, t: T) -> Result<()>;
+
+    /// Pack a single value passed by reference in little endian.
+    fn pack_ref_le<T: Packed>(&mut self, t: &T) -> Result<()>;
+    /// Pack a single value passed by reference in big endian.
+    fn pack_ref_be<T: Packed>(&mut self, t: &T) -> Result<()>;
+    /// Pack a single value passed by reference in the native byte order.
+    fn pack_ref_ne<T: Packed>(&mut self, t: &T) -> Result<()>;
+
+    /// Pack all the values of `buf` in little endian.
+    fn pack_all_le<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    /// Pack all the values of `buf` in big endian.
+    fn pack_all_be<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    /// Pack all the values of `buf` in the native byte order.
+    fn pack_all_ne<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+}
+
+impl<R: Read + ?Sized> ExplicitUnpacker for R {
+    fn unpack_le<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<LittleEndian, T, R>(self)
+    }
+
+    fn unpack_be<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_ne<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<NativeEndian, T, R>(self)
+    }
+
+    fn unpack_exact_le<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_exact_be<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_exact_ne<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn unpack_n_le<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<LittleEndian, T, R>(self, count)
+    }
+
+    fn unpack_n_be<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<BigEndian, T, R>(self, count)
+    }
+
+    fn unpack_n_ne<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<NativeEndian, T, R>(self, count)
+    }
+
+    fn unpack_to_end_le<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_to_end_be<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_to_end_ne<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<NativeEndian, T, R>(self, buf)
+    }
+}
+
+impl<W: Write + ?Sized> ExplicitPacker for W {
+    fn pack_le<T: Packed>(&mut self, t: T) -> Result<()> {
+        order::pack::<LittleEndian, T, W>(self, t)
+    }
+
+    fn pack_be<T: Packed>(&mut self, t: T) -> Result<()> {
+        order::pack::<BigEndian, T, W>(self, t)
+    }
+
+    fn pack_ne<T: Packed>(&mut self, t: T) -> Result<()> {
+        order::pack::<NativeEndian, T, W>(self, t)
+    }
+
+    fn pack_ref_le<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<LittleEndian, T, W>(self, t)
+    }
+
+    fn pack_ref_be<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<BigEndian, T, W>(self, t)
+    }
+
+    fn pack_ref_ne<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<NativeEndian, T, W>(self, t)
+    }
+
+    fn pack_all_le<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<LittleEndian, T, W>(self, buf)
+    }
+
+    fn pack_all_be<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<BigEndian, T, W>(self, buf)
+    }
+
+    fn pack_all_ne<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<NativeEndian, T, W>(self, buf)
+    }
+}
@@ -0,0 +1,91 @@
+//! Floating point values rejecting NaN and infinities when unpacked.
+
+use std::fmt;
+
+use {Packed, EndianSwitch, PackedValidate};
+
+/// A floating point value, `f32` or `f64`, which is neither NaN nor infinite. Unpacking it with
+/// the `unpack_validated` or `unpack_exact_validated` methods returns an `InvalidValue` error,
+/// whose kind is `InvalidData`, instead of propagating NaN or infinities read from a corrupted
+/// input.
+///
+/// The other unpacking methods do not check the values, so [`get`](#method.get) may then return
+/// a value which is not finite.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Finite, LEUnpacker, Error};
+///
+/// let mut reader = &[0u8, 0, 0xC0, 0x3F, 0, 0, 0xC0, 0x7F][..];
+/// let mut samples = [Finite::new(0f32).unwrap(); 2];
+/// match reader.unpack_exact_validated(&mut samples) {
+///     Err(Error::InvalidValue { reason, .. }) => assert_eq!(reason, "value 1: NaN is not finite"),
+///     _ => unreachable!()
+/// }
+/// assert_eq!(samples[0].get(), 1.5);
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Finite<T>(T);
+
+impl<T: Copy + fmt::Debug> fmt::Debug for Finite<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The floating point types, sealed since it is not exported.
+pub trait Float: Packed + Copy + fmt::Display {
+    fn is_finite(self) -> bool;
+}
+
+impl Float for f32 {
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+}
+
+impl Float for f64 {
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+impl<T: Float> Finite<T> {
+    /// Create a new value, or return `None` if `value` is NaN or infinite.
+    pub fn new(value: T) -> Option<Finite<T>> {
+        if value.is_finite() {
+            Some(Finite(value))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Return the value.
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Float> EndianSwitch for Finite<T> {
+    fn switch_endianness(&mut self) {
+        self.0.switch_endianness();
+    }
+}
+
+// Safe because `Finite` is a transparent wrapper around a float, any bit pattern is a valid
+// value even though only finite ones pass the validation.
+unsafe impl<T: Float> Packed for Finite<T> {}
+
+impl<T: Float> PackedValidate for Finite<T> {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_finite() {
+            Ok(())
+        }
+        else {
+            Err(format!("{} is not finite", self.0))
+        }
+    }
+}
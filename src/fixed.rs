@@ -0,0 +1,113 @@
+//! Fixed-point numbers.
+
+use std::fmt;
+
+use {Packed, EndianSwitch, PackedValidate, CDecl};
+
+/// The integer types storing fixed-point numbers, sealed since it is not exported.
+pub trait FixedBits: Packed + Copy {
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! fixed_bits {
+    ($($ty:ty),*) => {
+        $(
+            impl FixedBits for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> $ty {
+                    // float to integer casts saturate and map NaN to 0
+                    value.round() as $ty
+                }
+            }
+        )*
+    }
+}
+
+fixed_bits!(i8, u8, i16, u16, i32, u32, i64, u64);
+
+/// A fixed-point number stored as an integer of type `I` with `FRAC` fractional bits, its value
+/// being the integer divided by `2^FRAC`. The [`Q15`](type.Q15.html) and [`Q31`](type.Q31.html)
+/// aliases are the formats commonly used by sensors and DSPs.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Q15, LEUnpacker};
+///
+/// let mut reader = &[0x00u8, 0x40, 0x00, 0xC0][..];
+/// let gains : [Q15; 2] = reader.unpack().unwrap();
+/// assert_eq!(gains[0].to_f32(), 0.5);
+/// assert_eq!(gains[1].to_f32(), -0.5);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[repr(transparent)]
+pub struct Fixed<I, const FRAC: u32>(I);
+
+/// A signed 16 bits fixed-point number with 15 fractional bits, in the range [-1, 1).
+pub type Q15 = Fixed<i16, 15>;
+
+/// A signed 32 bits fixed-point number with 31 fractional bits, in the range [-1, 1).
+pub type Q31 = Fixed<i32, 31>;
+
+impl<I: FixedBits, const FRAC: u32> Fixed<I, FRAC> {
+    /// Create a number from its integer representation.
+    pub fn from_bits(bits: I) -> Fixed<I, FRAC> {
+        Fixed(bits)
+    }
+
+    /// Return the integer representation of the number.
+    pub fn to_bits(self) -> I {
+        self.0
+    }
+
+    /// Create the number nearest to `value`, saturating if it is out of range.
+    pub fn from_f64(value: f64) -> Fixed<I, FRAC> {
+        Fixed(I::from_f64(value * (FRAC as f64).exp2()))
+    }
+
+    /// Create the number nearest to `value`, saturating if it is out of range.
+    pub fn from_f32(value: f32) -> Fixed<I, FRAC> {
+        Fixed::from_f64(value as f64)
+    }
+
+    /// Return the value of the number.
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64() / (FRAC as f64).exp2()
+    }
+
+    /// Return the value of the number, rounded to the nearest `f32`.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+}
+
+impl<I: FixedBits, const FRAC: u32> fmt::Debug for Fixed<I, FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_f64().fmt(f)
+    }
+}
+
+impl<I: FixedBits, const FRAC: u32> EndianSwitch for Fixed<I, FRAC> {
+    fn switch_endianness(&mut self) {
+        self.0.switch_endianness();
+    }
+}
+
+// Safe because `Fixed` is a transparent wrapper around an integer.
+unsafe impl<I: FixedBits, const FRAC: u32> Packed for Fixed<I, FRAC> {}
+
+impl<I: FixedBits, const FRAC: u32> PackedValidate for Fixed<I, FRAC> {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<I: FixedBits + CDecl, const FRAC: u32> CDecl for Fixed<I, FRAC> {
+    fn c_decl(name: &str) -> String {
+        I::c_decl(name)
+    }
+}
@@ -0,0 +1,34 @@
+//! Integer types usable as the length prefix of a frame.
+
+use std::convert::TryFrom;
+
+use Packed;
+
+/// An unsigned integer type storing the length in bytes of a payload, as used by the `*_tlv` and
+/// `*_framed` methods of the packer and unpacker traits. It is implemented for `u8`, `u16`, `u32`
+/// and `u64`.
+pub trait LengthPrefix: Packed + Copy {
+    /// Convert `len` to a length prefix, or return `None` if it does not fit.
+    fn from_len(len: usize) -> Option<Self>;
+
+    /// Convert the length prefix to a length, or return `None` if it does not fit in a `usize`.
+    fn to_len(self) -> Option<usize>;
+}
+
+macro_rules! length_prefix {
+    ($($ty:ty),*) => {
+        $(
+            impl LengthPrefix for $ty {
+                fn from_len(len: usize) -> Option<$ty> {
+                    <$ty>::try_from(len).ok()
+                }
+
+                fn to_len(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+            }
+        )*
+    }
+}
+
+length_prefix!(u8, u16, u32, u64);
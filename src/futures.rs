@@ -0,0 +1,216 @@
+//! Asynchronous packer and unpacker traits for the `futures-io` traits, used by runtimes like
+//! async-std and smol. They mirror the Tokio flavored [`AsyncUnpacker`](../trait.AsyncUnpacker.html)
+//! and [`AsyncPacker`](../trait.AsyncPacker.html) of the `tokio` feature.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::slice;
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+use {Packed, Endianness, Result};
+use partial::{UnpackState, UnpackExactState, PackState, PackAllState};
+
+/// `AsyncUnpacker` provides the main methods of [`Unpacker`](../trait.Unpacker.html) for the
+/// `AsyncRead` types of `futures-io`. Each method returns a future which resolves once the whole value has been
+/// read. It is only available with the `futures-io` feature.
+///
+/// Dropping a future before it completes loses the bytes it already consumed.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::futures::AsyncUnpacker;
+/// use futures_io::AsyncRead;
+///
+/// async fn read_samples<R: AsyncRead + Unpin>(stream: &mut R) -> bytepack::Result<Vec<f32>> {
+///     let count : u32 = stream.unpack().await?;
+///     let mut samples = vec![0f32; count as usize];
+///     stream.unpack_exact(&mut samples[..]).await?;
+///     Ok(samples)
+/// }
+/// ```
+pub trait AsyncUnpacker {
+    /// Unpack a single value of type `T`.
+    fn unpack<T: Packed>(&mut self) -> UnpackFuture<'_, Self, T>;
+
+    /// Unpack exactly `buf.len()` values of type `T`. An error is returned if not enough bytes
+    /// could be read.
+    fn unpack_exact<'a, T: Packed>(&'a mut self, buf: &'a mut [T]) -> UnpackExactFuture<'a, Self, T>;
+
+    /// Same as [`unpack`](#tymethod.unpack) but in the byte order given by `endianness`.
+    fn unpack_with<T: Packed>(&mut self, endianness: Endianness) -> UnpackFuture<'_, Self, T>;
+
+    /// Same as [`unpack_exact`](#tymethod.unpack_exact) but in the byte order given by
+    /// `endianness`.
+    fn unpack_exact_with<'a, T: Packed>(&'a mut self, buf: &'a mut [T], endianness: Endianness) -> UnpackExactFuture<'a, Self, T>;
+}
+
+/// `AsyncPacker` provides the main methods of [`Packer`](../trait.Packer.html) for the
+/// `AsyncWrite` types of `futures-io`. Each method returns a future which resolves once the whole data has been
+/// written. It is only available with the `futures-io` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::futures::AsyncPacker;
+/// use futures_io::AsyncWrite;
+///
+/// async fn write_samples<W: AsyncWrite + Unpin>(stream: &mut W, samples: &[f32]) -> bytepack::Result<()> {
+///     stream.pack(samples.len() as u32).await?;
+///     stream.pack_all(samples).await
+/// }
+/// ```
+pub trait AsyncPacker {
+    /// Pack a single value.
+    fn pack<T: Packed>(&mut self, t: T) -> PackFuture<'_, Self, T>;
+
+    /// Pack a single value passed by reference. Large values are written from their original
+    /// location instead of being moved into the future.
+    fn pack_ref<'a, T: Packed>(&'a mut self, t: &'a T) -> PackAllFuture<'a, Self, T>;
+
+    /// Pack all the values of `buf`.
+    fn pack_all<'a, T: Packed>(&'a mut self, buf: &'a [T]) -> PackAllFuture<'a, Self, T>;
+
+    /// Same as [`pack`](#tymethod.pack) but in the byte order given by `endianness`.
+    fn pack_with<T: Packed>(&mut self, t: T, endianness: Endianness) -> PackFuture<'_, Self, T>;
+
+    /// Same as [`pack_ref`](#tymethod.pack_ref) but in the byte order given by `endianness`.
+    fn pack_ref_with<'a, T: Packed>(&'a mut self, t: &'a T, endianness: Endianness) -> PackAllFuture<'a, Self, T>;
+
+    /// Same as [`pack_all`](#tymethod.pack_all) but in the byte order given by `endianness`.
+    fn pack_all_with<'a, T: Packed>(&'a mut self, buf: &'a [T], endianness: Endianness) -> PackAllFuture<'a, Self, T>;
+}
+
+/// Future returned by [`AsyncUnpacker::unpack`](trait.AsyncUnpacker.html#tymethod.unpack).
+pub struct UnpackFuture<'a, R: ?Sized + 'a, T> {
+    reader: &'a mut R,
+    state: UnpackState<T>
+}
+
+/// Future returned by
+/// [`AsyncUnpacker::unpack_exact`](trait.AsyncUnpacker.html#tymethod.unpack_exact).
+pub struct UnpackExactFuture<'a, R: ?Sized + 'a, T: 'a> {
+    reader: &'a mut R,
+    buf: &'a mut [T],
+    state: UnpackExactState
+}
+
+/// Future returned by [`AsyncPacker::pack`](trait.AsyncPacker.html#tymethod.pack).
+pub struct PackFuture<'a, W: ?Sized + 'a, T> {
+    writer: &'a mut W,
+    state: PackState<T>
+}
+
+/// Future returned by [`AsyncPacker::pack_all`](trait.AsyncPacker.html#tymethod.pack_all) and
+/// [`AsyncPacker::pack_ref`](trait.AsyncPacker.html#tymethod.pack_ref).
+pub struct PackAllFuture<'a, W: ?Sized + 'a, T: 'a> {
+    writer: &'a mut W,
+    buf: &'a [T],
+    state: PackAllState
+}
+
+// The futures never rely on the address of the values they hold.
+impl<'a, R: ?Sized, T> Unpin for UnpackFuture<'a, R, T> {}
+impl<'a, R: ?Sized, T> Unpin for UnpackExactFuture<'a, R, T> {}
+impl<'a, W: ?Sized, T> Unpin for PackFuture<'a, W, T> {}
+impl<'a, W: ?Sized, T> Unpin for PackAllFuture<'a, W, T> {}
+
+impl<'a, R: AsyncRead + Unpin + ?Sized, T: Packed> Future for UnpackFuture<'a, R, T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<T>> {
+        let this = self.get_mut();
+        let reader = &mut *this.reader;
+        this.state.poll(|buf| Pin::new(&mut *reader).poll_read(cx, buf))
+    }
+}
+
+impl<'a, R: AsyncRead + Unpin + ?Sized, T: Packed> Future for UnpackExactFuture<'a, R, T> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        let reader = &mut *this.reader;
+        this.state.poll(this.buf, |buf| Pin::new(&mut *reader).poll_read(cx, buf))
+    }
+}
+
+impl<'a, W: AsyncWrite + Unpin + ?Sized, T: Packed> Future for PackFuture<'a, W, T> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        let writer = &mut *this.writer;
+        this.state.poll(|buf| Pin::new(&mut *writer).poll_write(cx, buf))
+    }
+}
+
+impl<'a, W: AsyncWrite + Unpin + ?Sized, T: Packed> Future for PackAllFuture<'a, W, T> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        let writer = &mut *this.writer;
+        this.state.poll(this.buf, |buf| Pin::new(&mut *writer).poll_write(cx, buf))
+    }
+}
+
+impl<R> AsyncUnpacker for R where R: AsyncRead + Unpin + ?Sized {
+    fn unpack<T: Packed>(&mut self) -> UnpackFuture<'_, R, T> {
+        self.unpack_with(Endianness::native())
+    }
+
+    fn unpack_exact<'a, T: Packed>(&'a mut self, buf: &'a mut [T]) -> UnpackExactFuture<'a, R, T> {
+        self.unpack_exact_with(buf, Endianness::native())
+    }
+
+    fn unpack_with<T: Packed>(&mut self, endianness: Endianness) -> UnpackFuture<'_, R, T> {
+        UnpackFuture {
+            reader: self,
+            state: UnpackState::new(endianness)
+        }
+    }
+
+    fn unpack_exact_with<'a, T: Packed>(&'a mut self, buf: &'a mut [T], endianness: Endianness) -> UnpackExactFuture<'a, R, T> {
+        UnpackExactFuture {
+            reader: self,
+            buf,
+            state: UnpackExactState::new(endianness)
+        }
+    }
+}
+
+impl<W> AsyncPacker for W where W: AsyncWrite + Unpin + ?Sized {
+    fn pack<T: Packed>(&mut self, t: T) -> PackFuture<'_, W, T> {
+        self.pack_with(t, Endianness::native())
+    }
+
+    fn pack_ref<'a, T: Packed>(&'a mut self, t: &'a T) -> PackAllFuture<'a, W, T> {
+        self.pack_ref_with(t, Endianness::native())
+    }
+
+    fn pack_all<'a, T: Packed>(&'a mut self, buf: &'a [T]) -> PackAllFuture<'a, W, T> {
+        self.pack_all_with(buf, Endianness::native())
+    }
+
+    fn pack_with<T: Packed>(&mut self, t: T, endianness: Endianness) -> PackFuture<'_, W, T> {
+        PackFuture {
+            writer: self,
+            state: PackState::new(t, endianness)
+        }
+    }
+
+    fn pack_ref_with<'a, T: Packed>(&'a mut self, t: &'a T, endianness: Endianness) -> PackAllFuture<'a, W, T> {
+        self.pack_all_with(slice::from_ref(t), endianness)
+    }
+
+    fn pack_all_with<'a, T: Packed>(&'a mut self, buf: &'a [T], endianness: Endianness) -> PackAllFuture<'a, W, T> {
+        PackAllFuture {
+            writer: self,
+            buf,
+            state: PackAllState::new(endianness)
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Reader and writer wrappers hashing the data going through them with a RustCrypto `Digest`.
+
+use std::io::{self, Read, Write};
+use std::mem;
+
+use digest::{Digest, Output};
+
+/// A reader wrapper feeding all the bytes read through it to a hash function implementing the
+/// RustCrypto `Digest` trait, like `sha2::Sha256`, so a payload can be verified while it is
+/// unpacked. It is only available with the `digest` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{DigestReader, LEUnpacker};
+/// use sha2::{Sha256, Digest};
+///
+/// let mut reader = DigestReader::<_, Sha256>::new(&[1u8, 0, 0, 0][..]);
+/// let value : u32 = reader.unpack().unwrap();
+/// assert_eq!(value, 1);
+/// assert_eq!(reader.finalize(), Sha256::digest([1u8, 0, 0, 0]));
+/// ```
+pub struct DigestReader<R, D> {
+    inner: R,
+    digest: D
+}
+
+impl<R, D: Digest> DigestReader<R, D> {
+    /// Wrap `inner` with a new hash function.
+    pub fn new(inner: R) -> DigestReader<R, D> {
+        DigestReader {
+            inner,
+            digest: D::new()
+        }
+    }
+
+    /// Return the hash of the bytes read so far and restart hashing from the next byte read.
+    pub fn finalize(&mut self) -> Output<D> {
+        mem::replace(&mut self.digest, D::new()).finalize()
+    }
+
+    /// Return a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader. Bytes read through it are not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, D: Digest> Read for DigestReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        self.digest.update(&buf[..size]);
+        Ok(size)
+    }
+}
+
+/// A writer wrapper feeding all the bytes written through it to a hash function implementing the
+/// RustCrypto `Digest` trait, like `sha2::Sha256`, so the hash of a payload is computed while it
+/// is packed. It is only available with the `digest` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{DigestWriter, LEPacker};
+/// use sha2::Sha256;
+///
+/// let mut writer = DigestWriter::<_, Sha256>::new(Vec::new());
+/// writer.pack_all(&[1.5f32, 2.5]).unwrap();
+/// let hash = writer.finalize();
+/// writer.pack_all(&hash[..]).unwrap();
+/// ```
+pub struct DigestWriter<W, D> {
+    inner: W,
+    digest: D
+}
+
+impl<W, D: Digest> DigestWriter<W, D> {
+    /// Wrap `inner` with a new hash function.
+    pub fn new(inner: W) -> DigestWriter<W, D> {
+        DigestWriter {
+            inner,
+            digest: D::new()
+        }
+    }
+
+    /// Return the hash of the bytes written so far and restart hashing from the next byte
+    /// written.
+    pub fn finalize(&mut self) -> Output<D> {
+        mem::replace(&mut self.digest, D::new()).finalize()
+    }
+
+    /// Return a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped writer. Bytes written through it are not
+    /// hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, D: Digest> Write for DigestWriter<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.digest.update(&buf[..size]);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
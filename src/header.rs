@@ -0,0 +1,69 @@
+//! Variable-size records whose size is given by a fixed-size header.
+
+use {Packed, Endianness, SliceUnpacker, Result};
+
+/// A variable-size record starting with a fixed-size header from which the size of the rest of
+/// the record, its body, can be derived. The crate handles the two-phase reading and writing
+/// through [`unpack_header_packed`](trait.Unpacker.html#tymethod.unpack_header_packed) and
+/// [`pack_header_packed`](trait.Packer.html#tymethod.pack_header_packed), so an implementation
+/// only has to decode and encode the body.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+/// use bytepack::{HeaderPacked, SliceUnpacker, Endianness, Result, BEPacker, BEUnpacker};
+///
+/// struct Message {
+///     kind: u8,
+///     text: String
+/// }
+///
+/// impl HeaderPacked for Message {
+///     // the kind of the message and the length of the text
+///     type Header = [u8; 2];
+///
+///     fn body_len(header: &[u8; 2]) -> usize {
+///         header[1] as usize
+///     }
+///
+///     fn header(&self) -> [u8; 2] {
+///         [self.kind, self.text.len() as u8]
+///     }
+///
+///     fn unpack_body(header: [u8; 2], body: &mut SliceUnpacker) -> Result<Message> {
+///         let mut text = String::new();
+///         body.read_to_string(&mut text)?;
+///         Ok(Message { kind: header[0], text })
+///     }
+///
+///     fn pack_body(&self, body: &mut Vec<u8>, _endianness: Endianness) -> Result<()> {
+///         body.extend_from_slice(self.text.as_bytes());
+///         Ok(())
+///     }
+/// }
+///
+/// let mut buffer = Vec::new();
+/// buffer.pack_header_packed(&Message { kind: 1, text: "hello".to_string() }).unwrap();
+/// assert_eq!(buffer, b"\x01\x05hello");
+/// let message : Message = (&buffer[..]).unpack_header_packed(256).unwrap();
+/// assert_eq!(message.text, "hello");
+/// ```
+pub trait HeaderPacked: Sized {
+    /// Type of the fixed-size header.
+    type Header: Packed;
+
+    /// Return the size in bytes of the body following `header`.
+    fn body_len(header: &Self::Header) -> usize;
+
+    /// Return the header of the record, giving the size of the body written by
+    /// [`pack_body`](#tymethod.pack_body).
+    fn header(&self) -> Self::Header;
+
+    /// Build the record from its header and its body, whose bytes must all be unpacked. The
+    /// byte order of the body is the one of `body`.
+    fn unpack_body(header: Self::Header, body: &mut SliceUnpacker) -> Result<Self>;
+
+    /// Append the body of the record to `body` in the `endianness` byte order.
+    fn pack_body(&self, body: &mut Vec<u8>, endianness: Endianness) -> Result<()>;
+}
@@ -0,0 +1,100 @@
+//! Packed IP addresses.
+
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use {Packed, EndianSwitch, PackedValidate, CDecl};
+
+macro_rules! packed_ip {
+    ($(#[$doc:meta])* $name:ident, $addr:ident, $len:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        #[repr(transparent)]
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            /// Create a packed address from `addr`.
+            pub fn new(addr: $addr) -> $name {
+                $name(addr.octets())
+            }
+
+            /// Return the address.
+            pub fn get(self) -> $addr {
+                $addr::from(self.0)
+            }
+
+            /// Return the octets of the address in network order.
+            pub fn octets(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<$addr> for $name {
+            fn from(addr: $addr) -> $name {
+                $name::new(addr)
+            }
+        }
+
+        impl From<$name> for $addr {
+            fn from(addr: $name) -> $addr {
+                addr.get()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+
+        impl EndianSwitch for $name {
+            fn switch_endianness(&mut self) {
+                // The octets are always in network order.
+            }
+        }
+
+        // Safe because the address is a transparent wrapper around an array of bytes.
+        unsafe impl Packed for $name {}
+
+        impl PackedValidate for $name {
+            fn validate(&self) -> Result<(), String> {
+                Ok(())
+            }
+        }
+
+        impl CDecl for $name {
+            fn c_decl(name: &str) -> String {
+                <[u8; $len]>::c_decl(name)
+            }
+        }
+    }
+}
+
+packed_ip!(
+    /// An IPv4 address stored as its 4 octets in network order, whichever packer or unpacker
+    /// trait is used, which converts to and from `std::net::Ipv4Addr`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use bytepack::{PackedIpv4, LEUnpacker};
+    ///
+    /// let mut reader = &[192u8, 168, 0, 1][..];
+    /// let addr : PackedIpv4 = reader.unpack().unwrap();
+    /// assert_eq!(addr.get(), Ipv4Addr::new(192, 168, 0, 1));
+    /// ```
+    PackedIpv4, Ipv4Addr, 4
+);
+
+packed_ip!(
+    /// An IPv6 address stored as its 16 octets in network order, whichever packer or unpacker
+    /// trait is used, which converts to and from `std::net::Ipv6Addr`.
+    PackedIpv6, Ipv6Addr, 16
+);
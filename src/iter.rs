@@ -0,0 +1,235 @@
+//! Iterators over packed values.
+
+use std::io::{Read, ErrorKind};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+
+use {order, Packed, Order, Error, Result};
+
+/// Iterator unpacking values of type `T` stored in the `O` byte order until `EOF` is reached. It
+/// is returned by the `unpack_iter` method of the unpacker traits.
+///
+/// If `EOF` is reached in the middle of a value, a `ShortRead` error is yielded and the
+/// iteration stops. The iteration also stops after any other error.
+pub struct UnpackIter<'a, R: 'a, T, O> {
+    reader: &'a mut R,
+    done: bool,
+    marker: PhantomData<(T, O)>
+}
+
+impl<'a, R: Read, T: Packed, O: Order> UnpackIter<'a, R, T, O> {
+    pub(crate) fn new(reader: &'a mut R) -> UnpackIter<'a, R, T, O> {
+        UnpackIter {
+            reader,
+            done: false,
+            marker: PhantomData
+        }
+    }
+}
+
+impl<'a, R: Read, T: Packed, O: Order> Iterator for UnpackIter<'a, R, T, O> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.done {
+            return None;
+        }
+        match order::unpack_maybe::<O, T, R>(self.reader) {
+            Ok(Some(t)) => Some(Ok(t)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Default size in bytes of the buffer of [`Records`](struct.Records.html).
+pub const RECORDS_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Iterator unpacking values of type `T` stored in the `O` byte order until `EOF` is reached,
+/// like [`UnpackIter`](struct.UnpackIter.html), but reading them by batches into an internal
+/// buffer so iterating over many small values does not require one read per value. It is
+/// returned by the `records` and `records_with_capacity` methods of the unpacker traits.
+///
+/// Because of the buffering, more values than the ones yielded may have been consumed from the
+/// underlying reader when the iterator is dropped.
+pub struct Records<'a, R: 'a, T, O> {
+    reader: &'a mut R,
+    buf: Vec<MaybeUninit<T>>,
+    // index of the next value to yield
+    start: usize,
+    // number of complete values in buf
+    end: usize,
+    // number of bytes of an incomplete value following the complete ones
+    partial: usize,
+    done: bool,
+    marker: PhantomData<O>
+}
+
+impl<'a, R: Read, T: Packed, O: Order> Records<'a, R, T, O> {
+    pub(crate) fn new(reader: &'a mut R, capacity: usize) -> Records<'a, R, T, O> {
+        let len = (capacity / T::SIZE.max(1)).max(1);
+        // the buffer is zeroed so it can be viewed as bytes before the values are read
+        let buf = (0..len).map(|_| MaybeUninit::zeroed()).collect();
+        Records {
+            reader,
+            buf,
+            start: 0,
+            end: 0,
+            partial: 0,
+            done: false,
+            marker: PhantomData
+        }
+    }
+
+    /// Read from the reader until the buffer holds at least one complete value or `EOF` is
+    /// reached, keeping the bytes of the incomplete value left by the previous batch.
+    fn refill(&mut self) -> Result<()> {
+        let size = T::SIZE;
+        // safe because we build a slice of exactly buf.len() * T::SIZE bytes
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut u8, self.buf.len() * T::SIZE)
+        };
+        bytes.copy_within(self.end * size..self.end * size + self.partial, 0);
+        let mut filled = self.partial;
+        self.start = 0;
+        self.end = 0;
+        self.partial = 0;
+        while filled < size {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {},
+                Err(e) => return Err(e.into())
+            }
+        }
+        self.end = filled / size;
+        self.partial = filled % size;
+        // safe because the first end values have been entirely read from the reader
+        O::convert_slice(unsafe {
+            slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, self.end)
+        });
+        if self.end == 0 && self.partial != 0 {
+            return Err(Error::short_read::<T>(size, self.partial));
+        }
+        Ok(())
+    }
+}
+
+impl<'a, R: Read, T: Packed, O: Order> Iterator for Records<'a, R, T, O> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.done {
+            return None;
+        }
+        if T::SIZE == 0 {
+            self.done = true;
+            return Some(Err(Error::invalid_input::<T, _>("cannot read zero-sized records")));
+        }
+        if self.start == self.end {
+            if let Err(e) = self.refill() {
+                self.done = true;
+                return Some(Err(e));
+            }
+            if self.end == 0 {
+                self.done = true;
+                return None;
+            }
+        }
+        self.start += 1;
+        // safe because the value is initialized and is moved out of the buffer only once, the
+        // buffer never dropping its content
+        Some(Ok(unsafe { ptr::read(self.buf[self.start - 1].as_ptr()) }))
+    }
+}
+
+/// Reader of values of type `T` stored in the `O` byte order by chunks of a fixed number of
+/// values, reusing the same buffer for every chunk so arbitrarily large inputs can be processed
+/// with a constant amount of memory. It is returned by the `unpack_chunks` method of the unpacker
+/// traits.
+///
+/// Since the chunks borrow the internal buffer, this is not an `Iterator`; chunks are obtained
+/// with [`next_chunk`](#method.next_chunk) instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use bytepack::Unpacker;
+/// use std::fs::File;
+///
+/// let mut file = File::open("samples").unwrap();
+/// let mut chunks = file.unpack_chunks::<f32>(4096);
+/// let mut peak = 0f32;
+/// while let Some(chunk) = chunks.next_chunk() {
+///     for sample in chunk.unwrap().iter() {
+///         peak = peak.max(sample.abs());
+///     }
+/// }
+/// ```
+pub struct Chunks<'a, R: 'a, T, O> {
+    reader: &'a mut R,
+    buf: Vec<MaybeUninit<T>>,
+    done: bool,
+    marker: PhantomData<O>
+}
+
+impl<'a, R: Read, T: Packed, O: Order> Chunks<'a, R, T, O> {
+    pub(crate) fn new(reader: &'a mut R, chunk_len: usize) -> Chunks<'a, R, T, O> {
+        // the buffer is zeroed so it can be viewed as bytes before the values are read
+        let buf = (0..chunk_len.max(1)).map(|_| MaybeUninit::zeroed()).collect();
+        Chunks {
+            reader,
+            buf,
+            done: false,
+            marker: PhantomData
+        }
+    }
+
+    /// Read the next chunk of values and return it, or `None` once `EOF` has been reached. Every
+    /// chunk holds as many values as requested except the last one, which may be shorter.
+    ///
+    /// If `EOF` is reached in the middle of a value, a `ShortRead` error is returned and the
+    /// values of the last chunk are lost. No chunk is returned after an error.
+    pub fn next_chunk(&mut self) -> Option<Result<&mut [T]>> {
+        if self.done {
+            return None;
+        }
+        let size = T::SIZE;
+        if size == 0 {
+            self.done = true;
+            return Some(Err(Error::invalid_input::<T, _>("cannot read zero-sized chunks")));
+        }
+        // safe because we build a slice of exactly buf.len() * T::SIZE bytes
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut u8, self.buf.len() * T::SIZE)
+        };
+        let filled = match order::fill(self.reader, bytes) {
+            Ok(filled) => filled,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e.into()));
+            }
+        };
+        if filled < bytes.len() {
+            self.done = true;
+        }
+        if filled % size != 0 {
+            return Some(Err(Error::short_read::<T>(size, filled % size)));
+        }
+        if filled == 0 {
+            return None;
+        }
+        // safe because the first filled / size values have been entirely read from the reader
+        let values = unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, filled / size) };
+        O::convert_slice(values);
+        Some(Ok(values))
+    }
+}
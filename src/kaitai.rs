@@ -0,0 +1,244 @@
+//! Rendering of Kaitai Struct descriptions of [`Packed`](../trait.Packed.html) types, so the
+//! Rust definition of a binary format can double as its documentation and be loaded in the
+//! Kaitai tools.
+
+use {Packed, Endianness, Order, Ordered};
+
+/// `KaitaiType` describes a [`Packed`](trait.Packed.html) type in the terms of a Kaitai Struct
+/// `.ksy` file, which is rendered by [`ksy_definition`](fn.ksy_definition.html).
+///
+/// It is implemented for all the primitive types implementing `Packed`, for arrays of types
+/// implementing `KaitaiType` and for [`Le`](type.Le.html) and [`Be`](type.Be.html) values of
+/// primitive types. Structures can implement it with the [`ks_struct!`](macro.ks_struct.html)
+/// macro.
+///
+/// ```
+/// use bytepack::{KaitaiType, Le};
+///
+/// assert_eq!(i16::ks_type(), "s2");
+/// assert_eq!(<Le<u32>>::ks_type(), "u4le");
+/// assert_eq!(<[[u8; 4]; 2]>::ks_repeat(), 8);
+/// ```
+pub trait KaitaiType: Packed {
+    /// Return the name of the Kaitai type of a value of this type.
+    fn ks_type() -> String;
+
+    /// Return the number of consecutive values of type [`ks_type`](#tymethod.ks_type) this type
+    /// is made of. This is 1 for types which are not arrays.
+    fn ks_repeat() -> usize {
+        1
+    }
+
+    /// Render the `seq` attributes of this type. This is `None` for types which do not need a
+    /// definition like primitive types and arrays.
+    fn ks_seq() -> Option<String> {
+        None
+    }
+
+    /// Append the names and `seq` attributes of the user types this type depends on, including
+    /// itself, to `types`, the dependencies first. Types already in `types` are not appended
+    /// again.
+    fn ks_types(types: &mut Vec<(String, String)>) {
+        let _ = types;
+    }
+}
+
+macro_rules! ks_primitive {
+    ($($ty:ty => $name:expr),*) => {
+        $(
+            impl KaitaiType for $ty {
+                fn ks_type() -> String {
+                    $name.to_string()
+                }
+            }
+        )*
+    };
+}
+
+ks_primitive!(u8 => "u1", i8 => "s1", u16 => "u2", i16 => "s2", u32 => "u4", i32 => "s4",
+    u64 => "u8", i64 => "s8", f32 => "f4", f64 => "f8");
+
+impl<T, const N: usize> KaitaiType for [T; N] where T: KaitaiType, [T; N]: Packed {
+    fn ks_type() -> String {
+        T::ks_type()
+    }
+
+    // Kaitai has no multidimensional arrays so nested arrays are flattened.
+    fn ks_repeat() -> usize {
+        N * T::ks_repeat()
+    }
+
+    fn ks_types(types: &mut Vec<(String, String)>) {
+        T::ks_types(types)
+    }
+}
+
+impl<T: KaitaiType, O: Order> KaitaiType for Ordered<T, O> {
+    fn ks_type() -> String {
+        // only primitive types of more than one byte take an endianness suffix
+        let ty = T::ks_type();
+        if T::ks_seq().is_some() || ty.ends_with('1') {
+            return ty;
+        }
+        match O::ENDIANNESS {
+            Endianness::Little => ty + "le",
+            Endianness::Big => ty + "be"
+        }
+    }
+
+    fn ks_repeat() -> usize {
+        T::ks_repeat()
+    }
+
+    fn ks_types(types: &mut Vec<(String, String)>) {
+        T::ks_types(types)
+    }
+}
+
+/// Render a `seq` attribute called `id` holding `repeat` values of the Kaitai type `ty`. This is
+/// used by [`ks_struct!`](macro.ks_struct.html) but can also be called directly by manual
+/// implementations of [`KaitaiType`](trait.KaitaiType.html).
+///
+/// ```
+/// use bytepack::ks_attribute;
+///
+/// assert_eq!(ks_attribute("magic", "u1", 4), "- id: magic\n  type: u1\n  repeat: expr\n  repeat-expr: 4\n");
+/// ```
+pub fn ks_attribute(id: &str, ty: &str, repeat: usize) -> String {
+    let mut attribute = format!("- id: {}\n  type: {}\n", id, ty);
+    if repeat != 1 {
+        attribute.push_str(&format!("  repeat: expr\n  repeat-expr: {}\n", repeat));
+    }
+    attribute
+}
+
+/// Convert a Rust type name like `FileHeader` to a Kaitai identifier like `file_header`.
+///
+/// ```
+/// use bytepack::ks_identifier;
+///
+/// assert_eq!(ks_identifier("FileHeader"), "file_header");
+/// ```
+pub fn ks_identifier(name: &str) -> String {
+    let mut identifier = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            identifier.push('_');
+        }
+        identifier.extend(c.to_lowercase());
+    }
+    identifier
+}
+
+fn indent(lines: &str, spaces: usize) -> String {
+    lines.lines().map(|line| format!("{:1$}{2}\n", "", spaces, line)).collect()
+}
+
+/// Render a complete Kaitai Struct `.ksy` description called `id` of the format made of a value
+/// of type `T`, whose values use the `endianness` byte order unless their type gives one.
+///
+/// ```
+/// #[macro_use]
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// use bytepack::{ksy_definition, Endianness, Packed};
+///
+/// ks_struct! {
+///     #[derive(Packed)]
+///     pub struct Header {
+///         pub magic: [u8; 4],
+///         pub length: u32,
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(ksy_definition::<Header>("archive", Endianness::Little), "\
+/// meta:
+///   id: archive
+///   endian: le
+/// seq:
+///   - id: header
+///     type: header
+/// types:
+///   header:
+///     seq:
+///       - id: magic
+///         type: u1
+///         repeat: expr
+///         repeat-expr: 4
+///       - id: length
+///         type: u4
+/// ");
+/// }
+/// ```
+pub fn ksy_definition<T: KaitaiType>(id: &str, endianness: Endianness) -> String {
+    let endian = match endianness {
+        Endianness::Little => "le",
+        Endianness::Big => "be"
+    };
+    let mut definition = format!("meta:\n  id: {}\n  endian: {}\nseq:\n", id, endian);
+    let ty = T::ks_type();
+    let name = if T::ks_seq().is_some() { ty.clone() } else { "value".to_string() };
+    definition.push_str(&indent(&ks_attribute(&name, &ty, T::ks_repeat()), 2));
+    let mut types = Vec::new();
+    T::ks_types(&mut types);
+    if !types.is_empty() {
+        definition.push_str("types:\n");
+        for (name, seq) in types {
+            definition.push_str(&format!("  {}:\n    seq:\n", name));
+            definition.push_str(&indent(&seq, 6));
+        }
+    }
+    definition
+}
+
+/// Define a structure and implement [`KaitaiType`](trait.KaitaiType.html) for it from the same
+/// definition, so its Kaitai Struct description can never get out of sync with the Rust one.
+/// The Kaitai type is named after the structure in snake case.
+///
+/// The structure is made `#[repr(C)]` so the description follows the order of its fields, and
+/// must not have padding for the description to match its layout. Structures used as fields
+/// need to implement `KaitaiType` themselves. See [`ksy_definition`](fn.ksy_definition.html) for
+/// an example.
+#[macro_export]
+macro_rules! ks_struct {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        impl $crate::KaitaiType for $name {
+            fn ks_type() -> String {
+                $crate::ks_identifier(stringify!($name))
+            }
+
+            fn ks_seq() -> Option<String> {
+                let mut seq = String::new();
+                $(seq.push_str(&$crate::ks_attribute(
+                    stringify!($field),
+                    &<$ty as $crate::KaitaiType>::ks_type(),
+                    <$ty as $crate::KaitaiType>::ks_repeat()
+                ));)*
+                Some(seq)
+            }
+
+            fn ks_types(types: &mut Vec<(String, String)>) {
+                $(<$ty as $crate::KaitaiType>::ks_types(types);)*
+                let name = <Self as $crate::KaitaiType>::ks_type();
+                if types.iter().all(|&(ref existing, _)| *existing != name) {
+                    let seq = <Self as $crate::KaitaiType>::ks_seq().unwrap();
+                    types.push((name, seq));
+                }
+            }
+        }
+    };
+}
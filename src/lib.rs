@@ -1,8 +1,8 @@
 //! `bytepack` is a simple crate which extends the `std::io` API to be able to read and write any 
 //! data type in their memory representation. It can be seen as a generalization of the 
 //! `std::io::Read` and `std::io::Write` trait , but operating on a generic parameter `T` instead 
-//! of `u8`. This crate focus on performances by beeing no copy (except in one clearly marked case) 
-//! and offering methods to read and write arrays.
+//! of `u8`. This crate focus on performances by beeing no copy (except when writing data which 
+//! needs an endianness switch) and offering methods to read and write arrays.
 //! 
 //! `bytepack` offers three trait famillies allowing different endianness control. 
 //! [`Unpacker`](trait.Unpacked.html) and [`Packer`](trait.Packer.html) read and write data in the 
@@ -11,15 +11,80 @@
 //! [`BEUnpacker`](trait.BEUnpacker.html) and [`BEPacker`](trait.BEPacker.html) do the 
 //! same in big endian. They all conform to the same API which is copied from the one of `std::io`.
 //! This means switching from one endianness to another can be done by simply bringing a different 
-//! trait in scope.
+//! trait in scope. When the endianness is only known at runtime, the `*_with` methods of 
+//! [`Unpacker`](trait.Unpacker.html) and [`Packer`](trait.Packer.html) take an 
+//! [`Endianness`](enum.Endianness.html) value instead, while code generic over the byte order 
+//! can use the [`Order`](trait.Order.html) trait. Code mixing several byte orders can instead use
+//! the `*_le`, `*_be` and `*_ne` methods of [`ExplicitUnpacker`](trait.ExplicitUnpacker.html) and
+//! [`ExplicitPacker`](trait.ExplicitPacker.html) side by side.
 //!
 //! Because `bytepack` is not a serialization library, it cannot read and write complex types like 
 //! `Vec`, `Rc`, etc. directly from a Reader or to Writer. Indeed those types do not contain the 
 //! underlying data directly packed inside but rather hold a reference or a pointer to it. To 
 //! identify types which holds their data "packed" together, the [`Packed`](trait.Packed.html) 
 //! trait is used. Additionnaly it provides a in-place endianness switching method. One can 
-//! implement this trait for the data types deemed safe to read and write. A custom derive for 
-//! structures made only of types implementing [`Packed`](trait.Packed.html) also exists.
+//! implement this `unsafe` trait for the data types which satisfy its safety contract. A safe
+//! custom derive for structures made only of types implementing [`Packed`](trait.Packed.html)
+//! also exists and is the recommended way to implement it.
+//!
+//! # Errors
+//!
+//! All the methods return a [`bytepack::Error`](enum.Error.html), which distinguishes a short
+//! read from trailing bytes or an invalid value and carries the name of the type involved, the
+//! expected and actual sizes and the offset when it is known. I/O errors of the underlying
+//! reader or writer are wrapped as they are, and `Error` converts into `io::Error`.
+//!
+//! # Allocation-free use
+//!
+//! The single value methods (`unpack`, `pack`, `pack_ref` and their variants), the methods
+//! working on caller provided slices (`unpack_exact`, `unpack_exact_uninit`) and the slice
+//! functions ([`unpack_from_slice`](fn.unpack_from_slice.html),
+//! [`pack_into_slice`](fn.pack_into_slice.html), etc.) never allocate memory. Writing slices with
+//! `pack_all` allocates a bounded buffer when the values need an endianness switch,
+//! `pack_all_into_slice` does not.
+//!
+//! This does not make the crate suitable for microcontrollers: it requires `std` for its
+//! `Read` and `Write` traits, and `unpack_exact_uninit`, `unpack_extend` and `pack_repeat` copy
+//! the values through an 8 KiB buffer on the stack, which needs that much stack space.
+//!
+//! # Features
+//!
+//! * `arrayvec`: unpack values into an `arrayvec::ArrayVec` through its
+//!   [`FixedCapacity`](trait.FixedCapacity.html) implementation.
+//! * `bytemuck`: pack and unpack `bytemuck::Pod` types with [`PodPacked`](struct.PodPacked.html)
+//!   or [`packed_pod!`](macro.packed_pod.html), and cast [`Le`](type.Le.html) and
+//!   [`Be`](type.Be.html) values with `bytemuck`.
+//! * `byteorder`: pack and unpack values with the byte order given as a `byteorder::ByteOrder`
+//!   type with [`ByteOrderUnpacker`](trait.ByteOrderUnpacker.html) and
+//!   [`ByteOrderPacker`](trait.ByteOrderPacker.html).
+//! * `digest`: hash the data going through a reader or a writer with any RustCrypto hash
+//!   function using [`DigestReader`](struct.DigestReader.html) and
+//!   [`DigestWriter`](struct.DigestWriter.html).
+//! * `flate2`: pack and unpack values through deflate streams with
+//!   [`pack_deflated`](fn.pack_deflated.html) and [`unpack_deflated`](fn.unpack_deflated.html).
+//! * `futures-io`: pack and unpack values asynchronously with the traits of the
+//!   [`futures`](futures/index.html) module, for runtimes built on `futures-io`.
+//! * `mmap`: view memory-mapped files as slices of packed values with
+//!   [`PackedMmap`](struct.PackedMmap.html) and [`PackedMmapMut`](struct.PackedMmapMut.html).
+//! * `proptest`: generate valid values of any packed type in property tests with
+//!   [`any_packed`](fn.any_packed.html).
+//! * `rayon`: parallelize the endianness switch of large buffers and the loading of large files
+//!   with [`UnpackAt::unpack_parallel_at`](trait.UnpackAt.html#tymethod.unpack_parallel_at).
+//! * `serde`: write and read any `Serialize` and `Deserialize` type in a fixed binary layout with
+//!   [`serialize_into`](fn.serialize_into.html) and [`deserialize_from`](fn.deserialize_from.html).
+//! * `smallvec`: unpack short runs of values without heap allocation into a `smallvec::SmallVec`
+//!   with [`SmallVecUnpacker`](trait.SmallVecUnpacker.html).
+//! * `tokio`: pack and unpack values asynchronously with [`AsyncUnpacker`](trait.AsyncUnpacker.html)
+//!   and [`AsyncPacker`](trait.AsyncPacker.html).
+//! * `tokio-uring`: read and write values at an offset of a file through io_uring with
+//!   [`UringUnpackAt`](trait.UringUnpackAt.html) and [`UringPackAt`](trait.UringPackAt.html), on
+//!   Linux only.
+//! * `tracing`: emit a trace event for every value or slice read or written, giving its type
+//!   name and size in bytes, within a span giving the offset in the stream for the steps run by
+//!   [`Tracking::track`](struct.Tracking.html#method.track).
+//! * `zerocopy`: pack and unpack types implementing `zerocopy::FromBytes` and `zerocopy::AsBytes`
+//!   with [`ZerocopyPacked`](struct.ZerocopyPacked.html) or
+//!   [`packed_zerocopy!`](macro.packed_zerocopy.html).
 //!
 //! # Example
 //!
@@ -44,13 +109,170 @@
 //! }
 //! ```
 
-use std::io::{Read, Write, Result, Error, ErrorKind};
-use std::mem::{zeroed, transmute, size_of, forget};
-use std::slice;
-
-/// This trait both identifies a type which holds his data packed together in memory and a type 
-/// which offers a `switch_endianness` method. This trait is voluntarily not implemented for 
-/// `isize` and `usize` because their size can vary from one system to another.
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "byteorder")]
+extern crate byteorder;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(feature = "flate2")]
+extern crate flate2;
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+extern crate tokio_uring;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
+
+use std::fmt::Debug;
+use std::io::{Read, BufRead, Write, Seek};
+use std::mem::{size_of, MaybeUninit};
+use std::sync::Arc;
+
+mod adapters;
+mod aligned;
+#[cfg(feature = "byteorder")]
+mod byteorder_compat;
+mod capacity;
+mod cdecl;
+mod compare;
+mod convert;
+#[cfg(feature = "flate2")]
+mod compression;
+mod counting;
+mod crc;
+mod dynamic;
+mod error;
+mod explicit;
+mod finite;
+mod fixed;
+mod framing;
+#[cfg(feature = "futures-io")]
+pub mod futures;
+#[cfg(feature = "digest")]
+mod hashing;
+mod header;
+mod ip;
+mod iter;
+mod kaitai;
+mod limited;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod net;
+mod order;
+mod ordered;
+mod parse;
+mod partial;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(any(unix, windows))]
+mod positioned;
+mod progress;
+#[cfg(feature = "proptest")]
+mod proptest_compat;
+mod record;
+mod scratch;
+mod sentinel;
+#[cfg(feature = "serde")]
+mod serde_compat;
+#[cfg(feature = "smallvec")]
+mod smallvec_compat;
+mod tail;
+mod testing;
+#[cfg(feature = "tokio")]
+mod tokio_io;
+mod tracking;
+mod trailing;
+mod transform;
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+mod uring;
+mod validate;
+mod view;
+#[cfg(feature = "zerocopy")]
+mod zerocopy_compat;
+
+pub use adapters::{PackedReader, PackedSink, ByteOrderAdapter};
+pub use aligned::AlignedVec;
+#[cfg(feature = "byteorder")]
+pub use byteorder_compat::{ByteOrderUnpacker, ByteOrderPacker, endianness_of};
+pub use capacity::FixedCapacity;
+pub use cdecl::{CDecl, c_struct_definition};
+pub use compare::{Difference, compare_packed, compare_packed_with};
+pub use convert::{Overflow, Integer};
+#[cfg(feature = "flate2")]
+pub use compression::{pack_deflated, unpack_deflated};
+pub use counting::{Counting, Counts};
+pub use crc::{Crc32Reader, Crc32Writer};
+pub use dynamic::{ReadUnpacker, WritePacker, unpack_dyn, unpack_dyn_with, unpack_exact_dyn, unpack_exact_dyn_with, unpack_to_end_dyn, unpack_to_end_dyn_with, pack_dyn, pack_dyn_with, pack_all_dyn, pack_all_dyn_with};
+pub use error::{Error, Result};
+pub use explicit::{ExplicitUnpacker, ExplicitPacker};
+pub use finite::Finite;
+pub use fixed::{Fixed, Q15, Q31};
+pub use framing::LengthPrefix;
+#[cfg(feature = "digest")]
+pub use hashing::{DigestReader, DigestWriter};
+pub use header::HeaderPacked;
+pub use ip::{PackedIpv4, PackedIpv6};
+pub use iter::{UnpackIter, Records, Chunks, RECORDS_BUFFER_SIZE};
+pub use kaitai::{KaitaiType, ks_attribute, ks_identifier, ksy_definition};
+pub use limited::Limited;
+#[cfg(feature = "mmap")]
+pub use mmap::{PackedMmap, PackedMmapMut};
+pub use net::{NetUnpacker, NetPacker};
+pub use order::{Endianness, Order, NativeEndian, LittleEndian, BigEndian};
+pub use order::{switch_endianness_slice, to_le_slice, to_be_slice, from_le_slice, from_be_slice};
+pub use ordered::{Ordered, Le, Be};
+pub use parse::{unpack_from_slice, unpack_from_slice_with, unpack_exact_from_slice, unpack_exact_from_slice_with, SliceUnpacker};
+pub use partial::PartialUnpack;
+pub use parse::{pack_into_slice, pack_into_slice_with, pack_all_into_slice, pack_all_into_slice_with};
+#[cfg(feature = "bytemuck")]
+pub use pod::{PodPacked, assert_pod};
+#[cfg(any(unix, windows))]
+pub use positioned::{UnpackAt, PackAt};
+pub use progress::Progress;
+#[cfg(feature = "proptest")]
+pub use proptest_compat::any_packed;
+pub use record::RecordFile;
+pub use scratch::ScratchBuffer;
+pub use sentinel::Sentinel;
+#[cfg(feature = "serde")]
+pub use serde_compat::{PackedSerializer, PackedDeserializer, serialize_into, deserialize_from};
+#[cfg(feature = "smallvec")]
+pub use smallvec_compat::SmallVecUnpacker;
+pub use tail::{TailCount, WithTail};
+pub use testing::roundtrip;
+#[cfg(feature = "tokio")]
+pub use tokio_io::{AsyncUnpacker, AsyncPacker, UnpackFuture, UnpackExactFuture, PackFuture, PackAllFuture};
+pub use tracking::Tracking;
+pub use trailing::{TrailingPolicy, ToEnd};
+pub use transform::Transform;
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+pub use uring::{UringUnpackAt, UringPackAt, UringFuture};
+pub use validate::PackedValidate;
+pub use view::{unpack_ref, unpack_slice_ref, unpack_mut, unpack_slice_mut, unpack_cow, PackedView};
+#[cfg(feature = "zerocopy")]
+pub use zerocopy_compat::{ZerocopyPacked, assert_zerocopy};
+
+/// This trait identifies a type which holds his data packed together in memory. Its byte order is
+/// switched through its [`EndianSwitch`](trait.EndianSwitch.html) supertrait. This trait is
+/// voluntarily not implemented for `isize` and `usize` because their size can vary from one
+/// system to another.
 ///
 /// # Example
 ///
@@ -83,14 +305,53 @@ use std::slice;
 ///
 /// ```
 ///
-/// Please note that also specifying `#[repr(packed)]` might make sense if you want to get rid of 
-/// the padding inside your structure.
-/// 
+/// Padding bytes would be packed uninitialized, so the derive refuses, at compile time, structures
+/// whose size is not the sum of the sizes of their fields. Reorder the fields, add explicit reserved
+/// fields or specify `#[repr(packed)]` to get rid of the padding inside your structure:
+///
+/// ```compile_fail
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// #[derive(Packed)]
+/// #[repr(C)]
+/// struct Padded {
+///    tag: u8,
+///    value: u32,
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// The size of a generic structure is checked when it is packed or unpacked with the type
+/// parameters of the call:
+///
+/// ```compile_fail
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// use bytepack::Packer;
+///
+/// #[derive(Packed)]
+/// #[repr(C)]
+/// struct Tagged<T> {
+///    tag: u8,
+///    value: T,
+/// }
+///
+/// fn main() {
+///     let mut buffer = Vec::new();
+///     buffer.pack_all(&[Tagged { tag: 1, value: 2u32 }]).unwrap();
+/// }
+/// ```
+///
 /// `Packed` can only be derived for strutures only composed of types implementing `Packed` 
 /// themselves. If you which to circumvent this restriction you can implement `Packed` yourselve, 
-/// however you need to make sure your struct is indeed "packed" and that reading and writing it as 
-/// one continuous memory zone makes sense. For example the following structures are not "packed" 
-/// because they all hold a reference to their data.
+/// which requires an `unsafe impl` because the crate reads and writes the values as raw memory.
+/// For example the following structures are not "packed" because they all hold a reference to
+/// their data, implementing `Packed` for them would be unsound.
 ///
 /// ```ignore
 /// struct NotPacked1 {
@@ -105,94 +366,273 @@ use std::slice;
 ///     count: Rc<u64>
 /// }
 /// ```
-pub trait Packed {
-    /// Perform an in-place switch of the endianness. This might be a no-op in some cases.
-    fn switch_endianness(&mut self);
+///
+/// # Safety
+///
+/// Values of a type implementing `Packed` are created from arbitrary bytes and their memory is
+/// written as is, so the implementor must guarantee that:
+///
+/// * any bit pattern of the size of the type is a valid value of the type,
+/// * the type has no padding bytes, which would be read uninitialized when packing,
+/// * the type holds no reference, pointer or handle and does not implement `Drop`,
+/// * its `EndianSwitch` implementation only permutes the bytes of the value, so it stays valid,
+/// * `SIZE` is `size_of::<Self>()`, which is its default.
+///
+/// `bool` does not implement `Packed` since only the bytes 0 and 1 are valid values, unpack a `u8`
+/// and compare it instead.
+///
+/// ```
+/// use bytepack::{EndianSwitch, Packed};
+///
+/// #[repr(transparent)]
+/// struct Celsius(i16);
+///
+/// impl EndianSwitch for Celsius {
+///     fn switch_endianness(&mut self) {
+///         self.0.switch_endianness();
+///     }
+/// }
+///
+/// // Safe because `Celsius` has the same layout as `i16`.
+/// unsafe impl Packed for Celsius {}
+/// ```
+pub unsafe trait Packed: EndianSwitch + Sized {
+    /// The number of bytes of a packed value of this type. Generic code should use it instead of
+    /// `size_of` for offset computations and pre-allocations, like the methods of this crate do.
+    /// The derive generates it as the sum of the sizes of the fields, which fails to compile if
+    /// the structure has padding bytes.
+    ///
+    /// ```
+    /// use bytepack::Packed;
+    ///
+    /// fn offset_of_item<T: Packed>(header_len: usize, index: usize) -> usize {
+    ///     header_len + index * T::SIZE
+    /// }
+    ///
+    /// assert_eq!(offset_of_item::<[u16; 3]>(8, 2), 20);
+    /// ```
+    const SIZE: usize = size_of::<Self>();
+
+    /// Return the number of bytes of this value once packed, which is [`SIZE`](#associatedconstant.SIZE).
+    fn packed_size(&self) -> usize {
+        Self::SIZE
+    }
+
+    /// Return the bytes of this value in the system endianness.
+    fn to_ne_bytes_vec(&self) -> Vec<u8> {
+        order::to_bytes_vec::<NativeEndian, Self>(self)
+    }
+
+    /// Return the bytes of this value in little endian.
+    fn to_le_bytes_vec(&self) -> Vec<u8> {
+        order::to_bytes_vec::<LittleEndian, Self>(self)
+    }
+
+    /// Return the bytes of this value in big endian.
+    ///
+    /// ```
+    /// use bytepack::Packed;
+    ///
+    /// assert_eq!([0x1234u16, 0x5678].to_be_bytes_vec(), [0x12, 0x34, 0x56, 0x78]);
+    /// ```
+    fn to_be_bytes_vec(&self) -> Vec<u8> {
+        order::to_bytes_vec::<BigEndian, Self>(self)
+    }
+
+    /// Create a value from its bytes in the system endianness. `bytes` must hold exactly one
+    /// value: a `ShortRead` error is returned if it is shorter and a `TrailingBytes` error if it
+    /// is longer.
+    fn from_ne_bytes_slice(bytes: &[u8]) -> Result<Self> {
+        order::from_bytes_slice::<NativeEndian, Self>(bytes)
+    }
+
+    /// Create a value from its bytes in little endian, with the same length checks as
+    /// [`from_ne_bytes_slice`](#method.from_ne_bytes_slice).
+    fn from_le_bytes_slice(bytes: &[u8]) -> Result<Self> {
+        order::from_bytes_slice::<LittleEndian, Self>(bytes)
+    }
+
+    /// Create a value from its bytes in big endian, with the same length checks as
+    /// [`from_ne_bytes_slice`](#method.from_ne_bytes_slice). The `_slice` suffix avoids
+    /// clashing with the inherent `from_be_bytes` of the primitive types.
+    ///
+    /// ```
+    /// use bytepack::Packed;
+    ///
+    /// assert_eq!(u32::from_be_bytes_slice(&[0, 0, 0x1F, 0x90]).unwrap(), 8080);
+    /// assert!(u32::from_be_bytes_slice(&[0x1F, 0x90]).is_err());
+    /// ```
+    fn from_be_bytes_slice(bytes: &[u8]) -> Result<Self> {
+        order::from_bytes_slice::<BigEndian, Self>(bytes)
+    }
 }
 
-impl Packed for bool {
-    fn switch_endianness(&mut self) {
+/// Return the number of bytes of `values` once packed.
+///
+/// ```
+/// use bytepack::{packed_len, LEPacker};
+///
+/// let samples = [0f32; 10];
+/// let mut buffer = Vec::with_capacity(packed_len(&samples));
+/// buffer.pack_all(&samples[..]).unwrap();
+/// assert_eq!(buffer.len(), 40);
+/// ```
+pub fn packed_len<T: Packed>(values: &[T]) -> usize {
+    values.len() * T::SIZE
+}
+
+/// Return the number of bytes of `value` once packed, which is `T::SIZE`.
+pub fn packed_size_of_val<T: Packed>(_value: &T) -> usize {
+    T::SIZE
+}
+
+/// Return the number of bytes of the values yielded by `iter` once packed, without packing them.
+/// If the size hint of the iterator is exact no value is produced, otherwise the iterator is
+/// consumed to count them.
+///
+/// ```
+/// use bytepack::{packed_size_of_iter, LEPacker};
+///
+/// let samples = (0..100).map(|i| i as f32);
+/// let mut buffer = Vec::new();
+/// buffer.pack(packed_size_of_iter(samples.clone()) as u32).unwrap();
+/// buffer.pack_iter(samples).unwrap();
+/// assert_eq!(buffer.len(), 4 + 400);
+/// ```
+pub fn packed_size_of_iter<T: Packed, I: IntoIterator<Item = T>>(iter: I) -> usize {
+    let iter = iter.into_iter();
+    match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => lower * T::SIZE,
+        _ => iter.count() * T::SIZE
     }
 }
 
-impl Packed for u8 {
+/// This trait identifies a type whose byte order can be switched in-place. It is a supertrait of
+/// [`Packed`](trait.Packed.html), which is all the endianness aware methods need, but it can also
+/// be implemented alone by types which are not safe to reinterpret as bytes.
+///
+/// There is no default implementation of `switch_endianness`, so forgetting a multi-byte field
+/// does not go unnoticed. Types without a meaningful byte order, like opaque identifiers or byte
+/// arrays, implement it as a no-op:
+///
+/// ```
+/// use bytepack::{EndianSwitch, Packed};
+///
+/// #[repr(transparent)]
+/// struct Uuid([u8; 16]);
+///
+/// impl EndianSwitch for Uuid {
+///     fn switch_endianness(&mut self) {
+///         // An identifier is a sequence of bytes.
+///     }
+/// }
+///
+/// // Safe because `Uuid` has the same layout as a byte array.
+/// unsafe impl Packed for Uuid {}
+/// ```
+pub trait EndianSwitch {
+    /// Perform an in-place switch of the endianness.
+    fn switch_endianness(&mut self);
+}
+
+unsafe impl Packed for u8 {}
+
+impl EndianSwitch for u8 {
     fn switch_endianness(&mut self) {
     }
 }
 
-impl Packed for i8 {
+unsafe impl Packed for i8 {}
+
+impl EndianSwitch for i8 {
     fn switch_endianness(&mut self) {
     }
 }
 
-impl Packed for u16 {
+unsafe impl Packed for u16 {}
+
+impl EndianSwitch for u16 {
     fn switch_endianness(&mut self) {
         *self = u16::swap_bytes(*self);
     }
 }
 
-impl Packed for i16 {
+unsafe impl Packed for i16 {}
+
+impl EndianSwitch for i16 {
     fn switch_endianness(&mut self) {
         *self = i16::swap_bytes(*self);
     }
 }
 
-impl Packed for u32 {
+unsafe impl Packed for u32 {}
+
+impl EndianSwitch for u32 {
     fn switch_endianness(&mut self) {
         *self = u32::swap_bytes(*self);
     }
 }
 
-impl Packed for i32 {
+unsafe impl Packed for i32 {}
+
+impl EndianSwitch for i32 {
     fn switch_endianness(&mut self) {
         *self = i32::swap_bytes(*self);
     }
 }
 
-impl Packed for u64 {
+unsafe impl Packed for u64 {}
+
+impl EndianSwitch for u64 {
     fn switch_endianness(&mut self) {
         *self = u64::swap_bytes(*self);
     }
 }
 
-impl Packed for i64 {
+unsafe impl Packed for i64 {}
+
+impl EndianSwitch for i64 {
     fn switch_endianness(&mut self) {
         *self = i64::swap_bytes(*self);
     }
 }
 
-impl Packed for f32 {
+unsafe impl Packed for f32 {}
+
+impl EndianSwitch for f32 {
     fn switch_endianness(&mut self) {
-        // Safe because we always revert to the original type
-        unsafe {
-            *self = transmute(u32::swap_bytes(transmute(*self)));
-        }
+        *self = f32::from_bits(u32::swap_bytes(self.to_bits()));
     }
 }
 
-impl Packed for f64 {
+unsafe impl Packed for f64 {}
+
+impl EndianSwitch for f64 {
     fn switch_endianness(&mut self) {
-        // Safe because we always revert to the original type
-        unsafe {
-            *self = transmute(u64::swap_bytes(transmute(*self)));
-        }
+        *self = f64::from_bits(u64::swap_bytes(self.to_bits()));
     }
 }
 
-impl<T> Packed for [T;1] where T: Packed {
+unsafe impl<T> Packed for [T;1] where T: Packed {}
+
+impl<T> EndianSwitch for [T;1] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
     }
 }
 
-impl<T> Packed for [T;2] where T: Packed {
+unsafe impl<T> Packed for [T;2] where T: Packed {}
+
+impl<T> EndianSwitch for [T;2] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
     }
 }
 
-impl<T> Packed for [T;3] where T: Packed {
+unsafe impl<T> Packed for [T;3] where T: Packed {}
+
+impl<T> EndianSwitch for [T;3] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -200,7 +640,9 @@ impl<T> Packed for [T;3] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;4] where T: Packed {
+unsafe impl<T> Packed for [T;4] where T: Packed {}
+
+impl<T> EndianSwitch for [T;4] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -209,7 +651,9 @@ impl<T> Packed for [T;4] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;5] where T: Packed {
+unsafe impl<T> Packed for [T;5] where T: Packed {}
+
+impl<T> EndianSwitch for [T;5] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -219,7 +663,9 @@ impl<T> Packed for [T;5] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;6] where T: Packed {
+unsafe impl<T> Packed for [T;6] where T: Packed {}
+
+impl<T> EndianSwitch for [T;6] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -230,7 +676,9 @@ impl<T> Packed for [T;6] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;7] where T: Packed {
+unsafe impl<T> Packed for [T;7] where T: Packed {}
+
+impl<T> EndianSwitch for [T;7] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -242,7 +690,9 @@ impl<T> Packed for [T;7] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;8] where T: Packed {
+unsafe impl<T> Packed for [T;8] where T: Packed {}
+
+impl<T> EndianSwitch for [T;8] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -255,7 +705,9 @@ impl<T> Packed for [T;8] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;9] where T: Packed {
+unsafe impl<T> Packed for [T;9] where T: Packed {}
+
+impl<T> EndianSwitch for [T;9] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -269,7 +721,9 @@ impl<T> Packed for [T;9] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;10] where T: Packed {
+unsafe impl<T> Packed for [T;10] where T: Packed {}
+
+impl<T> EndianSwitch for [T;10] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -284,7 +738,9 @@ impl<T> Packed for [T;10] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;11] where T: Packed {
+unsafe impl<T> Packed for [T;11] where T: Packed {}
+
+impl<T> EndianSwitch for [T;11] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -300,7 +756,9 @@ impl<T> Packed for [T;11] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;12] where T: Packed {
+unsafe impl<T> Packed for [T;12] where T: Packed {}
+
+impl<T> EndianSwitch for [T;12] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -317,7 +775,9 @@ impl<T> Packed for [T;12] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;13] where T: Packed {
+unsafe impl<T> Packed for [T;13] where T: Packed {}
+
+impl<T> EndianSwitch for [T;13] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -335,7 +795,9 @@ impl<T> Packed for [T;13] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;14] where T: Packed {
+unsafe impl<T> Packed for [T;14] where T: Packed {}
+
+impl<T> EndianSwitch for [T;14] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -354,7 +816,9 @@ impl<T> Packed for [T;14] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;15] where T: Packed {
+unsafe impl<T> Packed for [T;15] where T: Packed {}
+
+impl<T> EndianSwitch for [T;15] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -374,7 +838,9 @@ impl<T> Packed for [T;15] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;16] where T: Packed {
+unsafe impl<T> Packed for [T;16] where T: Packed {}
+
+impl<T> EndianSwitch for [T;16] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -395,7 +861,9 @@ impl<T> Packed for [T;16] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;17] where T: Packed {
+unsafe impl<T> Packed for [T;17] where T: Packed {}
+
+impl<T> EndianSwitch for [T;17] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -417,7 +885,9 @@ impl<T> Packed for [T;17] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;18] where T: Packed {
+unsafe impl<T> Packed for [T;18] where T: Packed {}
+
+impl<T> EndianSwitch for [T;18] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -440,7 +910,9 @@ impl<T> Packed for [T;18] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;19] where T: Packed {
+unsafe impl<T> Packed for [T;19] where T: Packed {}
+
+impl<T> EndianSwitch for [T;19] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -464,7 +936,9 @@ impl<T> Packed for [T;19] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;20] where T: Packed {
+unsafe impl<T> Packed for [T;20] where T: Packed {}
+
+impl<T> EndianSwitch for [T;20] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -489,7 +963,9 @@ impl<T> Packed for [T;20] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;21] where T: Packed {
+unsafe impl<T> Packed for [T;21] where T: Packed {}
+
+impl<T> EndianSwitch for [T;21] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -515,7 +991,9 @@ impl<T> Packed for [T;21] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;22] where T: Packed {
+unsafe impl<T> Packed for [T;22] where T: Packed {}
+
+impl<T> EndianSwitch for [T;22] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -542,7 +1020,9 @@ impl<T> Packed for [T;22] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;23] where T: Packed {
+unsafe impl<T> Packed for [T;23] where T: Packed {}
+
+impl<T> EndianSwitch for [T;23] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -570,7 +1050,9 @@ impl<T> Packed for [T;23] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;24] where T: Packed {
+unsafe impl<T> Packed for [T;24] where T: Packed {}
+
+impl<T> EndianSwitch for [T;24] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -599,7 +1081,9 @@ impl<T> Packed for [T;24] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;25] where T: Packed {
+unsafe impl<T> Packed for [T;25] where T: Packed {}
+
+impl<T> EndianSwitch for [T;25] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -629,7 +1113,9 @@ impl<T> Packed for [T;25] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;26] where T: Packed {
+unsafe impl<T> Packed for [T;26] where T: Packed {}
+
+impl<T> EndianSwitch for [T;26] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -660,7 +1146,9 @@ impl<T> Packed for [T;26] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;27] where T: Packed {
+unsafe impl<T> Packed for [T;27] where T: Packed {}
+
+impl<T> EndianSwitch for [T;27] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -692,7 +1180,9 @@ impl<T> Packed for [T;27] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;28] where T: Packed {
+unsafe impl<T> Packed for [T;28] where T: Packed {}
+
+impl<T> EndianSwitch for [T;28] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -725,7 +1215,9 @@ impl<T> Packed for [T;28] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;29] where T: Packed {
+unsafe impl<T> Packed for [T;29] where T: Packed {}
+
+impl<T> EndianSwitch for [T;29] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -759,7 +1251,9 @@ impl<T> Packed for [T;29] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;30] where T: Packed {
+unsafe impl<T> Packed for [T;30] where T: Packed {}
+
+impl<T> EndianSwitch for [T;30] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -794,7 +1288,9 @@ impl<T> Packed for [T;30] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;31] where T: Packed {
+unsafe impl<T> Packed for [T;31] where T: Packed {}
+
+impl<T> EndianSwitch for [T;31] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -830,7 +1326,9 @@ impl<T> Packed for [T;31] where T: Packed {
     }
 }
 
-impl<T> Packed for [T;32] where T: Packed {
+unsafe impl<T> Packed for [T;32] where T: Packed {}
+
+impl<T> EndianSwitch for [T;32] where T: EndianSwitch {
     fn switch_endianness(&mut self) {
         self[0].switch_endianness();
         self[1].switch_endianness();
@@ -924,241 +1422,1552 @@ pub trait Unpacker {
     /// file.unpack_exact(&mut buffer[..]).unwrap();
     /// ```
     fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
-}
 
-/// `Packer` provides the `std::io::Write` API but for any type `T` implementing 
-/// [`Packed`](trait.Packed.html). It does not perform any endianness conversion and thus always 
-/// writes data using the system endianness.
-///
-/// # Example
-/// 
-/// Example of writing a file containing a few float samples.
-/// 
-/// ```no_run
-/// use std::fs::File;
-/// use std::iter::repeat;
-///
-/// use bytepack::Packer;
-///
-/// fn write_samples(file: &str, samples: &Vec<f32>) {
-///     let mut file = File::create(file).unwrap();
-///     file.pack(samples.len() as u32).unwrap();
-///     file.pack_all(&samples[..]).unwrap();
-/// }
-/// ```
-pub trait Packer {
+    /// Unpack a single value of type `T` stored in the `endianness` byte order.
+    ///
+    /// ```no_run
+    /// # use bytepack::{Endianness, Unpacker};
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let float : f32 = file.unpack_with(Endianness::Big).unwrap();
+    /// ```
+    fn unpack_with<T: Packed>(&mut self, endianness: Endianness) -> Result<T>;
 
-    /// Pack a single value of type `T`.
+    /// Same as [`unpack_to_end`](#tymethod.unpack_to_end) but for values stored in the 
+    /// `endianness` byte order.
+    fn unpack_to_end_with<T: Packed>(&mut self, buf: &mut Vec<T>, endianness: Endianness) -> Result<usize>;
+
+    /// Same as [`unpack_exact`](#tymethod.unpack_exact) but for values stored in the 
+    /// `endianness` byte order.
+    fn unpack_exact_with<T: Packed>(&mut self, buf: &mut [T], endianness: Endianness) -> Result<()>;
+
+    /// Return an iterator unpacking values of type `T` until `EOF` is reached. If `EOF` is 
+    /// reached in the middle of a value, an error is yielded as the last item.
     ///
     /// ```no_run
-    /// # use bytepack::Packer;
+    /// # use bytepack::Unpacker;
     /// # use std::fs::File;
-    /// let mut file = File::create("test").unwrap();
-    /// file.pack(42f32).unwrap();
+    /// let mut file = File::open("test").unwrap();
+    /// for value in file.unpack_iter::<u32>() {
+    ///     println!("{}", value.unwrap());
+    /// }
     /// ```
-    fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, Self, T, NativeEndian> where Self: Sized;
 
-    /// Pack a single value of type `T` passed by reference.
+    /// Unpack exactly `count` values of type `T` and return them in a newly allocated `Vec`. An 
+    /// error is returned if not enough byte could be read.
     ///
     /// ```no_run
-    /// # use bytepack::Packer;
+    /// # use bytepack::Unpacker;
     /// # use std::fs::File;
-    /// let mut file = File::create("test").unwrap();
-    /// file.pack(42f32).unwrap();
+    /// let mut file = File::open("test").unwrap();
+    /// let num_samples : u32 = file.unpack().unwrap();
+    /// let samples : Vec<f32> = file.unpack_n(num_samples as usize).unwrap();
     /// ```
-    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()>;
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
 
-    /// Pack all the values of type `T` from `buf`.
+    /// Unpack exactly `len` values of type `T` into a newly allocated boxed slice. The memory is 
+    /// allocated once, with exactly the needed size. An error is returned if not enough byte could 
+    /// be read.
     ///
     /// ```no_run
-    /// # use bytepack::Packer;
+    /// # use bytepack::Unpacker;
     /// # use std::fs::File;
-    /// let mut file = File::create("test").unwrap();
-    /// let mut float_buffer = vec![666u16; 10];
-    /// file.pack_all(&mut float_buffer[..]).unwrap();
+    /// let mut file = File::open("test").unwrap();
+    /// let samples : Box<[f32]> = file.unpack_boxed_slice(1024).unwrap();
     /// ```
-    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
-}
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>>;
 
-impl<R> Unpacker for R where R: Read {
-    fn unpack<T: Packed>(&mut self) -> Result<T> {
-        let mut res: T;
-        // safe because we build a slice of exactly size_of::<T> bytes
-        unsafe {
-            res = zeroed();
-            self.read_exact(slice::from_raw_parts_mut(transmute::<&mut T, *mut u8>(&mut res), size_of::<T>()))?;
-        }
-        return Ok(res);
-    }
+    /// Unpack exactly `len` values of type `T` directly into a newly allocated `Arc<[T]>`, 
+    /// without going through an intermediate `Vec`. An error is returned if not enough byte could 
+    /// be read.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// # use std::sync::Arc;
+    /// let mut file = File::open("test").unwrap();
+    /// let samples : Arc<[f32]> = file.unpack_arc_slice(1024).unwrap();
+    /// ```
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>>;
 
-    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
-        // safe because converted is always forgotten before returning, capacity and length are 
-        // always recomputed, in case of error buf is truncated to it's original data.
-        unsafe {
-            let length = buf.len();
-            let capacity = buf.capacity();
-            let mut converted = Vec::<u8>::from_raw_parts(buf.as_mut_ptr() as *mut u8, length * size_of::<T>(), capacity * size_of::<T>());
-            match self.read_to_end(&mut converted) {
-                Ok(size) => {
-                    if converted.len() % size_of::<T>() != 0 {
-                        converted.truncate(length * size_of::<T>());
-                        let new_capacity = converted.len() / size_of::<T>();
-                        *buf = Vec::from_raw_parts(converted.as_mut_ptr() as *mut T, length, new_capacity);
-                        forget(converted);
-                        return Err(Error::new(
-                            ErrorKind::UnexpectedEof, 
-                            format!("read_to_end() returned a number of bytes ({}) which is not a multiple of the size of T ({})", size, size_of::<T>())
-                        ));
-                    }
-                },
-                Err(e) => {
-                    converted.truncate(length * size_of::<T>());
-                    let new_capacity = converted.len() / size_of::<T>();
-                    *buf = Vec::from_raw_parts(converted.as_mut_ptr() as *mut T, length, new_capacity);
-                    forget(converted);
-                    return Err(e);
-                }
-            };
-            let new_length = converted.len() / size_of::<T>();
-            let new_capacity = converted.len() / size_of::<T>();
-            *buf = Vec::from_raw_parts(converted.as_mut_ptr() as *mut T, new_length, new_capacity);
-            forget(converted);
-            return Ok(new_length - length);
-        }
-    }
+    /// Unpack values of type `T` to fill the uninitialized `buf` and return it as an initialized 
+    /// slice. This avoids having to build valid values of type `T` before reading over them: `buf`
+    /// is never zeroed, the bytes are read through a small internal buffer and copied into it. An 
+    /// error is returned if not enough byte could be read.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// # use std::mem::MaybeUninit;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut buffer = [MaybeUninit::<u64>::uninit(); 16];
+    /// let values : &mut [u64] = file.unpack_exact_uninit(&mut buffer).unwrap();
+    /// ```
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]>;
 
-    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
-        // safe because we build a slice of exactly buf.len() * size_of::<T> bytes
-        unsafe {
-            self.read_exact(slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, buf.len() * size_of::<T>()))
-        }
-    }
-}
+    /// Unpack exactly `count` values of type `T` and append them to `buf`, copying them into its 
+    /// spare capacity without zeroing it first. An error is returned if not enough byte could be
+    /// read, in which case `buf` keeps its original content.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut samples = Vec::<f32>::with_capacity(2048);
+    /// file.unpack_append(&mut samples, 1024).unwrap();
+    /// file.unpack_append(&mut samples, 1024).unwrap();
+    /// ```
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()>;
 
-impl<W> Packer for W where W: Write {
-    fn pack<T: Packed>(&mut self, t: T) -> Result<()> {
-        // safe because we build a slice of exactly size_of::<T> bytes
-        unsafe {
-            self.write_all(slice::from_raw_parts(transmute::<&T, *const u8>(&t), size_of::<T>()))?;
-        }
-        return Ok(());
-    }
+    /// Same as [`unpack_to_end`](#tymethod.unpack_to_end) but computes the number of remaining 
+    /// bytes beforehand using `Seek` so the memory of `buf` is reserved exactly once.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut buffer = Vec::<u64>::new();
+    /// file.unpack_to_end_sized(&mut buffer).unwrap();
+    /// ```
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where Self: Seek;
 
-    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()> {
-        // safe because we build a slice of exactly size_of::<T> bytes
-        unsafe {
-            self.write_all(slice::from_raw_parts(transmute::<&T, *const u8>(t), size_of::<T>()))?;
-        }
-        return Ok(());
-    }
+    /// Unpack a value of type `T` without consuming it: the position of the stream is restored
+    /// using `Seek` afterward, even if an error occurred.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let tag : u8 = file.peek().unwrap();
+    /// if tag == 1 {
+    ///     let tag : u8 = file.unpack().unwrap();
+    ///     let length : u32 = file.unpack().unwrap();
+    /// }
+    /// ```
+    fn peek<T: Packed>(&mut self) -> Result<T> where Self: Seek;
 
-    fn pack_all<T: Packed>(&mut self, t: &[T]) -> Result<()> {
-        // safe because we build a slice of exactly t.len() * size_of::<T> bytes
-        unsafe {
-            self.write_all(slice::from_raw_parts(transmute::<*const T, *const u8>(t.as_ptr()), t.len() * size_of::<T>()))?;
-        }
-        return Ok(());
-    }
-}
+    /// Skip `count` values of type `T` by reading and discarding them. An error is returned if
+    /// not enough bytes could be read.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// file.skip::<u32>(16).unwrap();
+    /// let value : u32 = file.unpack().unwrap();
+    /// ```
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()>;
 
-/// Provides the same API and functionnality as [`Unpacker`](trait.Unpacker.html) but ensure that 
-/// the data is in little endian format. See [`Unpacker`](trait.Unpacker.html) for more 
-/// documentation.
-pub trait LEUnpacker {
-    fn unpack<T: Packed>(&mut self) -> Result<T>;
-    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
-    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    /// Same as [`skip`](#tymethod.skip) but seeks past the values instead of reading them. An
+    /// error is returned, and the position left unchanged, if the stream ends before.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// file.skip_seek::<[u8; 32]>(64).unwrap();
+    /// ```
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where Self: Seek;
+
+    /// Unpack a value of type `T` located `offset` bytes from the start of the stream. The
+    /// position of the stream is restored afterward, even if an error occurred.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let directory_offset : u64 = file.unpack().unwrap();
+    /// let entry_count : u32 = file.unpack_at(directory_offset).unwrap();
+    /// ```
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where Self: Seek;
+
+    /// Unpack exactly `buf.len()` values of type `T` located `offset` bytes from the start of
+    /// the stream. The position of the stream is restored afterward, even if an error occurred.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut entries = [0u64; 16];
+    /// file.unpack_exact_at(512, &mut entries).unwrap();
+    /// ```
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where Self: Seek;
+
+    /// Call `f` with this reader and rewind the stream to its original position if `f` returns
+    /// an error, so a record which failed to be parsed is never left partially consumed.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let record = file.transaction(|file| {
+    ///     let id : u32 = file.unpack()?;
+    ///     let length : u16 = file.unpack()?;
+    ///     Ok((id, length))
+    /// });
+    /// ```
+    fn transaction<U, F: FnOnce(&mut Self) -> Result<U>>(&mut self, f: F) -> Result<U> where Self: Seek + Sized;
+
+    /// Return an iterator unpacking values of type `T` until `EOF` is reached, like
+    /// [`unpack_iter`](#tymethod.unpack_iter), but reading them by batches of
+    /// [`RECORDS_BUFFER_SIZE`](constant.RECORDS_BUFFER_SIZE.html) bytes.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut sum = 0u64;
+    /// for value in file.records::<u32>() {
+    ///     sum += value.unwrap() as u64;
+    /// }
+    /// ```
+    fn records<T: Packed>(&mut self) -> Records<'_, Self, T, NativeEndian> where Self: Sized;
+
+    /// Same as [`records`](#tymethod.records) but reading by batches of `capacity` bytes, rounded
+    /// down to a multiple of the size of `T` with a minimum of one value.
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, Self, T, NativeEndian> where Self: Sized;
+
+    /// Same as [`unpack_to_end`](#tymethod.unpack_to_end) but a `LimitExceeded` error is
+    /// returned, and `buf` is left unchanged, if the input holds more than `max_items` values, so
+    /// an untrusted stream cannot exhaust the memory.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// let mut samples = Vec::<f32>::new();
+    /// stream.unpack_to_end_limited(&mut samples, 1 << 20).unwrap();
+    /// ```
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize>;
+
+    /// Same as [`unpack_n`](#tymethod.unpack_n) but a `LimitExceeded` error is returned, before
+    /// allocating anything, if `count` is larger than `max_items`. It protects against bogus
+    /// lengths read from untrusted input.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let num_samples : u32 = file.unpack().unwrap();
+    /// let samples : Vec<f32> = file.unpack_n_limited(num_samples as usize, 1 << 20).unwrap();
+    /// ```
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>>;
+
+    /// Same as [`unpack_to_end`](#tymethod.unpack_to_end) but `policy` chooses what happens when
+    /// the number of bytes read is not a multiple of the size of `T`: the whole values can be
+    /// kept and the trailing bytes reported or returned instead of failing.
+    ///
+    /// ```
+    /// # use bytepack::{Unpacker, TrailingPolicy};
+    /// let mut reader = &[1u8, 0, 2, 0, 0xFF][..];
+    /// let mut values = Vec::<u16>::new();
+    /// let end = reader.unpack_to_end_with_trailing(&mut values, TrailingPolicy::ReturnTrailingBytes).unwrap();
+    /// assert_eq!(end.count, 2);
+    /// assert_eq!(end.trailing, [0xFF]);
+    /// ```
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd>;
+
+    /// Same as [`unpack`](#tymethod.unpack) but the value is checked with
+    /// [`PackedValidate::validate`](trait.PackedValidate.html#tymethod.validate) and an
+    /// `InvalidValue` error describing the problem is returned if it is invalid.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let channels : [u16; 4] = file.unpack_validated().unwrap();
+    /// ```
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T>;
+
+    /// Same as [`unpack_exact`](#tymethod.unpack_exact) but the values are checked with
+    /// [`PackedValidate::validate`](trait.PackedValidate.html#tymethod.validate) and an
+    /// `InvalidValue` error giving the index of the first invalid value is returned if any is
+    /// invalid. `buf` is filled in any case.
+    ///
+    /// ```no_run
+    /// # use bytepack::{Unpacker, Finite};
+    /// # use std::fs::File;
+    /// let mut file = File::open("samples").unwrap();
+    /// let mut samples = vec![Finite::new(0f64).unwrap(); 1024];
+    /// file.unpack_exact_validated(&mut samples[..]).unwrap();
+    /// ```
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()>;
+
+    /// Unpack values of type `T` into every `stride`-th value of `buf`, starting with the value
+    /// at index `offset`, leaving the other values untouched. This allows deinterleaving data
+    /// directly into a planar buffer. An `InvalidInput` error is returned if `stride` is zero.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("x").unwrap();
+    /// let mut xyz = [0f32; 6];
+    /// // reads 2 values into xyz[0] and xyz[3]
+    /// file.unpack_strided(&mut xyz[..], 0, 3).unwrap();
+    /// ```
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()>;
+
+    /// Unpack `frames` frames of `channels` interleaved values of type `T`, such as the samples
+    /// of a multi-channel audio file, and return one `Vec` per channel. The values are read
+    /// through an internal fixed-size buffer.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("stereo").unwrap();
+    /// let channels = file.unpack_deinterleaved::<i16>(2, 44100).unwrap();
+    /// let (left, right) = (&channels[0], &channels[1]);
+    /// ```
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>>;
+
+    /// Unpack rows of `row_len` values of type `T` to fill `buf`, each row starting
+    /// `row_stride` bytes after the previous one. The padding bytes following each row, the last
+    /// one included, are skipped. An `InvalidInput` error is returned if the length of `buf` is
+    /// not a multiple of `row_len` or if `row_stride` is shorter than a row.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEUnpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("pixels").unwrap();
+    /// // 3 rows of 3 RGB pixels, each row padded to 12 bytes
+    /// let mut pixels = [[0u8; 3]; 9];
+    /// file.unpack_rows(&mut pixels[..], 3, 12).unwrap();
+    /// ```
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()>;
+
+    /// Unpack a `rows` by `cols` matrix stored in column-major order into `buf` in row-major
+    /// order, transposing it while streaming. An `InvalidInput` error is returned if `buf` does
+    /// not hold exactly `rows * cols` values.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEUnpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("matrix").unwrap();
+    /// let mut matrix = [[0f64; 4]; 3];
+    /// file.unpack_transposed(3, 4, matrix.as_flattened_mut()).unwrap();
+    /// ```
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()>;
+
+    /// Return a [`Chunks`](struct.Chunks.html) reader unpacking values of type `T` by chunks of
+    /// `chunk_len` values, at least one, until `EOF` is reached. The same buffer is reused for
+    /// every chunk so the memory used does not depend on the size of the input.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("test").unwrap();
+    /// let mut chunks = file.unpack_chunks::<u32>(1024);
+    /// let mut sum = 0u64;
+    /// while let Some(chunk) = chunks.next_chunk() {
+    ///     sum += chunk.unwrap().iter().map(|&v| v as u64).sum::<u64>();
+    /// }
+    /// ```
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, Self, T, NativeEndian> where Self: Sized;
+
+    /// Unpack a type-length-value record: a tag of type `Tag`, a length in bytes of type `L`
+    /// and the payload, which is returned as bytes. The memory allocated for the payload is
+    /// bounded by the number of bytes actually read, so an untrusted length cannot cause a huge
+    /// allocation.
+    ///
+    /// ```no_run
+    /// # use bytepack::BEUnpacker;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// let (tag, payload) = stream.unpack_tlv::<u8, u16>().unwrap();
+    /// ```
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)>;
+
+    /// Same as [`unpack_tlv`](#tymethod.unpack_tlv) but the payload is unpacked as a value of
+    /// type `T`. A `ShortRead` or `TrailingBytes` error is returned, after the whole record has
+    /// been read, if the length is not the size of `T`.
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)>;
+
+    /// Unpack a frame made of a length in bytes of type `L` followed by the values of type `T`
+    /// it holds. A `LimitExceeded` error is returned, before reading the values, if the length is
+    /// above `max_len` bytes, and a `TrailingBytes` error, after reading the whole frame, if it is
+    /// not a multiple of the size of `T`.
+    ///
+    /// ```no_run
+    /// # use bytepack::BEUnpacker;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// let samples = stream.unpack_framed::<u32, f32>(1 << 20).unwrap();
+    /// ```
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>>;
+
+    /// Same as [`unpack_framed`](#tymethod.unpack_framed) but the frame must hold exactly one
+    /// value, which is returned. After skipping the whole frame, a `ShortRead` error is returned if
+    /// it is shorter than the value and a `TrailingBytes` error if it is longer.
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T>;
+
+    /// Read `magic.len()` bytes and check they are `magic`, returning an `InvalidValue` error
+    /// giving the expected and found bytes otherwise.
+    ///
+    /// ```no_run
+    /// # use bytepack::Unpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("image.png").unwrap();
+    /// file.expect_magic(b"\x89PNG\r\n\x1a\n").unwrap();
+    /// ```
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()>;
+
+    /// Same as [`expect_magic`](#tymethod.expect_magic) but the magic is a value of type `T`.
+    ///
+    /// ```no_run
+    /// # use bytepack::BEUnpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("Main.class").unwrap();
+    /// file.expect_magic_value(0xCAFEBABEu32).unwrap();
+    /// ```
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()>;
+
+    /// Call `f` with a [`Crc32Reader`](struct.Crc32Reader.html) wrapping this reader, then
+    /// unpack a trailing CRC-32 as a `u32` and check it is the one of the bytes read by `f`,
+    /// returning an `InvalidValue` error otherwise. The payload never has to be buffered.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEUnpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("record").unwrap();
+    /// let (id, value) = file.unpack_with_checksum(|r| {
+    ///     let id : u32 = r.unpack()?;
+    ///     let value : f64 = r.unpack()?;
+    ///     Ok((id, value))
+    /// }).unwrap();
+    /// ```
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+
+    /// Unpack values of type `S` and store them in `buf` converted by `convert`, such as PCM
+    /// samples normalized to floats, in a single pass through an internal fixed-size buffer.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEUnpacker;
+    /// # use std::fs::File;
+    /// let mut file = File::open("pcm").unwrap();
+    /// let mut samples = vec![0f32; 44100];
+    /// file.unpack_converted(&mut samples[..], |s: i16| s as f32 / 32768.).unwrap();
+    /// ```
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()>;
+
+    /// Unpack an integer of type `S` and convert it to the integer type `T`, applying the
+    /// `overflow` policy if it does not fit.
+    ///
+    /// ```no_run
+    /// # use bytepack::{LEUnpacker, Overflow};
+    /// # use std::fs::File;
+    /// let mut file = File::open("counters").unwrap();
+    /// let count = file.unpack_as::<u16, u64>(Overflow::Error).unwrap();
+    /// ```
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T>;
+
+    /// Same as [`unpack_as`](#tymethod.unpack_as) but filling `buf`. With the `Overflow::Error`
+    /// policy, an `InvalidValue` error giving the index of the first value out of range is
+    /// returned after `buf` has been filled, such values being replaced by zero.
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()>;
+
+    /// Unpack values until `EOF` is reached and append them to `buf`, a collection which cannot
+    /// grow, without allocating. The number of values read is returned. A `LimitExceeded` error
+    /// is returned if some data remains once `buf` is full and a `TrailingBytes` error if the
+    /// data ends with a partial value, in both cases leaving `buf` unchanged.
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize>;
+
+    /// Unpack `count` values into a new collection which cannot grow, without allocating. A
+    /// `LimitExceeded` error is returned if `count` exceeds the capacity of the collection.
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C>;
+
+    /// Unpack `count` values and append them to `target`, like a `VecDeque`, through its `Extend`
+    /// implementation. The values are read in chunks through a buffer on the stack, without any
+    /// allocation other than the growth of `target`. If an error occurs, the values of the
+    /// chunks read successfully have already been appended. As collections usually implement
+    /// `Extend` for both values and references, the type of the values generally has to be given
+    /// explicitly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    /// use bytepack::LEUnpacker;
+    ///
+    /// let mut recent : VecDeque<u16> = VecDeque::from(vec![1, 2]);
+    /// let mut data: &[u8] = &[3, 0, 4, 0];
+    /// recent.pop_front();
+    /// data.unpack_extend::<u16, _>(&mut recent, 2).unwrap();
+    /// assert_eq!(recent, [2, 3, 4]);
+    /// ```
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()>;
+
+    /// Unpack a single value of type `T`, returning `None` if `EOF` is reached before its first
+    /// byte, meaning the stream ended cleanly between two values. A `ShortRead` error is only
+    /// returned if the stream ends in the middle of the value.
+    ///
+    /// ```
+    /// use bytepack::LEUnpacker;
+    ///
+    /// let mut data: &[u8] = &[1, 0, 2];
+    /// assert_eq!(data.unpack_maybe::<u16>().unwrap(), Some(1));
+    /// assert!(data.unpack_maybe::<u16>().is_err());
+    /// assert_eq!(data.unpack_maybe::<u16>().unwrap(), None);
+    /// ```
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>>;
+
+    /// Unpack values and append them to `buf` until `sentinel` is found, like the `0xFFFF`
+    /// terminating some arrays. The sentinel is consumed and only appended with
+    /// [`Sentinel::Include`](enum.Sentinel.html#variant.Include). The number of values appended
+    /// is returned. A `LimitExceeded` error is returned if more than `max_items` values precede
+    /// the sentinel and a `ShortRead` error if `EOF` is reached first, in both cases leaving
+    /// `buf` unchanged. As the values are read one by one, a buffered reader should be used.
+    ///
+    /// ```
+    /// use bytepack::{BEUnpacker, Sentinel};
+    ///
+    /// let mut data: &[u8] = &[0, 1, 0, 2, 0xFF, 0xFF, 0, 3];
+    /// let mut ids = Vec::<u16>::new();
+    /// assert_eq!(data.unpack_until(0xFFFF, &mut ids, Sentinel::Exclude, 16).unwrap(), 2);
+    /// assert_eq!(ids, [1, 2]);
+    /// ```
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize>;
+
+    /// Unpack values and append them to `buf` as long as `predicate` accepts them. The first
+    /// rejected value is consumed and returned, or `None` if `EOF` is reached first. A
+    /// `LimitExceeded` error is returned, and `buf` is left unchanged, if more than `max_items`
+    /// values are accepted. As the values are read one by one, a buffered reader should be used.
+    ///
+    /// ```
+    /// use bytepack::LEUnpacker;
+    ///
+    /// let mut data: &[u8] = &[1, 2, 3, 200, 4];
+    /// let mut small = Vec::<u8>::new();
+    /// assert_eq!(data.unpack_while(&mut small, 16, |&b| b < 100).unwrap(), Some(200));
+    /// assert_eq!(small, [1, 2, 3]);
+    /// ```
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>>;
+
+    /// Same as [`unpack_while`](#tymethod.unpack_while) but the first rejected value is put
+    /// back by seeking before it, so the next read returns it again. The number of values
+    /// appended to `buf` is returned.
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where Self: Seek;
+
+    /// Return the number of whole values of type `T` between the current position and the end,
+    /// followed by the number of bytes of the partial value ending the data, if any, using
+    /// `Seek` and leaving the position unchanged. This allows reserving memory exactly and
+    /// detecting truncated data before reading it.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use bytepack::Unpacker;
+    ///
+    /// let mut data = Cursor::new(vec![0u8; 11]);
+    /// assert_eq!(data.remaining_items::<u32>().unwrap(), (2, 3));
+    /// ```
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where Self: Seek;
+
+    /// Same as [`unpack`](#tymethod.unpack) but the value is decoded directly out of the buffer
+    /// of a `BufRead` reader, like a `BufReader`, when it holds enough bytes, which avoids going
+    /// through `Read` for every small value.
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    /// use bytepack::BEUnpacker;
+    ///
+    /// let mut reader = BufReader::new(&[0u8, 0, 0, 7, 0, 9][..]);
+    /// assert_eq!(reader.unpack_buffered::<u32>().unwrap(), 7);
+    /// assert_eq!(reader.unpack_buffered::<u16>().unwrap(), 9);
+    /// ```
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where Self: BufRead;
+
+    /// Unpack a header of type `H` followed by the number of values of type `T` it gives, like
+    /// a C structure ending with a flexible array member. The values are read into a single
+    /// allocation. A `LimitExceeded` error is returned if the header gives more than
+    /// `max_items` values, so an untrusted header cannot exhaust the memory.
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>>;
+
+    /// Unpack a variable-size record of type `T`, reading its header first and then the size of
+    /// body it gives. A `LimitExceeded` error is returned if the body is larger than `max_len`
+    /// bytes and a `TrailingBytes` error if `T` did not unpack all the bytes of its body.
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T>;
+
+    /// Same as [`unpack_strided`](#tymethod.unpack_strided) but the values are read in `scratch`
+    /// instead of a newly allocated buffer.
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()>;
+
+    /// Same as [`unpack_deinterleaved`](#tymethod.unpack_deinterleaved) but the frames are read in
+    /// `scratch` instead of a newly allocated buffer.
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>>;
+
+    /// Same as [`unpack_converted`](#tymethod.unpack_converted) but the values are read in
+    /// `scratch` instead of a newly allocated buffer.
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()>;
+}
+
+/// `Packer` provides the `std::io::Write` API but for any type `T` implementing 
+/// [`Packed`](trait.Packed.html). It does not perform any endianness conversion and thus always 
+/// writes data using the system endianness.
+///
+/// # Example
+/// 
+/// Example of writing a file containing a few float samples.
+/// 
+/// ```no_run
+/// use std::fs::File;
+/// use std::iter::repeat;
+///
+/// use bytepack::Packer;
+///
+/// fn write_samples(file: &str, samples: &Vec<f32>) {
+///     let mut file = File::create(file).unwrap();
+///     file.pack(samples.len() as u32).unwrap();
+///     file.pack_all(&samples[..]).unwrap();
+/// }
+/// ```
+pub trait Packer {
+
+    /// Pack a single value of type `T`.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// file.pack(42f32).unwrap();
+    /// ```
+    fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
+
+    /// Pack a single value of type `T` passed by reference. In the native byte order the value is
+    /// written directly from its location, which avoids copying large records onto the stack.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// let table = [[0u32; 32]; 32];
+    /// file.pack_ref(&table).unwrap();
+    /// ```
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()>;
+
+    /// Pack all the values of type `T` from `buf`.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// let mut float_buffer = vec![666u16; 10];
+    /// file.pack_all(&mut float_buffer[..]).unwrap();
+    /// ```
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+
+    /// Pack a single value of type `T` in the `endianness` byte order.
+    ///
+    /// ```no_run
+    /// # use bytepack::{Endianness, Packer};
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// file.pack_with(42f32, Endianness::Little).unwrap();
+    /// ```
+    fn pack_with<T: Packed>(&mut self, t: T, endianness: Endianness) -> Result<()>;
+
+    /// Same as [`pack_ref`](#tymethod.pack_ref) but in the `endianness` byte order.
+    fn pack_ref_with<T: Packed>(&mut self, t: &T, endianness: Endianness) -> Result<()>;
+
+    /// Same as [`pack_all`](#tymethod.pack_all) but in the `endianness` byte order. If an 
+    /// endianness switch is needed, the values are converted and written through an internal 
+    /// fixed-size buffer.
+    fn pack_all_with<T: Packed>(&mut self, buf: &[T], endianness: Endianness) -> Result<()>;
+
+    /// Pack all the values of type `T` yielded by `iter`. The values are written through an 
+    /// internal fixed-size buffer so the whole sequence never has to be held in memory.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// file.pack_iter((0..1000000).map(|i| (i as f32).sin())).unwrap();
+    /// ```
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()>;
+
+    /// Pack all the values of type `T` from each slice of `bufs`, gathering them in as few 
+    /// `write_vectored` calls as possible for writers supporting it. If an endianness switch is 
+    /// needed, the slices are written one after the other instead.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// let header = [1u32, 8];
+    /// let payload = [3u32, 4];
+    /// stream.pack_all_vectored(&[&header[..], &payload[..]]).unwrap();
+    /// ```
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()>;
+
+    /// Pack `t` at `offset` bytes from the start of the stream, then restore the position of the
+    /// stream, even if an error occurred. This allows patching header fields once the rest of the
+    /// data has been written.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("test").unwrap();
+    /// file.pack(0u64).unwrap();
+    /// file.pack_all(&[1u32, 2, 3, 4]).unwrap();
+    /// file.pack_at(0, 16u64).unwrap();
+    /// ```
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where Self: Seek;
+
+    /// Pack every `stride`-th value of `buf`, starting with the value at index `offset`, without
+    /// building a temporary contiguous buffer. This allows extracting a single column out of an
+    /// interleaved buffer. An `InvalidInput` error is returned if `stride` is zero.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("x").unwrap();
+    /// let xyz = [0f32, 1., 2., 3., 4., 5.];
+    /// // writes 0 and 3
+    /// file.pack_strided(&xyz[..], 0, 3).unwrap();
+    /// ```
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()>;
+
+    /// Pack the field selected by `field` of every value of `buf`, which does not need to be
+    /// `Packed` itself.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// struct Point {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32
+    /// }
+    ///
+    /// let mut file = File::create("x").unwrap();
+    /// let points = [Point { x: 0., y: 1., z: 2. }, Point { x: 3., y: 4., z: 5. }];
+    /// file.pack_field(&points[..], |p| &p.x).unwrap();
+    /// ```
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()>;
+
+    /// Pack the values of the `channels` slices interleaved, one frame made of a value of each
+    /// channel after the other. An `InvalidInput` error is returned if the channels do not all
+    /// have the same length.
+    ///
+    /// ```no_run
+    /// # use bytepack::Packer;
+    /// # use std::fs::File;
+    /// let mut file = File::create("stereo").unwrap();
+    /// let left = vec![0i16; 44100];
+    /// let right = vec![0i16; 44100];
+    /// file.pack_interleaved(&[&left[..], &right[..]]).unwrap();
+    /// ```
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()>;
+
+    /// Pack the values of `buf` as rows of `row_len` values, each row being followed by zero
+    /// padding bytes so it starts `row_stride` bytes after the previous one. See
+    /// [`Unpacker::unpack_rows`](trait.Unpacker.html#tymethod.unpack_rows).
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()>;
+
+    /// Pack the `rows` by `cols` matrix stored in row-major order in `buf` in column-major
+    /// order. See [`Unpacker::unpack_transposed`](trait.Unpacker.html#tymethod.unpack_transposed).
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()>;
+
+    /// Pack a type-length-value record: `tag`, the length of `payload` in bytes as a value of
+    /// type `L` and `payload`. An `InvalidInput` error is returned if the length does not fit in
+    /// `L`.
+    ///
+    /// ```no_run
+    /// # use bytepack::BEPacker;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// stream.pack_tlv::<u8, u16>(3, b"hello").unwrap();
+    /// ```
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()>;
+
+    /// Same as [`pack_tlv`](#tymethod.pack_tlv) but the payload is the packed `value`.
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()>;
+
+    /// Pack a frame made of the length of `buf` in bytes as a value of type `L` followed by the
+    /// values of `buf`. A single value can be packed with `std::slice::from_ref`. An
+    /// `InvalidInput` error is returned if the length does not fit in `L`.
+    ///
+    /// ```no_run
+    /// # use bytepack::BEPacker;
+    /// # use std::net::TcpStream;
+    /// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+    /// stream.pack_framed::<u32, f32>(&[0.5, 1.5]).unwrap();
+    /// ```
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+
+    /// Call `f` with a [`Crc32Writer`](struct.Crc32Writer.html) wrapping this writer, then pack
+    /// the CRC-32 of the bytes written by `f` as a trailing `u32`.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEPacker;
+    /// # use std::fs::File;
+    /// let mut file = File::create("record").unwrap();
+    /// file.pack_with_checksum(|w| {
+    ///     w.pack(42u32)?;
+    ///     w.pack(0.5f64)
+    /// }).unwrap();
+    /// ```
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+
+    /// Pack the values of `buf`, which do not need to be `Packed`, converted by `convert` to
+    /// values of type `D`, through an internal fixed-size buffer.
+    ///
+    /// ```no_run
+    /// # use bytepack::LEPacker;
+    /// # use std::fs::File;
+    /// let mut file = File::create("pcm").unwrap();
+    /// let samples = vec![0.5f32; 44100];
+    /// file.pack_converted(&samples[..], |&s| (s * 32767.) as i16).unwrap();
+    /// ```
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()>;
+
+    /// Same as [`pack_all`](#tymethod.pack_all) but the values needing an endianness switch are
+    /// converted in `scratch` instead of a newly allocated buffer.
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()>;
+
+    /// Same as [`pack_iter`](#tymethod.pack_iter) but the values are gathered in `scratch`
+    /// instead of a newly allocated buffer.
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()>;
+
+    /// Pack `count` copies of `t`, like zero padding blocks or silence samples. The copies are
+    /// written through an internal fixed-size buffer on the stack, so the whole run is never
+    /// allocated.
+    ///
+    /// ```
+    /// use bytepack::BEPacker;
+    ///
+    /// let mut buffer = Vec::new();
+    /// buffer.pack_repeat(&0xFFFFu16, 3).unwrap();
+    /// assert_eq!(buffer, [0xFF; 6]);
+    /// ```
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()>;
+
+    /// Pack the header of `record` followed by its tail. An `InvalidInput` error is returned,
+    /// and nothing is written, if the number of values given by the header does not match the
+    /// length of the tail.
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()>;
+
+    /// Pack the variable-size record `t`, its header followed by its body. An `InvalidInput`
+    /// error is returned, and nothing is written, if the size of the body does not match the
+    /// size given by the header.
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()>;
+}
+
+impl<R> Unpacker for R where R: Read {
+    fn unpack<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<NativeEndian, T, R>(self)
+    }
+
+    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn unpack_with<T: Packed>(&mut self, endianness: Endianness) -> Result<T> {
+        match endianness {
+            Endianness::Little => order::unpack::<LittleEndian, T, R>(self),
+            Endianness::Big => order::unpack::<BigEndian, T, R>(self)
+        }
+    }
+
+    fn unpack_to_end_with<T: Packed>(&mut self, buf: &mut Vec<T>, endianness: Endianness) -> Result<usize> {
+        match endianness {
+            Endianness::Little => order::unpack_to_end::<LittleEndian, T, R>(self, buf),
+            Endianness::Big => order::unpack_to_end::<BigEndian, T, R>(self, buf)
+        }
+    }
+
+    fn unpack_exact_with<T: Packed>(&mut self, buf: &mut [T], endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::unpack_exact::<LittleEndian, T, R>(self, buf),
+            Endianness::Big => order::unpack_exact::<BigEndian, T, R>(self, buf)
+        }
+    }
+
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, R, T, NativeEndian> {
+        UnpackIter::new(self)
+    }
+
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<NativeEndian, T, R>(self, count)
+    }
+
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>> {
+        order::unpack_n::<NativeEndian, T, R>(self, len).map(Vec::into_boxed_slice)
+    }
+
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>> {
+        order::unpack_arc_slice::<NativeEndian, T, R>(self, len)
+    }
+
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+        order::unpack_exact_uninit::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()> {
+        order::unpack_append::<NativeEndian, T, R>(self, buf, count)
+    }
+
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where R: Seek {
+        order::unpack_to_end_sized::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn peek<T: Packed>(&mut self) -> Result<T> where R: Seek {
+        order::peek::<NativeEndian, T, R>(self)
+    }
+
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()> {
+        order::skip::<T, R>(self, count)
+    }
+
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where R: Seek {
+        order::skip_seek::<T, R>(self, count)
+    }
+
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where R: Seek {
+        order::unpack_at::<NativeEndian, T, R>(self, offset)
+    }
+
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where R: Seek {
+        order::unpack_exact_at::<NativeEndian, T, R>(self, offset, buf)
+    }
+
+    fn transaction<U, F: FnOnce(&mut R) -> Result<U>>(&mut self, f: F) -> Result<U> where R: Seek {
+        order::transaction(self, f)
+    }
+
+    fn records<T: Packed>(&mut self) -> Records<'_, R, T, NativeEndian> {
+        Records::new(self, RECORDS_BUFFER_SIZE)
+    }
+
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, R, T, NativeEndian> {
+        Records::new(self, capacity)
+    }
+
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize> {
+        order::unpack_to_end_limited::<NativeEndian, T, R>(self, buf, max_items)
+    }
+
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>> {
+        order::unpack_n_limited::<NativeEndian, T, R>(self, count, max_items)
+    }
+
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd> {
+        order::unpack_to_end_trailing::<NativeEndian, T, R>(self, buf, policy)
+    }
+
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T> {
+        order::unpack_validated::<NativeEndian, T, R>(self)
+    }
+
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact_validated::<NativeEndian, T, R>(self, buf)
+    }
+
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()> {
+        order::unpack_strided::<NativeEndian, T, R>(self, buf, offset, stride)
+    }
+
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved::<NativeEndian, T, R>(self, channels, frames)
+    }
+
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::unpack_rows::<NativeEndian, T, R>(self, buf, row_len, row_stride)
+    }
+
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()> {
+        order::unpack_transposed::<NativeEndian, T, R>(self, rows, cols, buf)
+    }
+
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, R, T, NativeEndian> {
+        Chunks::new(self, chunk_len)
+    }
+
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)> {
+        order::unpack_tlv::<NativeEndian, Tag, L, R>(self)
+    }
+
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)> {
+        order::unpack_tlv_value::<NativeEndian, Tag, L, T, R>(self)
+    }
+
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>> {
+        order::unpack_framed::<NativeEndian, L, T, R>(self, max_len)
+    }
+
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T> {
+        order::unpack_framed_value::<NativeEndian, L, T, R>(self)
+    }
+
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()> {
+        order::expect_magic(self, magic)
+    }
+
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()> {
+        order::expect_magic_value::<NativeEndian, T, R>(self, magic)
+    }
+
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut R>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::unpack_with_checksum::<NativeEndian, U, F, R>(self, f)
+    }
+
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()> {
+        order::unpack_converted::<NativeEndian, S, D, F, R>(self, buf, convert)
+    }
+
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T> {
+        order::unpack_as::<NativeEndian, S, T, R>(self, overflow)
+    }
+
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()> {
+        order::unpack_exact_as::<NativeEndian, S, T, R>(self, buf, overflow)
+    }
+
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize> {
+        order::unpack_to_end_fixed::<NativeEndian, C, R>(self, buf)
+    }
+
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C> {
+        order::unpack_n_fixed::<NativeEndian, C, R>(self, count)
+    }
+
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()> {
+        order::unpack_extend::<NativeEndian, T, E, R>(self, target, count)
+    }
+
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>> {
+        order::unpack_maybe::<NativeEndian, T, R>(self)
+    }
+
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize> {
+        order::unpack_until::<NativeEndian, T, R>(self, &sentinel, buf, policy, max_items)
+    }
+
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>> {
+        order::unpack_while::<NativeEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where R: Seek {
+        order::unpack_while_put_back::<NativeEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where R: Seek {
+        order::remaining_items::<T, R>(self)
+    }
+
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where R: BufRead {
+        order::unpack_buffered::<NativeEndian, T, R>(self)
+    }
+
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>> {
+        order::unpack_with_tail::<NativeEndian, H, T, R>(self, max_items)
+    }
+
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T> {
+        order::unpack_header_packed::<NativeEndian, T, R>(self, max_len)
+    }
+
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_strided_with_scratch::<NativeEndian, T, R>(self, buf, offset, stride, scratch)
+    }
+
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved_with_scratch::<NativeEndian, T, R>(self, channels, frames, scratch)
+    }
+
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_converted_with_scratch::<NativeEndian, S, D, F, R>(self, buf, convert, scratch)
+    }
+}
+
+impl<W> Packer for W where W: Write {
+    fn pack<T: Packed>(&mut self, t: T) -> Result<()> {
+        order::pack::<NativeEndian, T, W>(self, t)
+    }
+
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<NativeEndian, T, W>(self, t)
+    }
+
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<NativeEndian, T, W>(self, buf)
+    }
+
+    fn pack_with<T: Packed>(&mut self, t: T, endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::pack::<LittleEndian, T, W>(self, t),
+            Endianness::Big => order::pack::<BigEndian, T, W>(self, t)
+        }
+    }
+
+    fn pack_ref_with<T: Packed>(&mut self, t: &T, endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::pack_ref::<LittleEndian, T, W>(self, t),
+            Endianness::Big => order::pack_ref::<BigEndian, T, W>(self, t)
+        }
+    }
+
+    fn pack_all_with<T: Packed>(&mut self, buf: &[T], endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::pack_all::<LittleEndian, T, W>(self, buf),
+            Endianness::Big => order::pack_all::<BigEndian, T, W>(self, buf)
+        }
+    }
+
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()> {
+        order::pack_iter::<NativeEndian, T, I, W>(self, iter)
+    }
+
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()> {
+        order::pack_all_vectored::<NativeEndian, T, W>(self, bufs)
+    }
+
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where W: Seek {
+        order::pack_at::<NativeEndian, T, W>(self, offset, t)
+    }
+
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()> {
+        order::pack_strided::<NativeEndian, T, W>(self, buf, offset, stride)
+    }
+
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()> {
+        order::pack_field::<NativeEndian, T, U, F, W>(self, buf, field)
+    }
+
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()> {
+        order::pack_interleaved::<NativeEndian, T, W>(self, channels)
+    }
+
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::pack_rows::<NativeEndian, T, W>(self, buf, row_len, row_stride)
+    }
+
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()> {
+        order::pack_transposed::<NativeEndian, T, W>(self, rows, cols, buf)
+    }
+
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()> {
+        order::pack_tlv::<NativeEndian, Tag, L, W>(self, tag, payload)
+    }
+
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()> {
+        order::pack_tlv_value::<NativeEndian, Tag, L, T, W>(self, tag, value)
+    }
+
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_framed::<NativeEndian, L, T, W>(self, buf)
+    }
+
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut W>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::pack_with_checksum::<NativeEndian, U, F, W>(self, f)
+    }
+
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()> {
+        order::pack_converted::<NativeEndian, S, D, F, W>(self, buf, convert)
+    }
+
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_all_with_scratch::<NativeEndian, T, W>(self, buf, scratch)
+    }
+
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_iter_with_scratch::<NativeEndian, T, I, W>(self, iter, scratch)
+    }
+
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()> {
+        order::pack_repeat::<NativeEndian, T, W>(self, t, count)
+    }
+
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()> {
+        order::pack_with_tail::<NativeEndian, H, T, W>(self, record)
+    }
+
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()> {
+        order::pack_header_packed::<NativeEndian, T, W>(self, t)
+    }
+}
+
+/// Provides the same API and functionnality as [`Unpacker`](trait.Unpacker.html) but ensure that 
+/// the data is in little endian format. See [`Unpacker`](trait.Unpacker.html) for more 
+/// documentation.
+pub trait LEUnpacker {
+    fn unpack<T: Packed>(&mut self) -> Result<T>;
+    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
+    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, Self, T, LittleEndian> where Self: Sized;
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>>;
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>>;
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]>;
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()>;
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where Self: Seek;
+    fn peek<T: Packed>(&mut self) -> Result<T> where Self: Seek;
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()>;
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where Self: Seek;
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where Self: Seek;
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where Self: Seek;
+    fn transaction<U, F: FnOnce(&mut Self) -> Result<U>>(&mut self, f: F) -> Result<U> where Self: Seek + Sized;
+    fn records<T: Packed>(&mut self) -> Records<'_, Self, T, LittleEndian> where Self: Sized;
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, Self, T, LittleEndian> where Self: Sized;
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize>;
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>>;
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd>;
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T>;
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()>;
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>>;
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()>;
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, Self, T, LittleEndian> where Self: Sized;
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)>;
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)>;
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>>;
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T>;
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()>;
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()>;
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()>;
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T>;
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()>;
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize>;
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C>;
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()>;
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>>;
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize>;
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>>;
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where Self: Seek;
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where Self: Seek;
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where Self: BufRead;
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>>;
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T>;
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>>;
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()>;
+}
+
+/// Provides the same API and functionnality as [`Packer`](trait.Packer.html) but ensure that 
+/// the data is in little endian format. See [`Packer`](trait.Packer.html) for more 
+/// documentation.
+pub trait LEPacker {
+    fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()>;
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()>;
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()>;
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where Self: Seek;
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()>;
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()>;
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()>;
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()>;
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()>;
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()>;
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()>;
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()>;
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()>;
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()>;
 }
 
-/// Provides the same API and functionnality as [`Packer`](trait.Packer.html) but ensure that 
-/// the data is in little endian format. See [`Packer`](trait.Packer.html) for more 
-/// documentation.
-pub trait LEPacker {
-    fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
+impl<R> LEUnpacker for R where R: Read {
+    fn unpack<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<LittleEndian, T, R>(self)
+    }
+
+    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, R, T, LittleEndian> {
+        UnpackIter::new(self)
+    }
+
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<LittleEndian, T, R>(self, count)
+    }
+
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>> {
+        order::unpack_n::<LittleEndian, T, R>(self, len).map(Vec::into_boxed_slice)
+    }
+
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>> {
+        order::unpack_arc_slice::<LittleEndian, T, R>(self, len)
+    }
+
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+        order::unpack_exact_uninit::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()> {
+        order::unpack_append::<LittleEndian, T, R>(self, buf, count)
+    }
+
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where R: Seek {
+        order::unpack_to_end_sized::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn peek<T: Packed>(&mut self) -> Result<T> where R: Seek {
+        order::peek::<LittleEndian, T, R>(self)
+    }
+
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()> {
+        order::skip::<T, R>(self, count)
+    }
+
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where R: Seek {
+        order::skip_seek::<T, R>(self, count)
+    }
+
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where R: Seek {
+        order::unpack_at::<LittleEndian, T, R>(self, offset)
+    }
+
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where R: Seek {
+        order::unpack_exact_at::<LittleEndian, T, R>(self, offset, buf)
+    }
+
+    fn transaction<U, F: FnOnce(&mut R) -> Result<U>>(&mut self, f: F) -> Result<U> where R: Seek {
+        order::transaction(self, f)
+    }
+
+    fn records<T: Packed>(&mut self) -> Records<'_, R, T, LittleEndian> {
+        Records::new(self, RECORDS_BUFFER_SIZE)
+    }
+
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, R, T, LittleEndian> {
+        Records::new(self, capacity)
+    }
+
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize> {
+        order::unpack_to_end_limited::<LittleEndian, T, R>(self, buf, max_items)
+    }
+
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>> {
+        order::unpack_n_limited::<LittleEndian, T, R>(self, count, max_items)
+    }
+
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd> {
+        order::unpack_to_end_trailing::<LittleEndian, T, R>(self, buf, policy)
+    }
+
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T> {
+        order::unpack_validated::<LittleEndian, T, R>(self)
+    }
+
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact_validated::<LittleEndian, T, R>(self, buf)
+    }
+
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()> {
+        order::unpack_strided::<LittleEndian, T, R>(self, buf, offset, stride)
+    }
+
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved::<LittleEndian, T, R>(self, channels, frames)
+    }
+
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::unpack_rows::<LittleEndian, T, R>(self, buf, row_len, row_stride)
+    }
 
-    /// Here T needs to be `Clone` because the endianness switch cannot be done in-place. This method 
-    /// thus allocates a copy of `buf` if an endianness switch is needed.
-    fn pack_ref<T: Packed + Clone>(&mut self, t: &T) -> Result<()>;
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()> {
+        order::unpack_transposed::<LittleEndian, T, R>(self, rows, cols, buf)
+    }
 
-    /// Here T needs to be `Clone` because the endianness switch cannot be done in-place. This method 
-    /// thus allocates a copy of `buf` if an endianness switch is needed.
-    fn pack_all<T: Packed + Clone>(&mut self, buf: &[T]) -> Result<()>;
-}
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, R, T, LittleEndian> {
+        Chunks::new(self, chunk_len)
+    }
 
-impl<R> LEUnpacker for R where R: Read {
-    fn unpack<T: Packed>(&mut self) -> Result<T> {
-        if cfg!(target_endian = "big") {
-            let mut t = Unpacker::unpack::<T>(self)?;
-            t.switch_endianness();
-            Ok(t)
-        }
-        else {
-            Unpacker::unpack(self)
-        }
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)> {
+        order::unpack_tlv::<LittleEndian, Tag, L, R>(self)
     }
 
-    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
-        if cfg!(target_endian = "big") {
-            let size = Unpacker::unpack_to_end(self, buf)?;
-            let start = buf.len() - size;
-            for i in start..buf.len() {
-                buf[i].switch_endianness();
-            }
-            Ok(size)
-        }
-        else {
-            Unpacker::unpack_to_end(self, buf)
-        }
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)> {
+        order::unpack_tlv_value::<LittleEndian, Tag, L, T, R>(self)
     }
 
-    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
-        if cfg!(target_endian = "big") {
-            Unpacker::unpack_exact(self, buf)?;
-            for i in 0..buf.len() {
-                buf[i].switch_endianness();
-            }
-            Ok(())
-        }
-        else {
-            Unpacker::unpack_exact(self, buf)
-        }
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>> {
+        order::unpack_framed::<LittleEndian, L, T, R>(self, max_len)
+    }
+
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T> {
+        order::unpack_framed_value::<LittleEndian, L, T, R>(self)
+    }
+
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()> {
+        order::expect_magic(self, magic)
+    }
+
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()> {
+        order::expect_magic_value::<LittleEndian, T, R>(self, magic)
+    }
+
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut R>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::unpack_with_checksum::<LittleEndian, U, F, R>(self, f)
+    }
+
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()> {
+        order::unpack_converted::<LittleEndian, S, D, F, R>(self, buf, convert)
+    }
+
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T> {
+        order::unpack_as::<LittleEndian, S, T, R>(self, overflow)
+    }
+
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()> {
+        order::unpack_exact_as::<LittleEndian, S, T, R>(self, buf, overflow)
+    }
+
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize> {
+        order::unpack_to_end_fixed::<LittleEndian, C, R>(self, buf)
+    }
+
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C> {
+        order::unpack_n_fixed::<LittleEndian, C, R>(self, count)
+    }
+
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()> {
+        order::unpack_extend::<LittleEndian, T, E, R>(self, target, count)
+    }
+
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>> {
+        order::unpack_maybe::<LittleEndian, T, R>(self)
+    }
+
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize> {
+        order::unpack_until::<LittleEndian, T, R>(self, &sentinel, buf, policy, max_items)
+    }
+
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>> {
+        order::unpack_while::<LittleEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where R: Seek {
+        order::unpack_while_put_back::<LittleEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where R: Seek {
+        order::remaining_items::<T, R>(self)
+    }
+
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where R: BufRead {
+        order::unpack_buffered::<LittleEndian, T, R>(self)
+    }
+
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>> {
+        order::unpack_with_tail::<LittleEndian, H, T, R>(self, max_items)
+    }
+
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T> {
+        order::unpack_header_packed::<LittleEndian, T, R>(self, max_len)
+    }
+
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_strided_with_scratch::<LittleEndian, T, R>(self, buf, offset, stride, scratch)
+    }
+
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved_with_scratch::<LittleEndian, T, R>(self, channels, frames, scratch)
+    }
+
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_converted_with_scratch::<LittleEndian, S, D, F, R>(self, buf, convert, scratch)
     }
 }
 
 impl<W> LEPacker for W where W: Write {
     fn pack<T: Packed>(&mut self, t: T) -> Result<()> {
-        if cfg!(target_endian = "big") {
-            let mut t_copy = t;
-            t_copy.switch_endianness();
-            Packer::pack(self, t_copy)
-        }
-        else {
-            Packer::pack(self, t)
-        }
+        order::pack::<LittleEndian, T, W>(self, t)
     }
 
-    fn pack_ref<T: Packed + Clone>(&mut self, t: &T) -> Result<()> {
-        if cfg!(target_endian = "big") {
-            let mut t_copy = t.clone();
-            t_copy.switch_endianness();
-            Packer::pack(self, t_copy)
-        }
-        else {
-            Packer::pack_ref(self, t)
-        }
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<LittleEndian, T, W>(self, t)
     }
 
-    fn pack_all<T: Packed + Clone>(&mut self, buf: &[T]) -> Result<()> {
-        if cfg!(target_endian = "big") {
-            let mut buf_copy = buf.to_vec();
-            for i in 0..buf_copy.len() {
-                buf_copy[i].switch_endianness();
-            }
-            Packer::pack_all(self, &buf_copy[..])
-        }
-        else {
-            Packer::pack_all(self, buf)
-        }
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<LittleEndian, T, W>(self, buf)
+    }
+
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()> {
+        order::pack_iter::<LittleEndian, T, I, W>(self, iter)
+    }
+
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()> {
+        order::pack_all_vectored::<LittleEndian, T, W>(self, bufs)
+    }
+
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where W: Seek {
+        order::pack_at::<LittleEndian, T, W>(self, offset, t)
+    }
+
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()> {
+        order::pack_strided::<LittleEndian, T, W>(self, buf, offset, stride)
+    }
+
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()> {
+        order::pack_field::<LittleEndian, T, U, F, W>(self, buf, field)
+    }
+
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()> {
+        order::pack_interleaved::<LittleEndian, T, W>(self, channels)
+    }
+
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::pack_rows::<LittleEndian, T, W>(self, buf, row_len, row_stride)
+    }
+
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()> {
+        order::pack_transposed::<LittleEndian, T, W>(self, rows, cols, buf)
+    }
+
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()> {
+        order::pack_tlv::<LittleEndian, Tag, L, W>(self, tag, payload)
+    }
+
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()> {
+        order::pack_tlv_value::<LittleEndian, Tag, L, T, W>(self, tag, value)
+    }
+
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_framed::<LittleEndian, L, T, W>(self, buf)
+    }
+
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut W>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::pack_with_checksum::<LittleEndian, U, F, W>(self, f)
+    }
+
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()> {
+        order::pack_converted::<LittleEndian, S, D, F, W>(self, buf, convert)
+    }
+
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_all_with_scratch::<LittleEndian, T, W>(self, buf, scratch)
+    }
+
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_iter_with_scratch::<LittleEndian, T, I, W>(self, iter, scratch)
+    }
+
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()> {
+        order::pack_repeat::<LittleEndian, T, W>(self, t, count)
+    }
+
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()> {
+        order::pack_with_tail::<LittleEndian, H, T, W>(self, record)
+    }
+
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()> {
+        order::pack_header_packed::<LittleEndian, T, W>(self, t)
     }
 }
 
@@ -1169,6 +2978,55 @@ pub trait BEUnpacker {
     fn unpack<T: Packed>(&mut self) -> Result<T>;
     fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
     fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>>;
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>>;
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]>;
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()>;
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where Self: Seek;
+    fn peek<T: Packed>(&mut self) -> Result<T> where Self: Seek;
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()>;
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where Self: Seek;
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where Self: Seek;
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where Self: Seek;
+    fn transaction<U, F: FnOnce(&mut Self) -> Result<U>>(&mut self, f: F) -> Result<U> where Self: Seek + Sized;
+    fn records<T: Packed>(&mut self) -> Records<'_, Self, T, BigEndian> where Self: Sized;
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize>;
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>>;
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd>;
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T>;
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()>;
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>>;
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()>;
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)>;
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)>;
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>>;
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T>;
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()>;
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()>;
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()>;
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T>;
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()>;
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize>;
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C>;
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()>;
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>>;
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize>;
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>>;
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where Self: Seek;
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where Self: Seek;
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where Self: BufRead;
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>>;
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T>;
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>>;
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()>;
 }
 
 /// Provides the same API and functionnality as [`Packer`](trait.Packer.html) but ensure that 
@@ -1176,89 +3034,320 @@ pub trait BEUnpacker {
 /// documentation.
 pub trait BEPacker {
     fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
-
-    /// Here T needs to be `Clone` because the endianness switch cannot be done in-place. This method 
-    /// thus allocates a copy of `buf` if an endianness switch is needed.
-    fn pack_ref<T: Packed + Clone>(&mut self, t: &T) -> Result<()>;
-
-    /// Here T needs to be `Clone` because the endianness switch cannot be done in-place. This method 
-    /// thus allocates a copy of `buf` if an endianness switch is needed.
-    fn pack_all<T: Packed + Clone>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()>;
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()>;
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()>;
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where Self: Seek;
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()>;
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()>;
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()>;
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()>;
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()>;
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()>;
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()>;
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()>;
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()>;
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()>;
 }
 
 impl<R> BEUnpacker for R where R: Read {
     fn unpack<T: Packed>(&mut self) -> Result<T> {
-        if cfg!(target_endian = "little") {
-            let mut t = Unpacker::unpack::<T>(self)?;
-            t.switch_endianness();
-            Ok(t)
-        }
-        else {
-            Unpacker::unpack(self)
-        }
+        order::unpack::<BigEndian, T, R>(self)
     }
 
     fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
-        if cfg!(target_endian = "little") {
-            let size = Unpacker::unpack_to_end(self, buf)?;
-            let start = buf.len() - size;
-            for i in start..buf.len() {
-                buf[i].switch_endianness();
-            }
-            Ok(size)
-        }
-        else {
-            Unpacker::unpack_to_end(self, buf)
-        }
+        order::unpack_to_end::<BigEndian, T, R>(self, buf)
     }
 
     fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
-        if cfg!(target_endian = "little") {
-            Unpacker::unpack_exact(self, buf)?;
-            for i in 0..buf.len() {
-                buf[i].switch_endianness();
-            }
-            Ok(())
-        }
-        else {
-            Unpacker::unpack_exact(self, buf)
-        }
+        order::unpack_exact::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, R, T, BigEndian> {
+        UnpackIter::new(self)
+    }
+
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<BigEndian, T, R>(self, count)
+    }
+
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>> {
+        order::unpack_n::<BigEndian, T, R>(self, len).map(Vec::into_boxed_slice)
+    }
+
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>> {
+        order::unpack_arc_slice::<BigEndian, T, R>(self, len)
+    }
+
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+        order::unpack_exact_uninit::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()> {
+        order::unpack_append::<BigEndian, T, R>(self, buf, count)
+    }
+
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where R: Seek {
+        order::unpack_to_end_sized::<BigEndian, T, R>(self, buf)
+    }
+
+    fn peek<T: Packed>(&mut self) -> Result<T> where R: Seek {
+        order::peek::<BigEndian, T, R>(self)
+    }
+
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()> {
+        order::skip::<T, R>(self, count)
+    }
+
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where R: Seek {
+        order::skip_seek::<T, R>(self, count)
+    }
+
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where R: Seek {
+        order::unpack_at::<BigEndian, T, R>(self, offset)
+    }
+
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where R: Seek {
+        order::unpack_exact_at::<BigEndian, T, R>(self, offset, buf)
+    }
+
+    fn transaction<U, F: FnOnce(&mut R) -> Result<U>>(&mut self, f: F) -> Result<U> where R: Seek {
+        order::transaction(self, f)
+    }
+
+    fn records<T: Packed>(&mut self) -> Records<'_, R, T, BigEndian> {
+        Records::new(self, RECORDS_BUFFER_SIZE)
+    }
+
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, R, T, BigEndian> {
+        Records::new(self, capacity)
+    }
+
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize> {
+        order::unpack_to_end_limited::<BigEndian, T, R>(self, buf, max_items)
+    }
+
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>> {
+        order::unpack_n_limited::<BigEndian, T, R>(self, count, max_items)
+    }
+
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd> {
+        order::unpack_to_end_trailing::<BigEndian, T, R>(self, buf, policy)
+    }
+
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T> {
+        order::unpack_validated::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact_validated::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()> {
+        order::unpack_strided::<BigEndian, T, R>(self, buf, offset, stride)
+    }
+
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved::<BigEndian, T, R>(self, channels, frames)
+    }
+
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::unpack_rows::<BigEndian, T, R>(self, buf, row_len, row_stride)
+    }
+
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()> {
+        order::unpack_transposed::<BigEndian, T, R>(self, rows, cols, buf)
+    }
+
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, R, T, BigEndian> {
+        Chunks::new(self, chunk_len)
+    }
+
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)> {
+        order::unpack_tlv::<BigEndian, Tag, L, R>(self)
+    }
+
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)> {
+        order::unpack_tlv_value::<BigEndian, Tag, L, T, R>(self)
+    }
+
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>> {
+        order::unpack_framed::<BigEndian, L, T, R>(self, max_len)
+    }
+
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T> {
+        order::unpack_framed_value::<BigEndian, L, T, R>(self)
+    }
+
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()> {
+        order::expect_magic(self, magic)
+    }
+
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()> {
+        order::expect_magic_value::<BigEndian, T, R>(self, magic)
+    }
+
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut R>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::unpack_with_checksum::<BigEndian, U, F, R>(self, f)
+    }
+
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()> {
+        order::unpack_converted::<BigEndian, S, D, F, R>(self, buf, convert)
+    }
+
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T> {
+        order::unpack_as::<BigEndian, S, T, R>(self, overflow)
+    }
+
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()> {
+        order::unpack_exact_as::<BigEndian, S, T, R>(self, buf, overflow)
+    }
+
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize> {
+        order::unpack_to_end_fixed::<BigEndian, C, R>(self, buf)
+    }
+
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C> {
+        order::unpack_n_fixed::<BigEndian, C, R>(self, count)
+    }
+
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()> {
+        order::unpack_extend::<BigEndian, T, E, R>(self, target, count)
+    }
+
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>> {
+        order::unpack_maybe::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize> {
+        order::unpack_until::<BigEndian, T, R>(self, &sentinel, buf, policy, max_items)
+    }
+
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>> {
+        order::unpack_while::<BigEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where R: Seek {
+        order::unpack_while_put_back::<BigEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where R: Seek {
+        order::remaining_items::<T, R>(self)
+    }
+
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where R: BufRead {
+        order::unpack_buffered::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>> {
+        order::unpack_with_tail::<BigEndian, H, T, R>(self, max_items)
+    }
+
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T> {
+        order::unpack_header_packed::<BigEndian, T, R>(self, max_len)
+    }
+
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_strided_with_scratch::<BigEndian, T, R>(self, buf, offset, stride, scratch)
+    }
+
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved_with_scratch::<BigEndian, T, R>(self, channels, frames, scratch)
+    }
+
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_converted_with_scratch::<BigEndian, S, D, F, R>(self, buf, convert, scratch)
     }
 }
 
 impl<W> BEPacker for W where W: Write {
     fn pack<T: Packed>(&mut self, t: T) -> Result<()> {
-        if cfg!(target_endian = "little") {
-            let mut t_copy = t;
-            t_copy.switch_endianness();
-            Packer::pack(self, t_copy)
-        }
-        else {
-            Packer::pack(self, t)
-        }
+        order::pack::<BigEndian, T, W>(self, t)
     }
 
-    fn pack_ref<T: Packed + Clone>(&mut self, t: &T) -> Result<()> {
-        if cfg!(target_endian = "little") {
-            let mut t_copy = t.clone();
-            t_copy.switch_endianness();
-            Packer::pack(self, t_copy)
-        }
-        else {
-            Packer::pack_ref(self, t)
-        }
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<BigEndian, T, W>(self, t)
     }
 
-    fn pack_all<T: Packed + Clone>(&mut self, buf: &[T]) -> Result<()> {
-        if cfg!(target_endian = "little") {
-            let mut buf_copy = buf.to_vec();
-            for i in 0..buf_copy.len() {
-                buf_copy[i].switch_endianness();
-            }
-            Packer::pack_all(self, &buf_copy[..])
-        }
-        else {
-            Packer::pack_all(self, buf)
-        }
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<BigEndian, T, W>(self, buf)
+    }
+
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()> {
+        order::pack_iter::<BigEndian, T, I, W>(self, iter)
+    }
+
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()> {
+        order::pack_all_vectored::<BigEndian, T, W>(self, bufs)
+    }
+
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where W: Seek {
+        order::pack_at::<BigEndian, T, W>(self, offset, t)
+    }
+
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()> {
+        order::pack_strided::<BigEndian, T, W>(self, buf, offset, stride)
+    }
+
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()> {
+        order::pack_field::<BigEndian, T, U, F, W>(self, buf, field)
+    }
+
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()> {
+        order::pack_interleaved::<BigEndian, T, W>(self, channels)
+    }
+
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::pack_rows::<BigEndian, T, W>(self, buf, row_len, row_stride)
+    }
+
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()> {
+        order::pack_transposed::<BigEndian, T, W>(self, rows, cols, buf)
+    }
+
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()> {
+        order::pack_tlv::<BigEndian, Tag, L, W>(self, tag, payload)
+    }
+
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()> {
+        order::pack_tlv_value::<BigEndian, Tag, L, T, W>(self, tag, value)
+    }
+
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_framed::<BigEndian, L, T, W>(self, buf)
+    }
+
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut W>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::pack_with_checksum::<BigEndian, U, F, W>(self, f)
+    }
+
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()> {
+        order::pack_converted::<BigEndian, S, D, F, W>(self, buf, convert)
+    }
+
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_all_with_scratch::<BigEndian, T, W>(self, buf, scratch)
+    }
+
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_iter_with_scratch::<BigEndian, T, I, W>(self, iter, scratch)
+    }
+
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()> {
+        order::pack_repeat::<BigEndian, T, W>(self, t, count)
+    }
+
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()> {
+        order::pack_with_tail::<BigEndian, H, T, W>(self, record)
+    }
+
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()> {
+        order::pack_header_packed::<BigEndian, T, W>(self, t)
     }
 }
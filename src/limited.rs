@@ -0,0 +1,111 @@
+//! Reader wrapper enforcing a global limit on the amount of data read.
+
+use std::convert::TryFrom;
+use std::io::{self, Read, BufRead};
+
+use Error;
+
+/// A reader wrapper failing once more than `limit` bytes would be read from it, which bounds the
+/// memory all the unpacking methods can use on an untrusted input, including
+/// [`unpack_to_end`](trait.Unpacker.html#tymethod.unpack_to_end).
+///
+/// Unlike `Read::take`, which silently reports `EOF` at the limit, reading past the limit returns
+/// a `LimitExceeded` error whose limit is in bytes. Reaching the real `EOF` exactly at the limit
+/// is not an error.
+///
+/// Methods allocating their output from a length, like
+/// [`unpack_n`](trait.Unpacker.html#tymethod.unpack_n), allocate it before reading anything: use
+/// their `_limited` variants to check such lengths.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Limited, Unpacker, Error};
+///
+/// let mut reader = Limited::new(&[0u8; 64][..], 32);
+/// let mut values = Vec::<u32>::new();
+/// match reader.unpack_to_end(&mut values) {
+///     Err(Error::LimitExceeded { limit, .. }) => assert_eq!(limit, 32),
+///     _ => unreachable!()
+/// }
+/// ```
+pub struct Limited<R> {
+    inner: R,
+    limit: u64,
+    remaining: u64
+}
+
+impl<R> Limited<R> {
+    /// Wrap `inner`, allowing at most `limit` bytes to be read.
+    pub fn new(inner: R, limit: u64) -> Limited<R> {
+        Limited {
+            inner,
+            limit,
+            remaining: limit
+        }
+    }
+
+    /// Return the number of bytes which can still be read.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Return a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader. Bytes read through it are not counted
+    /// against the limit.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn exceeded(&self) -> io::Error {
+        Error::limit_exceeded::<u8>(self.limit).with_offset(self.limit).into()
+    }
+}
+
+impl<R: Read> Read for Limited<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            // only an error if there actually is more data
+            return match self.inner.read(&mut [0u8])? {
+                0 => Ok(0),
+                _ => Err(self.exceeded())
+            };
+        }
+        let len = buf.len().min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let size = self.inner.read(&mut buf[..len])?;
+        self.remaining -= size as u64;
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for Limited<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.remaining == 0 {
+            if self.inner.fill_buf()?.is_empty() {
+                return Ok(&[]);
+            }
+            return Err(self.exceeded());
+        }
+        let len = usize::try_from(self.remaining).unwrap_or(usize::MAX);
+        let buf = self.inner.fill_buf()?;
+        Ok(&buf[..buf.len().min(len)])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        self.inner.consume(amt);
+        self.remaining -= amt as u64;
+    }
+}
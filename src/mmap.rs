@@ -0,0 +1,203 @@
+//! Memory-mapped files exposed as slices of [`Packed`](../trait.Packed.html) values.
+
+use std::fs::File;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+use memmap2::{Mmap, MmapMut};
+
+use {Packed, Endianness, Result, switch_endianness_slice};
+use view::check_view;
+
+/// A read-only memory-mapped file viewed as a slice of values of type `T`. The content of the file
+/// is only loaded from the disk when accessed, which makes it suitable for files too large to be
+/// read through `Read`.
+///
+/// The values are viewed as stored in the file, in their raw byte order. The
+/// [`get_with`](#method.get_with) and [`to_vec_with`](#method.to_vec_with) methods convert them
+/// from a given byte order on demand.
+///
+/// This type is only available with the `mmap` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use bytepack::{PackedMmap, Endianness};
+///
+/// let file = File::open("samples").unwrap();
+/// let samples = unsafe { PackedMmap::<f32>::map(&file).unwrap() };
+/// let sum : f32 = samples.iter().sum();
+/// let last = samples.get_with(samples.len() - 1, Endianness::Little).unwrap();
+/// ```
+pub struct PackedMmap<T: Packed> {
+    map: Mmap,
+    len: usize,
+    marker: PhantomData<T>
+}
+
+impl<T: Packed> PackedMmap<T> {
+    /// Map `file` in memory. An error is returned if the size of the file is not a multiple of
+    /// the size of `T`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, by this process or another one, while it is mapped, as
+    /// this would change the content of the slice behind its back.
+    pub unsafe fn map(file: &File) -> Result<PackedMmap<T>> {
+        let map = Mmap::map(file)?;
+        let len = check_view::<T>(&map)?;
+        Ok(PackedMmap {
+            map,
+            len,
+            marker: PhantomData
+        })
+    }
+
+    /// Return the mapped values, in their raw byte order.
+    pub fn as_slice(&self) -> &[T] {
+        // safe because check_view validated the alignment and the size of the mapping
+        unsafe {
+            slice::from_raw_parts(self.map.as_ptr() as *const T, self.len)
+        }
+    }
+
+    /// Return a copy of the value at `index` converted from the byte order `endianness`, or
+    /// `None` if `index` is out of bounds.
+    pub fn get_with(&self, index: usize, endianness: Endianness) -> Option<T> where T: Clone {
+        self.as_slice().get(index).map(|t| {
+            let mut t = t.clone();
+            if !endianness.is_native() {
+                t.switch_endianness();
+            }
+            t
+        })
+    }
+
+    /// Return a copy of all the values converted from the byte order `endianness`.
+    pub fn to_vec_with(&self, endianness: Endianness) -> Vec<T> where T: Clone {
+        let mut buf = self.as_slice().to_vec();
+        if !endianness.is_native() {
+            switch_endianness_slice(&mut buf);
+        }
+        buf
+    }
+}
+
+impl<T: Packed> Deref for PackedMmap<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+/// A writable memory-mapped file viewed as a mutable slice of values of type `T`, allowing fixed
+/// size records to be modified in place. Modifications are written back to the file by the
+/// operating system, [`flush`](#method.flush) forces them to be written immediately.
+///
+/// Like [`PackedMmap`](struct.PackedMmap.html), the values are viewed in their raw byte order and
+/// [`get_with`](#method.get_with) and [`set_with`](#method.set_with) convert them on demand.
+///
+/// This type is only available with the `mmap` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::OpenOptions;
+///
+/// use bytepack::{PackedMmapMut, Endianness};
+///
+/// let file = OpenOptions::new().read(true).write(true).open("calibration").unwrap();
+/// let mut table = unsafe { PackedMmapMut::<f64>::map_mut(&file).unwrap() };
+/// let gain = table.get_with(42, Endianness::Little).unwrap();
+/// table.set_with(42, gain * 1.5, Endianness::Little);
+/// table.flush().unwrap();
+/// ```
+pub struct PackedMmapMut<T: Packed> {
+    map: MmapMut,
+    len: usize,
+    marker: PhantomData<T>
+}
+
+impl<T: Packed> PackedMmapMut<T> {
+    /// Map `file`, which needs to be opened for reading and writing, in memory. An error is
+    /// returned if the size of the file is not a multiple of the size of `T`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, by this process or another one, other than through the
+    /// mapping while it is mapped.
+    pub unsafe fn map_mut(file: &File) -> Result<PackedMmapMut<T>> {
+        let map = MmapMut::map_mut(file)?;
+        let len = check_view::<T>(&map)?;
+        Ok(PackedMmapMut {
+            map,
+            len,
+            marker: PhantomData
+        })
+    }
+
+    /// Return the mapped values, in their raw byte order.
+    pub fn as_slice(&self) -> &[T] {
+        // safe because check_view validated the alignment and the size of the mapping
+        unsafe {
+            slice::from_raw_parts(self.map.as_ptr() as *const T, self.len)
+        }
+    }
+
+    /// Return the mapped values as a mutable slice, in their raw byte order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // safe because check_view validated the alignment and the size of the mapping
+        unsafe {
+            slice::from_raw_parts_mut(self.map.as_mut_ptr() as *mut T, self.len)
+        }
+    }
+
+    /// Return a copy of the value at `index` converted from the byte order `endianness`, or
+    /// `None` if `index` is out of bounds.
+    pub fn get_with(&self, index: usize, endianness: Endianness) -> Option<T> where T: Clone {
+        self.as_slice().get(index).map(|t| {
+            let mut t = t.clone();
+            if !endianness.is_native() {
+                t.switch_endianness();
+            }
+            t
+        })
+    }
+
+    /// Store `value` at `index` in the byte order `endianness`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_with(&mut self, index: usize, value: T, endianness: Endianness) {
+        let mut value = value;
+        if !endianness.is_native() {
+            value.switch_endianness();
+        }
+        self.as_mut_slice()[index] = value;
+    }
+
+    /// Write the modifications back to the file and wait for them to be completed.
+    pub fn flush(&self) -> Result<()> {
+        self.map.flush()?;
+        Ok(())
+    }
+}
+
+impl<T: Packed> Deref for PackedMmapMut<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Packed> DerefMut for PackedMmapMut<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
@@ -0,0 +1,421 @@
+//! Network byte order traits for protocol implementations.
+
+use std::fmt::Debug;
+use std::io::{Read, BufRead, Write, Seek};
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+
+use {order, Packed, BigEndian, UnpackIter, Records, Chunks, RECORDS_BUFFER_SIZE, Error, Result, TrailingPolicy, ToEnd, PackedValidate, LengthPrefix, Crc32Reader, Crc32Writer, Integer, Overflow, ScratchBuffer, FixedCapacity, Sentinel, TailCount, WithTail, HeaderPacked};
+
+/// Provides the same API and functionnality as [`BEUnpacker`](trait.BEUnpacker.html) under the
+/// name protocols use for it, the network byte order, plus helpers for fields commonly found in
+/// protocol headers. See [`Unpacker`](trait.Unpacker.html) for more documentation.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::TcpStream;
+///
+/// use bytepack::NetUnpacker;
+///
+/// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+/// let port : u16 = stream.unpack().unwrap();
+/// let length = stream.unpack_u24().unwrap();
+/// ```
+pub trait NetUnpacker {
+    fn unpack<T: Packed>(&mut self) -> Result<T>;
+    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize>;
+    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>>;
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>>;
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>>;
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]>;
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()>;
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where Self: Seek;
+    fn peek<T: Packed>(&mut self) -> Result<T> where Self: Seek;
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()>;
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where Self: Seek;
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where Self: Seek;
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where Self: Seek;
+    fn transaction<U, F: FnOnce(&mut Self) -> Result<U>>(&mut self, f: F) -> Result<U> where Self: Seek + Sized;
+    fn records<T: Packed>(&mut self) -> Records<'_, Self, T, BigEndian> where Self: Sized;
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize>;
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>>;
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd>;
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T>;
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()>;
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()>;
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>>;
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()>;
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, Self, T, BigEndian> where Self: Sized;
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)>;
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)>;
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>>;
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T>;
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()>;
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()>;
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()>;
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T>;
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()>;
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize>;
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C>;
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()>;
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>>;
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize>;
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>>;
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where Self: Seek;
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where Self: Seek;
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where Self: BufRead;
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>>;
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T>;
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>>;
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()>;
+    /// Unpack a 24 bits unsigned integer, as commonly used for length fields.
+    fn unpack_u24(&mut self) -> Result<u32>;
+}
+
+/// Provides the same API and functionnality as [`BEPacker`](trait.BEPacker.html) under the
+/// name protocols use for it, the network byte order, plus helpers for fields commonly found in
+/// protocol headers. See [`Packer`](trait.Packer.html) for more documentation.
+pub trait NetPacker {
+    fn pack<T: Packed>(&mut self, t: T) -> Result<()>;
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()>;
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()>;
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()>;
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where Self: Seek;
+
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()>;
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()>;
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()>;
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()>;
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()>;
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()>;
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()>;
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()>;
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut Self>) -> Result<U>>(&mut self, f: F) -> Result<U>;
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()>;
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()>;
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()>;
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()>;
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()>;
+    /// Pack `value` as a 24 bits unsigned integer, as commonly used for length fields. An error
+    /// is returned if `value` does not fit in 24 bits.
+    fn pack_u24(&mut self, value: u32) -> Result<()>;
+}
+
+impl<R> NetUnpacker for R where R: Read {
+    fn unpack<T: Packed>(&mut self) -> Result<T> {
+        order::unpack::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_to_end<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> {
+        order::unpack_to_end::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_iter<T: Packed>(&mut self) -> UnpackIter<'_, R, T, BigEndian> {
+        UnpackIter::new(self)
+    }
+
+    fn unpack_n<T: Packed>(&mut self, count: usize) -> Result<Vec<T>> {
+        order::unpack_n::<BigEndian, T, R>(self, count)
+    }
+
+    fn unpack_boxed_slice<T: Packed>(&mut self, len: usize) -> Result<Box<[T]>> {
+        order::unpack_n::<BigEndian, T, R>(self, len).map(Vec::into_boxed_slice)
+    }
+
+    fn unpack_arc_slice<T: Packed>(&mut self, len: usize) -> Result<Arc<[T]>> {
+        order::unpack_arc_slice::<BigEndian, T, R>(self, len)
+    }
+
+    fn unpack_exact_uninit<'a, T: Packed>(&mut self, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+        order::unpack_exact_uninit::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_append<T: Packed>(&mut self, buf: &mut Vec<T>, count: usize) -> Result<()> {
+        order::unpack_append::<BigEndian, T, R>(self, buf, count)
+    }
+
+    fn unpack_to_end_sized<T: Packed>(&mut self, buf: &mut Vec<T>) -> Result<usize> where R: Seek {
+        order::unpack_to_end_sized::<BigEndian, T, R>(self, buf)
+    }
+
+    fn peek<T: Packed>(&mut self) -> Result<T> where R: Seek {
+        order::peek::<BigEndian, T, R>(self)
+    }
+
+    fn skip<T: Packed>(&mut self, count: usize) -> Result<()> {
+        order::skip::<T, R>(self, count)
+    }
+
+    fn skip_seek<T: Packed>(&mut self, count: usize) -> Result<()> where R: Seek {
+        order::skip_seek::<T, R>(self, count)
+    }
+
+    fn unpack_at<T: Packed>(&mut self, offset: u64) -> Result<T> where R: Seek {
+        order::unpack_at::<BigEndian, T, R>(self, offset)
+    }
+
+    fn unpack_exact_at<T: Packed>(&mut self, offset: u64, buf: &mut [T]) -> Result<()> where R: Seek {
+        order::unpack_exact_at::<BigEndian, T, R>(self, offset, buf)
+    }
+
+    fn transaction<U, F: FnOnce(&mut R) -> Result<U>>(&mut self, f: F) -> Result<U> where R: Seek {
+        order::transaction(self, f)
+    }
+
+    fn records<T: Packed>(&mut self) -> Records<'_, R, T, BigEndian> {
+        Records::new(self, RECORDS_BUFFER_SIZE)
+    }
+
+    fn records_with_capacity<T: Packed>(&mut self, capacity: usize) -> Records<'_, R, T, BigEndian> {
+        Records::new(self, capacity)
+    }
+
+    fn unpack_to_end_limited<T: Packed>(&mut self, buf: &mut Vec<T>, max_items: usize) -> Result<usize> {
+        order::unpack_to_end_limited::<BigEndian, T, R>(self, buf, max_items)
+    }
+
+    fn unpack_n_limited<T: Packed>(&mut self, count: usize, max_items: usize) -> Result<Vec<T>> {
+        order::unpack_n_limited::<BigEndian, T, R>(self, count, max_items)
+    }
+
+    fn unpack_to_end_with_trailing<T: Packed>(&mut self, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd> {
+        order::unpack_to_end_trailing::<BigEndian, T, R>(self, buf, policy)
+    }
+
+    fn unpack_validated<T: PackedValidate>(&mut self) -> Result<T> {
+        order::unpack_validated::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_exact_validated<T: PackedValidate>(&mut self, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact_validated::<BigEndian, T, R>(self, buf)
+    }
+
+    fn unpack_strided<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize) -> Result<()> {
+        order::unpack_strided::<BigEndian, T, R>(self, buf, offset, stride)
+    }
+
+    fn unpack_deinterleaved<T: Packed>(&mut self, channels: usize, frames: usize) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved::<BigEndian, T, R>(self, channels, frames)
+    }
+
+    fn unpack_rows<T: Packed>(&mut self, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::unpack_rows::<BigEndian, T, R>(self, buf, row_len, row_stride)
+    }
+
+    fn unpack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &mut [T]) -> Result<()> {
+        order::unpack_transposed::<BigEndian, T, R>(self, rows, cols, buf)
+    }
+
+    fn unpack_chunks<T: Packed>(&mut self, chunk_len: usize) -> Chunks<'_, R, T, BigEndian> {
+        Chunks::new(self, chunk_len)
+    }
+
+    fn unpack_tlv<Tag: Packed, L: LengthPrefix>(&mut self) -> Result<(Tag, Vec<u8>)> {
+        order::unpack_tlv::<BigEndian, Tag, L, R>(self)
+    }
+
+    fn unpack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self) -> Result<(Tag, T)> {
+        order::unpack_tlv_value::<BigEndian, Tag, L, T, R>(self)
+    }
+
+    fn unpack_framed<L: LengthPrefix, T: Packed>(&mut self, max_len: usize) -> Result<Vec<T>> {
+        order::unpack_framed::<BigEndian, L, T, R>(self, max_len)
+    }
+
+    fn unpack_framed_value<L: LengthPrefix, T: Packed>(&mut self) -> Result<T> {
+        order::unpack_framed_value::<BigEndian, L, T, R>(self)
+    }
+
+    fn expect_magic(&mut self, magic: &[u8]) -> Result<()> {
+        order::expect_magic(self, magic)
+    }
+
+    fn expect_magic_value<T: Packed + PartialEq + Debug>(&mut self, magic: T) -> Result<()> {
+        order::expect_magic_value::<BigEndian, T, R>(self, magic)
+    }
+
+    fn unpack_with_checksum<U, F: FnOnce(&mut Crc32Reader<&mut R>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::unpack_with_checksum::<BigEndian, U, F, R>(self, f)
+    }
+
+    fn unpack_converted<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F) -> Result<()> {
+        order::unpack_converted::<BigEndian, S, D, F, R>(self, buf, convert)
+    }
+
+    fn unpack_as<S: Integer, T: Integer>(&mut self, overflow: Overflow) -> Result<T> {
+        order::unpack_as::<BigEndian, S, T, R>(self, overflow)
+    }
+
+    fn unpack_exact_as<S: Integer, T: Integer>(&mut self, buf: &mut [T], overflow: Overflow) -> Result<()> {
+        order::unpack_exact_as::<BigEndian, S, T, R>(self, buf, overflow)
+    }
+
+    fn unpack_to_end_fixed<C: FixedCapacity + ?Sized>(&mut self, buf: &mut C) -> Result<usize> {
+        order::unpack_to_end_fixed::<BigEndian, C, R>(self, buf)
+    }
+
+    fn unpack_n_fixed<C: FixedCapacity + Default>(&mut self, count: usize) -> Result<C> {
+        order::unpack_n_fixed::<BigEndian, C, R>(self, count)
+    }
+
+    fn unpack_extend<T: Packed, E: Extend<T> + ?Sized>(&mut self, target: &mut E, count: usize) -> Result<()> {
+        order::unpack_extend::<BigEndian, T, E, R>(self, target, count)
+    }
+
+    fn unpack_maybe<T: Packed>(&mut self) -> Result<Option<T>> {
+        order::unpack_maybe::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_until<T: Packed + PartialEq>(&mut self, sentinel: T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize> {
+        order::unpack_until::<BigEndian, T, R>(self, &sentinel, buf, policy, max_items)
+    }
+
+    fn unpack_while<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<Option<T>> {
+        order::unpack_while::<BigEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn unpack_while_put_back<T: Packed, F: FnMut(&T) -> bool>(&mut self, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> where R: Seek {
+        order::unpack_while_put_back::<BigEndian, T, R, F>(self, buf, max_items, predicate)
+    }
+
+    fn remaining_items<T: Packed>(&mut self) -> Result<(u64, usize)> where R: Seek {
+        order::remaining_items::<T, R>(self)
+    }
+
+    fn unpack_buffered<T: Packed>(&mut self) -> Result<T> where R: BufRead {
+        order::unpack_buffered::<BigEndian, T, R>(self)
+    }
+
+    fn unpack_with_tail<H: TailCount, T: Packed>(&mut self, max_items: usize) -> Result<WithTail<H, T>> {
+        order::unpack_with_tail::<BigEndian, H, T, R>(self, max_items)
+    }
+
+    fn unpack_header_packed<T: HeaderPacked>(&mut self, max_len: usize) -> Result<T> {
+        order::unpack_header_packed::<BigEndian, T, R>(self, max_len)
+    }
+
+    fn unpack_strided_with_scratch<T: Packed>(&mut self, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_strided_with_scratch::<BigEndian, T, R>(self, buf, offset, stride, scratch)
+    }
+
+    fn unpack_deinterleaved_with_scratch<T: Packed>(&mut self, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>> {
+        order::unpack_deinterleaved_with_scratch::<BigEndian, T, R>(self, channels, frames, scratch)
+    }
+
+    fn unpack_converted_with_scratch<S: Packed, D, F: FnMut(S) -> D>(&mut self, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::unpack_converted_with_scratch::<BigEndian, S, D, F, R>(self, buf, convert, scratch)
+    }
+
+    fn unpack_u24(&mut self) -> Result<u32> {
+        let bytes = order::unpack::<BigEndian, [u8; 3], R>(self)?;
+        Ok((bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32)
+    }
+}
+
+impl<W> NetPacker for W where W: Write {
+    fn pack<T: Packed>(&mut self, t: T) -> Result<()> {
+        order::pack::<BigEndian, T, W>(self, t)
+    }
+
+    fn pack_ref<T: Packed>(&mut self, t: &T) -> Result<()> {
+        order::pack_ref::<BigEndian, T, W>(self, t)
+    }
+
+    fn pack_all<T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_all::<BigEndian, T, W>(self, buf)
+    }
+
+    fn pack_iter<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<()> {
+        order::pack_iter::<BigEndian, T, I, W>(self, iter)
+    }
+
+    fn pack_all_vectored<T: Packed>(&mut self, bufs: &[&[T]]) -> Result<()> {
+        order::pack_all_vectored::<BigEndian, T, W>(self, bufs)
+    }
+
+    fn pack_at<T: Packed>(&mut self, offset: u64, t: T) -> Result<()> where W: Seek {
+        order::pack_at::<BigEndian, T, W>(self, offset, t)
+    }
+
+    fn pack_strided<T: Packed>(&mut self, buf: &[T], offset: usize, stride: usize) -> Result<()> {
+        order::pack_strided::<BigEndian, T, W>(self, buf, offset, stride)
+    }
+
+    fn pack_field<T, U: Packed, F: Fn(&T) -> &U>(&mut self, buf: &[T], field: F) -> Result<()> {
+        order::pack_field::<BigEndian, T, U, F, W>(self, buf, field)
+    }
+
+    fn pack_interleaved<T: Packed>(&mut self, channels: &[&[T]]) -> Result<()> {
+        order::pack_interleaved::<BigEndian, T, W>(self, channels)
+    }
+
+    fn pack_rows<T: Packed>(&mut self, buf: &[T], row_len: usize, row_stride: usize) -> Result<()> {
+        order::pack_rows::<BigEndian, T, W>(self, buf, row_len, row_stride)
+    }
+
+    fn pack_transposed<T: Packed>(&mut self, rows: usize, cols: usize, buf: &[T]) -> Result<()> {
+        order::pack_transposed::<BigEndian, T, W>(self, rows, cols, buf)
+    }
+
+    fn pack_tlv<Tag: Packed, L: LengthPrefix>(&mut self, tag: Tag, payload: &[u8]) -> Result<()> {
+        order::pack_tlv::<BigEndian, Tag, L, W>(self, tag, payload)
+    }
+
+    fn pack_tlv_value<Tag: Packed, L: LengthPrefix, T: Packed>(&mut self, tag: Tag, value: &T) -> Result<()> {
+        order::pack_tlv_value::<BigEndian, Tag, L, T, W>(self, tag, value)
+    }
+
+    fn pack_framed<L: LengthPrefix, T: Packed>(&mut self, buf: &[T]) -> Result<()> {
+        order::pack_framed::<BigEndian, L, T, W>(self, buf)
+    }
+
+    fn pack_with_checksum<U, F: FnOnce(&mut Crc32Writer<&mut W>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        order::pack_with_checksum::<BigEndian, U, F, W>(self, f)
+    }
+
+    fn pack_converted<S, D: Packed, F: FnMut(&S) -> D>(&mut self, buf: &[S], convert: F) -> Result<()> {
+        order::pack_converted::<BigEndian, S, D, F, W>(self, buf, convert)
+    }
+
+    fn pack_all_with_scratch<T: Packed>(&mut self, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_all_with_scratch::<BigEndian, T, W>(self, buf, scratch)
+    }
+
+    fn pack_iter_with_scratch<T: Packed, I: IntoIterator<Item = T>>(&mut self, iter: I, scratch: &mut ScratchBuffer) -> Result<()> {
+        order::pack_iter_with_scratch::<BigEndian, T, I, W>(self, iter, scratch)
+    }
+
+    fn pack_repeat<T: Packed>(&mut self, t: &T, count: usize) -> Result<()> {
+        order::pack_repeat::<BigEndian, T, W>(self, t, count)
+    }
+
+    fn pack_with_tail<H: TailCount, T: Packed>(&mut self, record: &WithTail<H, T>) -> Result<()> {
+        order::pack_with_tail::<BigEndian, H, T, W>(self, record)
+    }
+
+    fn pack_header_packed<T: HeaderPacked>(&mut self, t: &T) -> Result<()> {
+        order::pack_header_packed::<BigEndian, T, W>(self, t)
+    }
+
+    fn pack_u24(&mut self, value: u32) -> Result<()> {
+        if value > 0xFFFFFF {
+            return Err(Error::invalid_input::<[u8; 3], _>(
+                format!("value ({}) does not fit in 24 bits", value)
+            ));
+        }
+        order::pack::<BigEndian, [u8; 3], W>(self, [(value >> 16) as u8, (value >> 8) as u8, value as u8])
+    }
+}
@@ -0,0 +1,1293 @@
+//! Byte orders, both as a runtime value and as type-level parameters, and the generic
+//! implementation shared by all the packer and unpacker trait families.
+
+use std::any::type_name;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::io;
+use std::io::{Read, BufRead, Write, Seek, SeekFrom, IoSlice, ErrorKind};
+use std::mem::{align_of, size_of, ManuallyDrop, MaybeUninit};
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use {Packed, PackedValidate, ScratchBuffer, Sentinel, TailCount, WithTail, HeaderPacked, SliceUnpacker, FixedCapacity, LengthPrefix, Integer, Overflow, Crc32Reader, Crc32Writer, Error, Result, TrailingPolicy, ToEnd};
+
+/// Size in bytes of the internal buffers used by the streaming methods.
+pub(crate) const CHUNK_SIZE: usize = 8192;
+
+/// Number of values of type `T` fitting in a chunk of `CHUNK_SIZE` bytes, at least one.
+pub(crate) fn chunk_len<T: Packed>() -> usize {
+    (CHUNK_SIZE / T::SIZE.max(1)).max(1)
+}
+
+/// Byte order of packed data. It allows selecting the endianness at runtime, for example after
+/// having read a magic value, using the `*_with` methods of [`Unpacker`](trait.Unpacker.html)
+/// and [`Packer`](trait.Packer.html).
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use bytepack::{Endianness, Unpacker};
+///
+/// let mut file = File::open("test").unwrap();
+/// let endianness = match file.unpack::<[u8; 2]>().unwrap() {
+///     [b'I', b'I'] => Endianness::Little,
+///     _ => Endianness::Big,
+/// };
+/// let offset : u32 = file.unpack_with(endianness).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big
+}
+
+impl Endianness {
+    /// Return the endianness of the system.
+    pub const fn native() -> Endianness {
+        if cfg!(target_endian = "big") {
+            Endianness::Big
+        }
+        else {
+            Endianness::Little
+        }
+    }
+
+    /// Return `true` if this is the endianness of the system, meaning no endianness switch is
+    /// needed.
+    pub fn is_native(self) -> bool {
+        self == Endianness::native()
+    }
+}
+
+/// Type-level byte order. It is implemented by [`NativeEndian`](enum.NativeEndian.html),
+/// [`LittleEndian`](enum.LittleEndian.html) and [`BigEndian`](enum.BigEndian.html) and allows
+/// writing code which is generic over the byte order of the data.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Order, BigEndian, LittleEndian};
+///
+/// fn decode<O: Order>(mut value: u32) -> u32 {
+///     O::convert(&mut value);
+///     value
+/// }
+///
+/// assert_eq!(decode::<BigEndian>(0x12345678u32.to_be()), 0x12345678);
+/// assert_eq!(decode::<LittleEndian>(0x12345678u32.to_le()), 0x12345678);
+/// ```
+pub trait Order {
+    /// The runtime equivalent of this byte order.
+    const ENDIANNESS: Endianness;
+
+    /// Convert `t` in-place between this byte order and the system one. This is a no-op if they
+    /// are the same.
+    fn convert<T: Packed>(t: &mut T) {
+        if !Self::ENDIANNESS.is_native() {
+            t.switch_endianness();
+        }
+    }
+
+    /// Convert all the values of `buf` in-place between this byte order and the system one. This
+    /// is a no-op if they are the same.
+    fn convert_slice<T: Packed>(buf: &mut [T]) {
+        if !Self::ENDIANNESS.is_native() {
+            switch_endianness_slice(buf);
+        }
+    }
+}
+
+/// The byte order of the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NativeEndian {}
+
+/// The little endian byte order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LittleEndian {}
+
+/// The big endian byte order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BigEndian {}
+
+impl Order for NativeEndian {
+    const ENDIANNESS: Endianness = Endianness::native();
+}
+
+impl Order for LittleEndian {
+    const ENDIANNESS: Endianness = Endianness::Little;
+}
+
+impl Order for BigEndian {
+    const ENDIANNESS: Endianness = Endianness::Big;
+}
+
+/// Size in bytes above which the endianness switch of a slice is parallelized when the `rayon`
+/// feature is enabled.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 1 << 20;
+
+/// Switch in-place the endianness of all the values of `buf`. Large buffers are processed in
+/// parallel when the `rayon` feature is enabled.
+///
+/// ```
+/// use bytepack::switch_endianness_slice;
+///
+/// let mut buf = [0x1234u16, 0x5678];
+/// switch_endianness_slice(&mut buf);
+/// assert_eq!(buf, [0x3412, 0x7856]);
+/// ```
+pub fn switch_endianness_slice<T: Packed>(buf: &mut [T]) {
+    #[cfg(feature = "rayon")]
+    {
+        if buf.len() * T::SIZE >= PARALLEL_THRESHOLD {
+            return switch_endianness_slice_parallel(buf);
+        }
+    }
+    for t in buf.iter_mut() {
+        t.switch_endianness();
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn switch_endianness_slice_parallel<T: Packed>(buf: &mut [T]) {
+    use rayon::prelude::*;
+
+    struct Chunk<T>(*mut T, usize);
+    // safe because packed values are plain data which do not depend on the thread they are
+    // accessed from and the chunks never overlap.
+    unsafe impl<T> Send for Chunk<T> {}
+
+    let chunks : Vec<Chunk<T>> = buf.chunks_mut(chunk_len::<T>() * 16)
+        .map(|chunk| Chunk(chunk.as_mut_ptr(), chunk.len()))
+        .collect();
+    chunks.into_par_iter().for_each(|chunk| {
+        // safe because each chunk was built from a distinct part of buf which outlives this call
+        let chunk = unsafe { slice::from_raw_parts_mut(chunk.0, chunk.1) };
+        for t in chunk.iter_mut() {
+            t.switch_endianness();
+        }
+    });
+}
+
+/// Convert in-place all the values of `buf` from the system endianness to little endian, for
+/// example before handing the buffer to a memory map or FFI.
+///
+/// ```
+/// use bytepack::to_le_slice;
+///
+/// let mut buf = [0x12345678u32];
+/// to_le_slice(&mut buf);
+/// assert_eq!(buf, [0x12345678u32.to_le()]);
+/// ```
+pub fn to_le_slice<T: Packed>(buf: &mut [T]) {
+    LittleEndian::convert_slice(buf);
+}
+
+/// Convert in-place all the values of `buf` from the system endianness to big endian.
+pub fn to_be_slice<T: Packed>(buf: &mut [T]) {
+    BigEndian::convert_slice(buf);
+}
+
+/// Convert in-place all the values of `buf` from little endian to the system endianness, for
+/// example after obtaining the buffer from a memory map or FFI.
+pub fn from_le_slice<T: Packed>(buf: &mut [T]) {
+    LittleEndian::convert_slice(buf);
+}
+
+/// Convert in-place all the values of `buf` from big endian to the system endianness.
+pub fn from_be_slice<T: Packed>(buf: &mut [T]) {
+    BigEndian::convert_slice(buf);
+}
+
+/// Read from `r` until `bytes` is full or `EOF` is reached and return the number of bytes read.
+pub(crate) fn fill<R: Read + ?Sized>(r: &mut R, bytes: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < bytes.len() {
+        match r.read(&mut bytes[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {},
+            Err(e) => return Err(e)
+        }
+    }
+    Ok(filled)
+}
+
+/// Emit a trace event for `bytes` bytes of values of type `T` read or written, with the `tracing`
+/// feature.
+#[cfg(feature = "tracing")]
+fn trace<T: ?Sized>(operation: &'static str, bytes: usize) {
+    tracing::trace!(type_name = type_name::<T>(), bytes = bytes, "{}", operation);
+}
+
+#[cfg(not(feature = "tracing"))]
+fn trace<T: ?Sized>(_operation: &'static str, _bytes: usize) {}
+
+/// Fill `bytes`, the memory of values of type `T`, from `r`. A `ShortRead` error is returned if
+/// `EOF` is reached first.
+fn fill_exact<T, R: Read + ?Sized>(r: &mut R, bytes: &mut [u8]) -> Result<()> {
+    let filled = fill(r, bytes)?;
+    if filled < bytes.len() {
+        return Err(Error::short_read::<T>(bytes.len(), filled));
+    }
+    trace::<T>("read", filled);
+    Ok(())
+}
+
+pub(crate) fn read_raw<T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<T> {
+    let mut res = MaybeUninit::<T>::zeroed();
+    // safe because we build a slice of exactly T::SIZE zeroed bytes and res is only assumed
+    // initialized once they have all been read.
+    unsafe {
+        fill_exact::<T, R>(r, slice::from_raw_parts_mut(res.as_mut_ptr() as *mut u8, T::SIZE))?;
+        Ok(res.assume_init())
+    }
+}
+
+/// Read a value of type `T`, returning `None` if `EOF` is reached before reading any byte. An
+/// error is still returned if `EOF` is reached in the middle of the value.
+pub(crate) fn read_raw_maybe<T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<Option<T>> {
+    let mut res = MaybeUninit::<T>::zeroed();
+    // safe because we build a slice of exactly T::SIZE zeroed bytes and res is only assumed
+    // initialized once they have all been read.
+    unsafe {
+        let bytes = slice::from_raw_parts_mut(res.as_mut_ptr() as *mut u8, T::SIZE);
+        let filled = fill(r, bytes)?;
+        if filled == 0 && !bytes.is_empty() {
+            return Ok(None);
+        }
+        if filled < bytes.len() {
+            return Err(Error::short_read::<T>(bytes.len(), filled));
+        }
+        trace::<T>("read", filled);
+        Ok(Some(res.assume_init()))
+    }
+}
+
+/// Read values of type `T` until `EOF` is reached and append them to `buf`. The bytes following
+/// the last whole value are returned instead of being appended.
+pub(crate) fn read_raw_to_end_trailing<T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>) -> Result<(usize, Vec<u8>)> {
+    let length = buf.len();
+    if T::SIZE == 0 {
+        return Ok((0, Vec::new()));
+    }
+    loop {
+        if buf.len() == buf.capacity() {
+            // only grow if there is another value, so a buffer reserved exactly keeps its capacity
+            let mut next = MaybeUninit::<T>::zeroed();
+            // safe because we build a slice of exactly T::SIZE zeroed bytes and next is only
+            // assumed initialized once they have all been read.
+            let bytes = unsafe { slice::from_raw_parts_mut(next.as_mut_ptr() as *mut u8, T::SIZE) };
+            let read = match fill(r, bytes) {
+                Ok(read) => read,
+                Err(e) => {
+                    buf.truncate(length);
+                    return Err(e.into());
+                }
+            };
+            if read < bytes.len() {
+                trace::<T>("read to end", (buf.len() - length) * T::SIZE);
+                return Ok((buf.len() - length, bytes[..read].to_vec()));
+            }
+            buf.reserve(chunk_len::<T>());
+            buf.push(unsafe { next.assume_init() });
+            continue;
+        }
+        // only zero the part of the spare capacity read in this iteration, at most one chunk
+        let len = (buf.capacity() - buf.len()).min(chunk_len::<T>());
+        let spare = &mut buf.spare_capacity_mut()[..len];
+        // safe because the chunk is zeroed before building a slice of exactly its size in bytes,
+        // which only lives during this iteration.
+        let bytes = unsafe {
+            ptr::write_bytes(spare.as_mut_ptr(), 0, spare.len());
+            slice::from_raw_parts_mut(spare.as_mut_ptr() as *mut u8, spare.len() * T::SIZE)
+        };
+        let read = match fill(r, bytes) {
+            Ok(read) => read,
+            Err(e) => {
+                buf.truncate(length);
+                return Err(e.into());
+            }
+        };
+        let whole = read / T::SIZE;
+        let trailing = bytes[whole * T::SIZE..read].to_vec();
+        let eof = read < bytes.len();
+        // safe because the first whole values of the spare capacity have been entirely read
+        unsafe {
+            buf.set_len(buf.len() + whole);
+        }
+        if eof {
+            trace::<T>("read to end", (buf.len() - length) * T::SIZE);
+            return Ok((buf.len() - length, trailing));
+        }
+    }
+}
+
+pub(crate) fn read_raw_to_end<T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>) -> Result<usize> {
+    let length = buf.len();
+    let (count, trailing) = read_raw_to_end_trailing(r, buf)?;
+    if !trailing.is_empty() {
+        buf.truncate(length);
+        return Err(Error::trailing_bytes::<T>(trailing.len()));
+    }
+    Ok(count)
+}
+
+pub(crate) fn read_raw_exact<T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T]) -> Result<()> {
+    fill_exact::<T, R>(r, raw_bytes_mut(buf))
+}
+
+/// Read values of type `T` to fill the uninitialized `buf` and return it as initialized. `buf` is
+/// never zeroed: the bytes are read into a zeroed chunk of `CHUNK_SIZE` bytes and copied from it.
+pub(crate) fn read_raw_uninit<'a, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+    let size = buf.len() * T::SIZE;
+    let dst = buf.as_mut_ptr() as *mut u8;
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut filled = 0;
+    while filled < size {
+        let len = (size - filled).min(CHUNK_SIZE);
+        let read = fill(r, &mut chunk[..len])?;
+        // safe because dst points to size bytes and filled + read <= size
+        unsafe {
+            ptr::copy_nonoverlapping(chunk.as_ptr(), dst.add(filled), read);
+        }
+        filled += read;
+        if read < len {
+            return Err(Error::short_read::<T>(size, filled));
+        }
+    }
+    trace::<T>("read", size);
+    // safe because all the bytes of buf have been written
+    unsafe {
+        Ok(slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, buf.len()))
+    }
+}
+
+fn check_size<T: Packed>(count: usize) -> Result<usize> {
+    count.checked_mul(T::SIZE).ok_or_else(|| Error::invalid_input::<T, _>(
+        format!("{} values of {} bytes overflow the address space", count, T::SIZE)
+    ))
+}
+
+/// Return a `Vec` with room for `count` values of type `T`, or an `InvalidInput` error if their
+/// size overflows the address space.
+pub(crate) fn with_capacity<T: Packed>(count: usize) -> Result<Vec<T>> {
+    check_size::<T>(count)?;
+    Ok(Vec::with_capacity(count))
+}
+
+pub(crate) fn read_raw_n<T: Packed, R: Read + ?Sized>(r: &mut R, count: usize) -> Result<Vec<T>> {
+    let mut buf = with_capacity::<T>(count)?;
+    read_raw_uninit(r, &mut buf.spare_capacity_mut()[..count])?;
+    // safe because the first count values have been initialized
+    unsafe {
+        buf.set_len(count);
+    }
+    Ok(buf)
+}
+
+/// Return the number of bytes between the current position of `r` and its end, leaving the
+/// position unchanged.
+pub(crate) fn remaining_len<R: Seek + ?Sized>(r: &mut R) -> Result<u64> {
+    let position = r.stream_position()?;
+    let end = r.seek(SeekFrom::End(0))?;
+    if end != position {
+        r.seek(SeekFrom::Start(position))?;
+    }
+    Ok(end.saturating_sub(position))
+}
+
+pub(crate) fn remaining_items<T: Packed, R: Seek + ?Sized>(r: &mut R) -> Result<(u64, usize)> {
+    let remaining = remaining_len(r)?;
+    let size = T::SIZE.max(1) as u64;
+    Ok((remaining / size, (remaining % size) as usize))
+}
+
+pub(crate) fn skip<T: Packed, R: Read + ?Sized>(r: &mut R, count: usize) -> Result<()> {
+    let size = check_size::<T>(count)? as u64;
+    let skipped = io::copy(&mut Read::take(&mut *r, size), &mut io::sink())?;
+    if skipped < size {
+        return Err(Error::short_read::<T>(size as usize, skipped as usize));
+    }
+    Ok(())
+}
+
+pub(crate) fn skip_seek<T: Packed, R: Seek + ?Sized>(r: &mut R, count: usize) -> Result<()> {
+    let size = check_size::<T>(count)? as u64;
+    let remaining = remaining_len(r)?;
+    if remaining < size {
+        return Err(Error::short_read::<T>(size as usize, remaining as usize));
+    }
+    r.seek(SeekFrom::Current(size as i64))?;
+    Ok(())
+}
+
+pub(crate) fn write_raw<T: Packed, W: Write + ?Sized>(w: &mut W, t: &T) -> Result<()> {
+    // safe because we build a slice of exactly T::SIZE bytes
+    unsafe {
+        w.write_all(slice::from_raw_parts(t as *const T as *const u8, T::SIZE))?;
+    }
+    trace::<T>("write", T::SIZE);
+    Ok(())
+}
+
+pub(crate) fn write_raw_all<T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T]) -> Result<()> {
+    // safe because we build a slice of exactly buf.len() * T::SIZE bytes
+    unsafe {
+        w.write_all(slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len() * T::SIZE))?;
+    }
+    trace::<T>("write", buf.len() * T::SIZE);
+    Ok(())
+}
+
+/// Return the bytes of the values of `buf` in memory.
+pub(crate) fn raw_bytes<T: Packed>(buf: &[T]) -> &[u8] {
+    // safe because we build a slice of exactly buf.len() * T::SIZE bytes
+    unsafe {
+        slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len() * T::SIZE)
+    }
+}
+
+/// Return the bytes of the values of `buf` in memory as a mutable slice.
+pub(crate) fn raw_bytes_mut<T: Packed>(buf: &mut [T]) -> &mut [u8] {
+    // safe because we build a slice of exactly buf.len() * T::SIZE bytes
+    unsafe {
+        slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, buf.len() * T::SIZE)
+    }
+}
+
+pub(crate) fn write_raw_vectored<T: Packed, W: Write + ?Sized>(w: &mut W, bufs: &[&[T]]) -> Result<()> {
+    let mut slices : Vec<IoSlice> = bufs.iter().map(|buf| IoSlice::new(raw_bytes(buf))).collect();
+    let total = slices.iter().map(|slice| slice.len()).sum();
+    let mut written = 0;
+    let mut slices = &mut slices[..];
+    // skip the leading empty slices so a write of zero bytes always means failure
+    IoSlice::advance_slices(&mut slices, 0);
+    while !slices.is_empty() {
+        match w.write_vectored(slices) {
+            Ok(0) => return Err(Error::short_write::<T>(total, written)),
+            Ok(n) => {
+                written += n;
+                IoSlice::advance_slices(&mut slices, n);
+            },
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {},
+            Err(e) => return Err(e.into())
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<T> {
+    let mut t = read_raw::<T, R>(r)?;
+    O::convert(&mut t);
+    Ok(t)
+}
+
+pub(crate) fn unpack_validated<O: Order, T: PackedValidate, R: Read + ?Sized>(r: &mut R) -> Result<T> {
+    let t = unpack::<O, T, R>(r)?;
+    t.validate().map_err(Error::invalid_value::<T, _>)?;
+    Ok(t)
+}
+
+pub(crate) fn unpack_exact_validated<O: Order, T: PackedValidate, R: Read + ?Sized>(r: &mut R, buf: &mut [T]) -> Result<()> {
+    unpack_exact::<O, T, R>(r, buf)?;
+    for (i, t) in buf.iter().enumerate() {
+        t.validate().map_err(|reason| Error::invalid_value::<T, _>(format!("value {}: {}", i, reason)))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_buffered<O: Order, T: Packed, R: BufRead + ?Sized>(r: &mut R) -> Result<T> {
+    let bytes = r.fill_buf()?;
+    if bytes.len() < T::SIZE {
+        // the value straddles the end of the buffer
+        return unpack::<O, T, R>(r);
+    }
+    // safe because bytes holds at least T::SIZE bytes, which are read without any
+    // alignment requirement, and the bitwise copy of a packed value is a valid value.
+    let mut t = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) };
+    r.consume(T::SIZE);
+    trace::<T>("read", T::SIZE);
+    O::convert(&mut t);
+    Ok(t)
+}
+
+pub(crate) fn unpack_maybe<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<Option<T>> {
+    let mut t = read_raw_maybe::<T, R>(r)?;
+    if let Some(ref mut t) = t {
+        O::convert(t);
+    }
+    Ok(t)
+}
+
+pub(crate) fn unpack_to_end<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>) -> Result<usize> {
+    let size = read_raw_to_end(r, buf)?;
+    let start = buf.len() - size;
+    O::convert_slice(&mut buf[start..]);
+    Ok(size)
+}
+
+pub(crate) fn unpack_to_end_sized<O: Order, T: Packed, R: Read + Seek + ?Sized>(r: &mut R, buf: &mut Vec<T>) -> Result<usize> {
+    let remaining = remaining_len(r)? / T::SIZE.max(1) as u64;
+    if let Ok(remaining) = usize::try_from(remaining) {
+        buf.reserve_exact(remaining);
+    }
+    unpack_to_end::<O, T, R>(r, buf)
+}
+
+pub(crate) fn unpack_to_end_trailing<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>, policy: TrailingPolicy) -> Result<ToEnd> {
+    if policy == TrailingPolicy::Error {
+        let count = unpack_to_end::<O, T, R>(r, buf)?;
+        return Ok(ToEnd { count, trailing_len: 0, trailing: Vec::new() });
+    }
+    let (count, trailing) = read_raw_to_end_trailing(r, buf)?;
+    let start = buf.len() - count;
+    O::convert_slice(&mut buf[start..]);
+    Ok(ToEnd {
+        count,
+        trailing_len: trailing.len(),
+        trailing: if policy == TrailingPolicy::ReturnTrailingBytes { trailing } else { Vec::new() }
+    })
+}
+
+pub(crate) fn unpack_to_end_limited<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>, max_items: usize) -> Result<usize> {
+    let length = buf.len();
+    let size = check_size::<T>(max_items)? as u64;
+    let count = unpack_to_end::<O, T, _>(&mut Read::take(&mut *r, size), buf)?;
+    // the limit is only exceeded if some data remains after max_items values
+    if count == max_items && fill(r, &mut [0u8])? != 0 {
+        buf.truncate(length);
+        return Err(Error::limit_exceeded::<T>(max_items as u64));
+    }
+    Ok(count)
+}
+
+pub(crate) fn unpack_while<O: Order, T: Packed, R: Read + ?Sized, F: FnMut(&T) -> bool>(r: &mut R, buf: &mut Vec<T>, max_items: usize, mut predicate: F) -> Result<Option<T>> {
+    let length = buf.len();
+    let result = (|| loop {
+        let t = match unpack_maybe::<O, T, R>(r)? {
+            Some(t) => t,
+            None => return Ok(None)
+        };
+        if !predicate(&t) {
+            return Ok(Some(t));
+        }
+        if buf.len() - length == max_items {
+            return Err(Error::limit_exceeded::<T>(max_items as u64));
+        }
+        buf.push(t);
+    })();
+    if result.is_err() {
+        buf.truncate(length);
+    }
+    result
+}
+
+pub(crate) fn unpack_while_put_back<O: Order, T: Packed, R: Read + Seek + ?Sized, F: FnMut(&T) -> bool>(r: &mut R, buf: &mut Vec<T>, max_items: usize, predicate: F) -> Result<usize> {
+    let length = buf.len();
+    if unpack_while::<O, T, R, F>(r, buf, max_items, predicate)?.is_some() {
+        if let Err(e) = r.seek(SeekFrom::Current(-(T::SIZE as i64))) {
+            buf.truncate(length);
+            return Err(e.into());
+        }
+    }
+    Ok(buf.len() - length)
+}
+
+pub(crate) fn unpack_until<O: Order, T: Packed + PartialEq, R: Read + ?Sized>(r: &mut R, sentinel: &T, buf: &mut Vec<T>, policy: Sentinel, max_items: usize) -> Result<usize> {
+    let length = buf.len();
+    // the sentinel does not count toward the limit
+    match unpack_while::<O, T, R, _>(r, buf, max_items, |t| t != sentinel)? {
+        Some(t) => {
+            if policy == Sentinel::Include {
+                buf.push(t);
+            }
+            Ok(buf.len() - length)
+        },
+        None => {
+            buf.truncate(length);
+            Err(Error::short_read::<T>(T::SIZE, 0))
+        }
+    }
+}
+
+pub(crate) fn unpack_n_limited<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, count: usize, max_items: usize) -> Result<Vec<T>> {
+    if count > max_items {
+        return Err(Error::limit_exceeded::<T>(max_items as u64));
+    }
+    unpack_n::<O, T, R>(r, count)
+}
+
+/// Call `f` with a chunk of `chunk_len::<T>()` uninitialized values on the stack, or `None` if
+/// `T` is too large or too aligned to fit in it.
+fn with_stack_chunk<T: Packed, U, F: FnOnce(Option<&mut [MaybeUninit<T>]>) -> U>(f: F) -> U {
+    if size_of::<T>() > CHUNK_SIZE || align_of::<T>() > align_of::<u64>() {
+        return f(None);
+    }
+    let mut words = [MaybeUninit::<u64>::uninit(); CHUNK_SIZE / 8];
+    // safe because the words are CHUNK_SIZE bytes long and aligned for T, which is checked above
+    f(Some(unsafe { slice::from_raw_parts_mut(words.as_mut_ptr() as *mut MaybeUninit<T>, chunk_len::<T>()) }))
+}
+
+pub(crate) fn unpack_extend<O: Order, T: Packed, E: Extend<T> + ?Sized, R: Read + ?Sized>(r: &mut R, target: &mut E, count: usize) -> Result<()> {
+    with_stack_chunk(|chunk| {
+        let chunk = match chunk {
+            Some(chunk) => chunk,
+            None => {
+                for _ in 0..count {
+                    target.extend(Some(unpack::<O, T, R>(r)?));
+                }
+                return Ok(());
+            }
+        };
+        let mut remaining = count;
+        while remaining > 0 {
+            let len = remaining.min(chunk.len());
+            let values = unpack_exact_uninit::<O, T, R>(r, &mut chunk[..len])?;
+            // safe because the values are bitwise copied out of the chunk, which never drops them
+            target.extend(values.iter().map(|value| unsafe { ptr::read(value) }));
+            remaining -= len;
+        }
+        Ok(())
+    })
+}
+
+pub(crate) fn unpack_to_end_fixed<O: Order, C: FixedCapacity + ?Sized, R: Read + ?Sized>(r: &mut R, buf: &mut C) -> Result<usize> {
+    if <C::Item as Packed>::SIZE == 0 {
+        return Err(Error::invalid_input::<C::Item, _>("cannot read zero-sized values to the end"));
+    }
+    let length = buf.len();
+    let spare = buf.spare_capacity_mut();
+    let capacity = spare.len();
+    // safe because the spare capacity is zeroed before building a slice of exactly its size in
+    // bytes and the values are only considered initialized once they have been entirely read.
+    let read = unsafe {
+        ptr::write_bytes(spare.as_mut_ptr(), 0, capacity);
+        fill(r, slice::from_raw_parts_mut(spare.as_mut_ptr() as *mut u8, capacity * <C::Item as Packed>::SIZE))?
+    };
+    let partial = read % <C::Item as Packed>::SIZE;
+    if partial != 0 {
+        return Err(Error::trailing_bytes::<C::Item>(partial));
+    }
+    let count = read / <C::Item as Packed>::SIZE;
+    // the capacity is only exceeded if some data remains once the collection is full
+    if count == capacity && fill(r, &mut [0u8])? != 0 {
+        return Err(Error::limit_exceeded::<C::Item>(capacity as u64));
+    }
+    // safe because the first count values of the spare capacity have been read
+    unsafe {
+        let values = slice::from_raw_parts_mut(spare.as_mut_ptr() as *mut C::Item, count);
+        O::convert_slice(values);
+        buf.set_len(length + count);
+    }
+    Ok(count)
+}
+
+pub(crate) fn unpack_n_fixed<O: Order, C: FixedCapacity + Default, R: Read + ?Sized>(r: &mut R, count: usize) -> Result<C> {
+    let mut buf = C::default();
+    let length = buf.len();
+    let spare = buf.spare_capacity_mut();
+    if count > spare.len() {
+        return Err(Error::limit_exceeded::<C::Item>(spare.len() as u64));
+    }
+    unpack_exact_uninit::<O, C::Item, R>(r, &mut spare[..count])?;
+    // safe because the first count values of the spare capacity have been read
+    unsafe {
+        buf.set_len(length + count);
+    }
+    Ok(buf)
+}
+
+pub(crate) fn unpack_with_tail<O: Order, H: TailCount, T: Packed, R: Read + ?Sized>(r: &mut R, max_items: usize) -> Result<WithTail<H, T>> {
+    let header = unpack::<O, H, R>(r)?;
+    let tail = unpack_n_limited::<O, T, R>(r, header.tail_count(), max_items)?;
+    Ok(WithTail {
+        header,
+        tail
+    })
+}
+
+pub(crate) fn pack_with_tail<O: Order, H: TailCount, T: Packed, W: Write + ?Sized>(w: &mut W, record: &WithTail<H, T>) -> Result<()> {
+    let count = record.header.tail_count();
+    if count != record.tail.len() {
+        return Err(Error::invalid_input::<H, _>(format!("the header gives {} values but the tail holds {}", count, record.tail.len())));
+    }
+    pack_ref::<O, H, W>(w, &record.header)?;
+    pack_all::<O, T, W>(w, &record.tail)
+}
+
+pub(crate) fn unpack_header_packed<O: Order, T: HeaderPacked, R: Read + ?Sized>(r: &mut R, max_len: usize) -> Result<T> {
+    let header = unpack::<O, T::Header, R>(r)?;
+    let len = T::body_len(&header);
+    if len > max_len {
+        return Err(Error::byte_limit_exceeded::<T>(max_len as u64));
+    }
+    let body = read_payload(r, len)?;
+    let mut unpacker = SliceUnpacker::with_endianness(&body, O::ENDIANNESS);
+    let t = T::unpack_body(header, &mut unpacker)?;
+    let trailing = unpacker.remaining().len();
+    if trailing != 0 {
+        // A record has no fixed size, the one reported is the number of bytes it unpacked.
+        return Err(Error::TrailingBytes {
+            type_name: type_name::<T>(),
+            size: <T::Header as Packed>::SIZE + len - trailing,
+            trailing,
+            offset: None
+        });
+    }
+    Ok(t)
+}
+
+pub(crate) fn pack_header_packed<O: Order, T: HeaderPacked, W: Write + ?Sized>(w: &mut W, t: &T) -> Result<()> {
+    let header = t.header();
+    let mut body = Vec::new();
+    t.pack_body(&mut body, O::ENDIANNESS)?;
+    let len = T::body_len(&header);
+    if len != body.len() {
+        return Err(Error::invalid_input::<T, _>(format!("the header gives a body of {} bytes but {} were packed", len, body.len())));
+    }
+    pack::<O, T::Header, W>(w, header)?;
+    write_raw_all(w, &body[..])
+}
+
+/// Call `f` with `s` positioned at `offset` bytes from its start, then restore the original
+/// position, even if `f` failed.
+pub(crate) fn at<S: Seek + ?Sized, U, F: FnOnce(&mut S) -> Result<U>>(s: &mut S, offset: u64, f: F) -> Result<U> {
+    let position = s.stream_position()?;
+    if offset != position {
+        s.seek(SeekFrom::Start(offset))?;
+    }
+    let result = f(s).map_err(|e| e.with_offset(offset));
+    // an error of f is more useful than the one of restoring the position
+    let restored = s.seek(SeekFrom::Start(position));
+    let value = result?;
+    restored?;
+    Ok(value)
+}
+
+/// Call `f` with `s` and restore the original position of `s` if `f` failed.
+pub(crate) fn transaction<S: Seek + ?Sized, U, F: FnOnce(&mut S) -> Result<U>>(s: &mut S, f: F) -> Result<U> {
+    let position = s.stream_position()?;
+    let result = f(s);
+    if result.is_err() {
+        // the error of f is returned even if the position cannot be restored
+        let _ = s.seek(SeekFrom::Start(position));
+    }
+    result
+}
+
+pub(crate) fn peek<O: Order, T: Packed, R: Read + Seek + ?Sized>(r: &mut R) -> Result<T> {
+    let position = r.stream_position()?;
+    at(r, position, unpack::<O, T, R>)
+}
+
+pub(crate) fn unpack_at<O: Order, T: Packed, R: Read + Seek + ?Sized>(r: &mut R, offset: u64) -> Result<T> {
+    at(r, offset, unpack::<O, T, R>)
+}
+
+pub(crate) fn unpack_exact_at<O: Order, T: Packed, R: Read + Seek + ?Sized>(r: &mut R, offset: u64, buf: &mut [T]) -> Result<()> {
+    at(r, offset, |r| unpack_exact::<O, T, R>(r, buf))
+}
+
+pub(crate) fn unpack_exact<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T]) -> Result<()> {
+    read_raw_exact(r, buf)?;
+    O::convert_slice(buf);
+    Ok(())
+}
+
+pub(crate) fn unpack_n<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, count: usize) -> Result<Vec<T>> {
+    let mut buf = read_raw_n::<T, R>(r, count)?;
+    O::convert_slice(&mut buf[..]);
+    Ok(buf)
+}
+
+pub(crate) fn unpack_exact_uninit<'a, O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &'a mut [MaybeUninit<T>]) -> Result<&'a mut [T]> {
+    let buf = read_raw_uninit(r, buf)?;
+    O::convert_slice(buf);
+    Ok(buf)
+}
+
+pub(crate) fn unpack_strided<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T], offset: usize, stride: usize) -> Result<()> {
+    let mut chunk = Vec::<T>::with_capacity(chunk_len::<T>().min(buf.len()).max(1));
+    unpack_strided_chunked::<O, T, R>(r, buf, offset, stride, chunk.spare_capacity_mut())
+}
+
+pub(crate) fn unpack_strided_with_scratch<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T], offset: usize, stride: usize, scratch: &mut ScratchBuffer) -> Result<()> {
+    unpack_strided_chunked::<O, T, R>(r, buf, offset, stride, scratch.chunk(chunk_len::<T>().min(buf.len()).max(1)))
+}
+
+/// Unpack values into every `stride` value of `buf` starting at `offset`, read in `chunk`, which
+/// must not be empty.
+fn unpack_strided_chunked<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T], offset: usize, stride: usize, chunk: &mut [MaybeUninit<T>]) -> Result<()> {
+    if stride == 0 {
+        return Err(Error::invalid_input::<T, _>("the stride must not be zero"));
+    }
+    let mut remaining = if offset < buf.len() { (buf.len() - offset - 1) / stride + 1 } else { 0 };
+    let mut targets = buf.iter_mut().skip(offset).step_by(stride);
+    while remaining > 0 {
+        let count = chunk.len().min(remaining);
+        let values = unpack_exact_uninit::<O, T, R>(r, &mut chunk[..count])?;
+        for (value, target) in values.iter().zip(targets.by_ref()) {
+            // safe because the bitwise copy of a packed value is a valid value and packed values
+            // have no drop glue.
+            *target = unsafe { ptr::read(value) };
+        }
+        remaining -= count;
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_deinterleaved<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, channels: usize, frames: usize) -> Result<Vec<Vec<T>>> {
+    let mut chunk = Vec::<T>::with_capacity(deinterleave_chunk_len::<T>(channels, frames));
+    unpack_deinterleaved_chunked::<O, T, R>(r, channels, frames, chunk.spare_capacity_mut())
+}
+
+pub(crate) fn unpack_deinterleaved_with_scratch<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, channels: usize, frames: usize, scratch: &mut ScratchBuffer) -> Result<Vec<Vec<T>>> {
+    unpack_deinterleaved_chunked::<O, T, R>(r, channels, frames, scratch.chunk(deinterleave_chunk_len::<T>(channels, frames)))
+}
+
+/// Number of values of the chunk used to deinterleave `frames` frames of `channels` values: a
+/// whole number of frames, at least one.
+fn deinterleave_chunk_len<T: Packed>(channels: usize, frames: usize) -> usize {
+    let frames_per_chunk = (chunk_len::<T>() / channels.max(1)).max(1);
+    frames_per_chunk.min(frames).max(1).saturating_mul(channels)
+}
+
+/// Unpack `frames` frames of `channels` values, read in `chunk`, which must hold at least one
+/// frame.
+fn unpack_deinterleaved_chunked<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, channels: usize, frames: usize, chunk: &mut [MaybeUninit<T>]) -> Result<Vec<Vec<T>>> {
+    let count = channels.checked_mul(frames).ok_or_else(|| Error::invalid_input::<T, _>(
+        format!("{} channels of {} frames overflow the address space", channels, frames)
+    ))?;
+    check_size::<T>(count)?;
+    let mut planar: Vec<Vec<T>> = (0..channels).map(|_| Vec::with_capacity(frames)).collect();
+    let frames_per_chunk = chunk.len() / channels.max(1);
+    let mut remaining = frames;
+    while remaining > 0 && channels > 0 {
+        let count = frames_per_chunk.min(remaining);
+        let values = unpack_exact_uninit::<O, T, R>(r, &mut chunk[..count * channels])?;
+        for frame in values.chunks(channels) {
+            for (channel, value) in planar.iter_mut().zip(frame.iter()) {
+                // safe because the bitwise copy of a packed value is a valid value and packed
+                // values have no drop glue.
+                channel.push(unsafe { ptr::read(value) });
+            }
+        }
+        remaining -= count;
+    }
+    Ok(planar)
+}
+
+fn check_matrix<T>(rows: usize, cols: usize, buf_len: usize) -> Result<()> {
+    if rows.checked_mul(cols) != Some(buf_len) {
+        return Err(Error::invalid_input::<T, _>(
+            format!("a buffer of {} values does not hold a {}x{} matrix", buf_len, rows, cols)
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_transposed<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, rows: usize, cols: usize, buf: &mut [T]) -> Result<()> {
+    check_matrix::<T>(rows, cols, buf.len())?;
+    let mut chunk = Vec::<T>::with_capacity(chunk_len::<T>().min(rows).max(1));
+    for col in 0..cols {
+        unpack_strided_chunked::<O, T, R>(r, buf, col, cols, chunk.spare_capacity_mut())?;
+    }
+    Ok(())
+}
+
+/// Return the number of padding bytes following each row of `row_len` values of type `T`.
+fn row_padding<T: Packed>(buf_len: usize, row_len: usize, row_stride: usize) -> Result<usize> {
+    if row_len == 0 || !buf_len.is_multiple_of(row_len) {
+        return Err(Error::invalid_input::<T, _>(
+            format!("a buffer of {} values cannot be split in rows of {} values", buf_len, row_len)
+        ));
+    }
+    let row_size = check_size::<T>(row_len)?;
+    row_stride.checked_sub(row_size).ok_or_else(|| Error::invalid_input::<T, _>(
+        format!("a row stride of {} bytes is shorter than a row of {} bytes", row_stride, row_size)
+    ))
+}
+
+pub(crate) fn unpack_rows<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut [T], row_len: usize, row_stride: usize) -> Result<()> {
+    let padding = row_padding::<T>(buf.len(), row_len, row_stride)?;
+    for row in buf.chunks_mut(row_len) {
+        unpack_exact::<O, T, R>(r, row)?;
+        skip::<u8, R>(r, padding)?;
+    }
+    Ok(())
+}
+
+/// Read a payload of `len` bytes without allocating more memory than what is actually read, since
+/// `len` may come from untrusted input.
+fn read_payload<R: Read + ?Sized>(r: &mut R, len: usize) -> Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    Read::take(&mut *r, len as u64).read_to_end(&mut payload)?;
+    if payload.len() < len {
+        return Err(Error::short_read::<u8>(len, payload.len()));
+    }
+    Ok(payload)
+}
+
+pub(crate) fn unpack_tlv<O: Order, Tag: Packed, L: LengthPrefix, R: Read + ?Sized>(r: &mut R) -> Result<(Tag, Vec<u8>)> {
+    let tag = unpack::<O, Tag, R>(r)?;
+    let len = unpack::<O, L, R>(r)?;
+    let len = len.to_len().ok_or_else(|| Error::invalid_value::<L, _>("the length does not fit in a usize"))?;
+    Ok((tag, read_payload(r, len)?))
+}
+
+pub(crate) fn unpack_tlv_value<O: Order, Tag: Packed, L: LengthPrefix, T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<(Tag, T)> {
+    let (tag, payload) = unpack_tlv::<O, Tag, L, R>(r)?;
+    Ok((tag, from_bytes_slice::<O, T>(&payload[..])?))
+}
+
+pub(crate) fn unpack_framed<O: Order, L: LengthPrefix, T: Packed, R: Read + ?Sized>(r: &mut R, max_len: usize) -> Result<Vec<T>> {
+    let len = unpack::<O, L, R>(r)?.to_len().filter(|&len| len <= max_len)
+        .ok_or_else(|| Error::byte_limit_exceeded::<T>(max_len as u64))?;
+    let size = T::SIZE;
+    if size == 0 {
+        return Err(Error::invalid_input::<T, _>("cannot unpack a frame of zero-sized values"));
+    }
+    let values = unpack_n::<O, T, R>(r, len / size)?;
+    if len % size != 0 {
+        skip::<u8, R>(r, len % size)?;
+        return Err(Error::trailing_bytes::<T>(len % size));
+    }
+    Ok(values)
+}
+
+pub(crate) fn unpack_framed_value<O: Order, L: LengthPrefix, T: Packed, R: Read + ?Sized>(r: &mut R) -> Result<T> {
+    let size = T::SIZE;
+    let len = unpack::<O, L, R>(r)?.to_len().ok_or_else(|| Error::byte_limit_exceeded::<T>(size as u64))?;
+    if len != size {
+        skip::<u8, R>(r, len)?;
+        return Err(if len < size {
+            Error::short_read::<T>(size, len)
+        } else {
+            Error::trailing_bytes::<T>(len - size)
+        });
+    }
+    unpack::<O, T, R>(r)
+}
+
+pub(crate) fn expect_magic<R: Read + ?Sized>(r: &mut R, magic: &[u8]) -> Result<()> {
+    let mut found = vec![0u8; magic.len()];
+    read_raw_exact(r, &mut found[..])?;
+    if found != magic {
+        return Err(Error::invalid_value::<[u8], _>(format!("expected magic {:02X?}, found {:02X?}", magic, found)));
+    }
+    Ok(())
+}
+
+pub(crate) fn expect_magic_value<O: Order, T: Packed + PartialEq + Debug, R: Read + ?Sized>(r: &mut R, magic: T) -> Result<()> {
+    let found = unpack::<O, T, R>(r)?;
+    if found != magic {
+        return Err(Error::invalid_value::<T, _>(format!("expected magic {:X?}, found {:X?}", magic, found)));
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_with_checksum<O: Order, U, F: FnOnce(&mut Crc32Reader<&mut R>) -> Result<U>, R: Read + ?Sized>(r: &mut R, f: F) -> Result<U> {
+    let mut reader = Crc32Reader::new(&mut *r);
+    let u = f(&mut reader)?;
+    let computed = reader.digest();
+    let stored = unpack::<O, u32, R>(r)?;
+    if stored != computed {
+        return Err(Error::invalid_value::<u32, _>(
+            format!("the checksum {:08X} does not match the computed {:08X}", stored, computed)
+        ));
+    }
+    Ok(u)
+}
+
+pub(crate) fn unpack_converted<O: Order, S: Packed, D, F: FnMut(S) -> D, R: Read + ?Sized>(r: &mut R, buf: &mut [D], convert: F) -> Result<()> {
+    let mut chunk = Vec::<S>::with_capacity(chunk_len::<S>().min(buf.len()).max(1));
+    unpack_converted_chunked::<O, S, D, F, R>(r, buf, convert, chunk.spare_capacity_mut())
+}
+
+pub(crate) fn unpack_converted_with_scratch<O: Order, S: Packed, D, F: FnMut(S) -> D, R: Read + ?Sized>(r: &mut R, buf: &mut [D], convert: F, scratch: &mut ScratchBuffer) -> Result<()> {
+    unpack_converted_chunked::<O, S, D, F, R>(r, buf, convert, scratch.chunk(chunk_len::<S>().min(buf.len()).max(1)))
+}
+
+/// Unpack values of type `S` read in `chunk`, which must not be empty, and store them converted
+/// in `buf`.
+fn unpack_converted_chunked<O: Order, S: Packed, D, F: FnMut(S) -> D, R: Read + ?Sized>(r: &mut R, buf: &mut [D], mut convert: F, chunk: &mut [MaybeUninit<S>]) -> Result<()> {
+    for targets in buf.chunks_mut(chunk.len()) {
+        let values = unpack_exact_uninit::<O, S, R>(r, &mut chunk[..targets.len()])?;
+        for (target, value) in targets.iter_mut().zip(values.iter()) {
+            // safe because the bitwise copy of a packed value is a valid value and packed values
+            // have no drop glue.
+            *target = convert(unsafe { ptr::read(value) });
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_as<O: Order, S: Integer, T: Integer, R: Read + ?Sized>(r: &mut R, overflow: Overflow) -> Result<T> {
+    let value = unpack::<O, S, R>(r)?.to_i128();
+    T::from_i128(value, overflow).ok_or_else(|| Error::invalid_value::<T, _>(format!("{} is out of range", value)))
+}
+
+pub(crate) fn unpack_exact_as<O: Order, S: Integer, T: Integer, R: Read + ?Sized>(r: &mut R, buf: &mut [T], overflow: Overflow) -> Result<()> {
+    let mut invalid = None;
+    let mut index = 0;
+    unpack_converted::<O, S, T, _, R>(r, buf, |value| {
+        let value = value.to_i128();
+        let converted = T::from_i128(value, overflow).unwrap_or_else(|| {
+            invalid = invalid.or(Some((index, value)));
+            T::from_i128(0, Overflow::Wrap).unwrap()
+        });
+        index += 1;
+        converted
+    })?;
+    match invalid {
+        Some((i, value)) => Err(Error::invalid_value::<T, _>(format!("value {}: {} is out of range", i, value))),
+        None => Ok(())
+    }
+}
+
+pub(crate) fn unpack_append<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, buf: &mut Vec<T>, count: usize) -> Result<()> {
+    check_size::<T>(count)?;
+    buf.reserve(count);
+    let length = buf.len();
+    unpack_exact_uninit::<O, T, R>(r, &mut buf.spare_capacity_mut()[..count])?;
+    // safe because the count values following the original length have been initialized
+    unsafe {
+        buf.set_len(length + count);
+    }
+    Ok(())
+}
+
+pub(crate) fn unpack_arc_slice<O: Order, T: Packed, R: Read + ?Sized>(r: &mut R, len: usize) -> Result<Arc<[T]>> {
+    check_size::<T>(len)?;
+    let mut arc = Arc::<[T]>::new_uninit_slice(len);
+    {
+        // the Arc was just allocated so it cannot be shared yet
+        let buf = Arc::get_mut(&mut arc).unwrap();
+        O::convert_slice(read_raw_uninit(r, buf)?);
+    }
+    // safe because all the values have been initialized by read_raw_uninit
+    unsafe {
+        Ok(arc.assume_init())
+    }
+}
+
+pub(crate) fn pack<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, t: T) -> Result<()> {
+    let mut t = t;
+    O::convert(&mut t);
+    write_raw(w, &t)
+}
+
+pub(crate) fn pack_ref<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, t: &T) -> Result<()> {
+    if O::ENDIANNESS.is_native() {
+        write_raw(w, t)
+    }
+    else {
+        // safe because the bitwise copy of a packed value is a valid value and the copy is never
+        // dropped.
+        let mut copy = ManuallyDrop::new(unsafe { ptr::read(t) });
+        O::convert(&mut *copy);
+        write_raw(w, &*copy)
+    }
+}
+
+pub(crate) fn pack_repeat<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, t: &T, count: usize) -> Result<()> {
+    with_stack_chunk(|chunk| {
+        let chunk = match chunk {
+            Some(chunk) => chunk,
+            None => {
+                for _ in 0..count {
+                    pack_ref::<O, T, W>(w, t)?;
+                }
+                return Ok(());
+            }
+        };
+        let len = count.min(chunk.len());
+        // safe because the first len values of the chunk are initialized with bitwise copies of
+        // a packed value, which are valid values never dropped by the chunk.
+        let copies = unsafe {
+            for copy in chunk[..len].iter_mut() {
+                ptr::copy_nonoverlapping(t, copy.as_mut_ptr(), 1);
+            }
+            slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut T, len)
+        };
+        O::convert_slice(copies);
+        let mut remaining = count;
+        while remaining > 0 {
+            let len = remaining.min(copies.len());
+            write_raw_all(w, &copies[..len])?;
+            remaining -= len;
+        }
+        Ok(())
+    })
+}
+
+pub(crate) fn to_bytes_vec<O: Order, T: Packed>(t: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(T::SIZE);
+    pack_ref::<O, T, Vec<u8>>(&mut bytes, t).expect("writing to a Vec cannot fail");
+    bytes
+}
+
+pub(crate) fn from_bytes_slice<O: Order, T: Packed>(bytes: &[u8]) -> Result<T> {
+    let mut rest = bytes;
+    let t = unpack::<O, T, &[u8]>(&mut rest)?;
+    if !rest.is_empty() {
+        return Err(Error::trailing_bytes::<T>(rest.len()));
+    }
+    Ok(t)
+}
+
+pub(crate) fn pack_at<O: Order, T: Packed, W: Write + Seek + ?Sized>(w: &mut W, offset: u64, t: T) -> Result<()> {
+    at(w, offset, |w| pack::<O, T, W>(w, t))
+}
+
+pub(crate) fn pack_all<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T]) -> Result<()> {
+    if O::ENDIANNESS.is_native() {
+        return write_raw_all(w, buf);
+    }
+    let mut chunk = Vec::<T>::with_capacity(chunk_len::<T>().min(buf.len()).max(1));
+    pack_all_chunked::<O, T, W>(w, buf, chunk.spare_capacity_mut())
+}
+
+pub(crate) fn pack_all_with_scratch<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T], scratch: &mut ScratchBuffer) -> Result<()> {
+    if O::ENDIANNESS.is_native() {
+        return write_raw_all(w, buf);
+    }
+    pack_all_chunked::<O, T, W>(w, buf, scratch.chunk(chunk_len::<T>().min(buf.len()).max(1)))
+}
+
+/// Pack the values of `buf` converted in `chunk`, which must not be empty.
+fn pack_all_chunked<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T], chunk: &mut [MaybeUninit<T>]) -> Result<()> {
+    for values in buf.chunks(chunk.len()) {
+        // safe because the chunk has room for values.len() values, the bitwise copy of a packed
+        // value is a valid value and the copies are never dropped.
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), chunk.as_mut_ptr() as *mut T, values.len());
+            let copies = slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut T, values.len());
+            O::convert_slice(copies);
+            write_raw_all(w, copies)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn pack_iter<O: Order, T: Packed, I: IntoIterator<Item = T>, W: Write + ?Sized>(w: &mut W, iter: I) -> Result<()> {
+    let mut chunk = Vec::<T>::with_capacity(chunk_len::<T>());
+    pack_iter_chunked::<O, T, I, W>(w, iter, chunk.spare_capacity_mut())
+}
+
+pub(crate) fn pack_iter_with_scratch<O: Order, T: Packed, I: IntoIterator<Item = T>, W: Write + ?Sized>(w: &mut W, iter: I, scratch: &mut ScratchBuffer) -> Result<()> {
+    pack_iter_chunked::<O, T, I, W>(w, iter, scratch.chunk(chunk_len::<T>()))
+}
+
+/// Pack the values yielded by `iter` gathered in `chunk`, which must not be empty.
+fn pack_iter_chunked<O: Order, T: Packed, I: IntoIterator<Item = T>, W: Write + ?Sized>(w: &mut W, iter: I, chunk: &mut [MaybeUninit<T>]) -> Result<()> {
+    let mut len = 0;
+    for t in iter {
+        chunk[len] = MaybeUninit::new(t);
+        len += 1;
+        if len == chunk.len() {
+            // safe because the whole chunk has been initialized
+            let values = unsafe { slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut T, len) };
+            O::convert_slice(values);
+            write_raw_all(w, values)?;
+            len = 0;
+        }
+    }
+    // safe because the first len values of the chunk have been initialized
+    let values = unsafe { slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut T, len) };
+    O::convert_slice(values);
+    write_raw_all(w, values)
+}
+
+pub(crate) fn pack_strided<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T], offset: usize, stride: usize) -> Result<()> {
+    if stride == 0 {
+        return Err(Error::invalid_input::<T, _>("the stride must not be zero"));
+    }
+    // safe because the bitwise copy of a packed value is a valid value and packed values have no
+    // drop glue.
+    let values = buf.iter().skip(offset).step_by(stride).map(|t| unsafe { ptr::read(t) });
+    pack_iter::<O, T, _, W>(w, values)
+}
+
+pub(crate) fn pack_interleaved<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, channels: &[&[T]]) -> Result<()> {
+    let frames = channels.first().map_or(0, |channel| channel.len());
+    if channels.iter().any(|channel| channel.len() != frames) {
+        return Err(Error::invalid_input::<T, _>("the channels do not all have the same length"));
+    }
+    // safe for the same reason as in pack_strided.
+    let values = (0..frames).flat_map(|i| channels.iter().map(move |channel| unsafe { ptr::read(&channel[i]) }));
+    pack_iter::<O, T, _, W>(w, values)
+}
+
+pub(crate) fn pack_rows<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T], row_len: usize, row_stride: usize) -> Result<()> {
+    let padding = vec![0u8; row_padding::<T>(buf.len(), row_len, row_stride)?];
+    for row in buf.chunks(row_len) {
+        pack_all::<O, T, W>(w, row)?;
+        write_raw_all(w, &padding[..])?;
+    }
+    Ok(())
+}
+
+pub(crate) fn pack_transposed<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, rows: usize, cols: usize, buf: &[T]) -> Result<()> {
+    check_matrix::<T>(rows, cols, buf.len())?;
+    for col in 0..cols {
+        pack_strided::<O, T, W>(w, buf, col, cols)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn pack_tlv<O: Order, Tag: Packed, L: LengthPrefix, W: Write + ?Sized>(w: &mut W, tag: Tag, payload: &[u8]) -> Result<()> {
+    let len = L::from_len(payload.len()).ok_or_else(|| Error::invalid_input::<L, _>(
+        format!("a payload of {} bytes is too long for its length prefix", payload.len())
+    ))?;
+    pack::<O, Tag, W>(w, tag)?;
+    pack::<O, L, W>(w, len)?;
+    write_raw_all(w, payload)
+}
+
+pub(crate) fn pack_tlv_value<O: Order, Tag: Packed, L: LengthPrefix, T: Packed, W: Write + ?Sized>(w: &mut W, tag: Tag, value: &T) -> Result<()> {
+    pack_tlv::<O, Tag, L, W>(w, tag, &to_bytes_vec::<O, T>(value)[..])
+}
+
+pub(crate) fn pack_framed<O: Order, L: LengthPrefix, T: Packed, W: Write + ?Sized>(w: &mut W, buf: &[T]) -> Result<()> {
+    let len = L::from_len(buf.len() * T::SIZE).ok_or_else(|| Error::invalid_input::<L, _>(
+        format!("a frame of {} bytes is too long for its length prefix", buf.len() * T::SIZE)
+    ))?;
+    pack::<O, L, W>(w, len)?;
+    pack_all::<O, T, W>(w, buf)
+}
+
+pub(crate) fn pack_with_checksum<O: Order, U, F: FnOnce(&mut Crc32Writer<&mut W>) -> Result<U>, W: Write + ?Sized>(w: &mut W, f: F) -> Result<U> {
+    let mut writer = Crc32Writer::new(&mut *w);
+    let u = f(&mut writer)?;
+    let crc = writer.digest();
+    pack::<O, u32, W>(w, crc)?;
+    Ok(u)
+}
+
+pub(crate) fn pack_converted<O: Order, S, D: Packed, F: FnMut(&S) -> D, W: Write + ?Sized>(w: &mut W, buf: &[S], convert: F) -> Result<()> {
+    pack_iter::<O, D, _, W>(w, buf.iter().map(convert))
+}
+
+pub(crate) fn pack_field<O: Order, T, U: Packed, F: Fn(&T) -> &U, W: Write + ?Sized>(w: &mut W, buf: &[T], field: F) -> Result<()> {
+    // safe for the same reason as in pack_strided.
+    let values = buf.iter().map(|t| unsafe { ptr::read(field(t)) });
+    pack_iter::<O, U, _, W>(w, values)
+}
+
+pub(crate) fn pack_all_vectored<O: Order, T: Packed, W: Write + ?Sized>(w: &mut W, bufs: &[&[T]]) -> Result<()> {
+    if O::ENDIANNESS.is_native() {
+        write_raw_vectored(w, bufs)
+    }
+    else {
+        for buf in bufs {
+            pack_all::<O, T, W>(w, buf)?;
+        }
+        Ok(())
+    }
+}
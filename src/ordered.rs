@@ -0,0 +1,140 @@
+//! Wrapper types carrying their byte order in their type.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ptr;
+
+use {Packed, EndianSwitch, CDecl, Order, LittleEndian, BigEndian};
+
+/// A value of type `T` stored in the `O` byte order. Because its byte order is part of its type,
+/// an `Ordered` value is always read and written in that byte order, whichever packer or unpacker
+/// trait is used. This allows declaring structures mixing several byte orders.
+///
+/// An `Ordered` value has an alignment of 1, like the bytes it is stored as, so structures made
+/// of them have no padding. It is compared and hashed by its value in the system byte order.
+///
+/// The [`Le`](type.Le.html) and [`Be`](type.Be.html) aliases are usually more convenient.
+///
+/// # Example
+///
+/// ```
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// use std::io::Cursor;
+/// use bytepack::{Be, Le, Packed, Unpacker};
+///
+/// #[derive(Packed)]
+/// struct Header {
+///     id: Be<u32>,
+///     length: Le<u32>,
+/// }
+///
+/// fn main() {
+///     let mut buffer = Cursor::new(vec![0x12, 0x34, 0x56, 0x78, 0x0A, 0x00, 0x00, 0x00]);
+///     let header : Header = buffer.unpack().unwrap();
+///     assert_eq!(header.id.get(), 0x12345678);
+///     assert_eq!(header.length.get(), 10);
+/// }
+/// ```
+#[repr(C, packed)]
+pub struct Ordered<T, O: Order> {
+    raw: T,
+    order: PhantomData<O>
+}
+
+/// A value of type `T` always stored in little endian.
+pub type Le<T> = Ordered<T, LittleEndian>;
+
+/// A value of type `T` always stored in big endian.
+pub type Be<T> = Ordered<T, BigEndian>;
+
+impl<T: Packed, O: Order> Ordered<T, O> {
+    /// Create a new value from `value` in the system byte order.
+    pub fn new(value: T) -> Ordered<T, O> {
+        let mut raw = value;
+        O::convert(&mut raw);
+        Ordered {
+            raw,
+            order: PhantomData
+        }
+    }
+
+    /// Return the value in the system byte order.
+    pub fn get(&self) -> T {
+        // safe because the bitwise copy of a packed value is a valid value and packed values have
+        // no drop glue.
+        let mut value = unsafe { ptr::read_unaligned(ptr::addr_of!(self.raw)) };
+        O::convert(&mut value);
+        value
+    }
+
+    /// Replace the value with `value` in the system byte order.
+    pub fn set(&mut self, value: T) {
+        *self = Ordered::new(value);
+    }
+
+    /// Consume the wrapper and return the value in the system byte order.
+    pub fn into_inner(self) -> T {
+        self.get()
+    }
+}
+
+impl<T: Packed, O: Order> From<T> for Ordered<T, O> {
+    fn from(value: T) -> Ordered<T, O> {
+        Ordered::new(value)
+    }
+}
+
+impl<T: Packed + Default, O: Order> Default for Ordered<T, O> {
+    fn default() -> Ordered<T, O> {
+        Ordered::new(T::default())
+    }
+}
+
+impl<T: Packed, O: Order> Clone for Ordered<T, O> {
+    fn clone(&self) -> Ordered<T, O> {
+        // safe because the bitwise copy of a packed value is a valid value.
+        unsafe { ptr::read(self) }
+    }
+}
+
+impl<T: Packed + Copy, O: Order> Copy for Ordered<T, O> {}
+
+impl<T: Packed + PartialEq, O: Order> PartialEq for Ordered<T, O> {
+    fn eq(&self, other: &Ordered<T, O>) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: Packed + Eq, O: Order> Eq for Ordered<T, O> {}
+
+impl<T: Packed + Hash, O: Order> Hash for Ordered<T, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl<T: Packed + fmt::Debug, O: Order> fmt::Debug for Ordered<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T, O: Order> EndianSwitch for Ordered<T, O> {
+    fn switch_endianness(&mut self) {
+        // The byte order is fixed by the type.
+    }
+}
+
+// Safe because `Ordered` has the layout of a `Packed` value, without its alignment.
+unsafe impl<T: Packed, O: Order> Packed for Ordered<T, O> {}
+
+impl<T: CDecl, O: Order> CDecl for Ordered<T, O> {
+    // The value is rendered as its bytes, which have the same size and alignment in C.
+    fn c_decl(name: &str) -> String {
+        format!("uint8_t {}[{}]", name, T::SIZE)
+    }
+}
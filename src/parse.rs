@@ -0,0 +1,225 @@
+//! Parsing and writing of packed values at the front of byte slices. None of these functions
+//! allocate memory.
+
+use std::cmp::min;
+use std::io::{self, Read};
+use std::ptr;
+
+use {order, switch_endianness_slice, Order, Packed, Endianness, NativeEndian, LittleEndian, BigEndian, Error, Result};
+
+/// Unpack a value of type `T` from the front of `bytes` and return it with the remaining bytes.
+/// A `ShortRead` error is returned if `bytes` is shorter than `T`. Unlike the borrowing
+/// functions, `bytes` does not need to be aligned since the value is copied.
+///
+/// ```
+/// use bytepack::unpack_from_slice;
+///
+/// let packet = [1u8, 0, 0, 0, 42];
+/// let (length, rest) = unpack_from_slice::<u32>(&packet).unwrap();
+/// let (flags, rest) = unpack_from_slice::<u8>(rest).unwrap();
+/// assert_eq!(flags, 42);
+/// assert!(rest.is_empty());
+/// ```
+pub fn unpack_from_slice<T: Packed>(bytes: &[u8]) -> Result<(T, &[u8])> {
+    let mut rest = bytes;
+    let t = order::unpack::<NativeEndian, T, _>(&mut rest)?;
+    Ok((t, rest))
+}
+
+/// Same as [`unpack_from_slice`](fn.unpack_from_slice.html) but the value is stored in the byte
+/// order given by `endianness`.
+///
+/// ```
+/// use bytepack::{unpack_from_slice_with, Endianness};
+///
+/// let (port, rest) = unpack_from_slice_with::<u16>(&[0x1F, 0x90, 0xFF], Endianness::Big).unwrap();
+/// assert_eq!(port, 8080);
+/// assert_eq!(rest, &[0xFF]);
+/// ```
+pub fn unpack_from_slice_with<T: Packed>(bytes: &[u8], endianness: Endianness) -> Result<(T, &[u8])> {
+    let mut rest = bytes;
+    let t = match endianness {
+        Endianness::Little => order::unpack::<LittleEndian, T, _>(&mut rest)?,
+        Endianness::Big => order::unpack::<BigEndian, T, _>(&mut rest)?
+    };
+    Ok((t, rest))
+}
+
+/// Unpack exactly `buf.len()` values of type `T` from the front of `bytes` and return the
+/// remaining bytes. A `ShortRead` error is returned if `bytes` is too short.
+///
+/// ```
+/// use bytepack::unpack_exact_from_slice;
+///
+/// let mut samples = [0u16; 2];
+/// let rest = unpack_exact_from_slice(&[1, 0, 2, 0, 3][..], &mut samples).unwrap();
+/// assert_eq!(rest, &[3]);
+/// ```
+pub fn unpack_exact_from_slice<'a, T: Packed>(bytes: &'a [u8], buf: &mut [T]) -> Result<&'a [u8]> {
+    let mut rest = bytes;
+    order::unpack_exact::<NativeEndian, T, _>(&mut rest, buf)?;
+    Ok(rest)
+}
+
+/// Same as [`unpack_exact_from_slice`](fn.unpack_exact_from_slice.html) but the values are stored
+/// in the byte order given by `endianness`.
+pub fn unpack_exact_from_slice_with<'a, T: Packed>(bytes: &'a [u8], buf: &mut [T], endianness: Endianness) -> Result<&'a [u8]> {
+    let mut rest = bytes;
+    match endianness {
+        Endianness::Little => order::unpack_exact::<LittleEndian, T, _>(&mut rest, buf)?,
+        Endianness::Big => order::unpack_exact::<BigEndian, T, _>(&mut rest, buf)?
+    }
+    Ok(rest)
+}
+
+/// Pack `t` at the front of `bytes` and return the remaining bytes. A `ShortWrite` error is
+/// returned, and nothing is written, if `bytes` is shorter than `T`.
+///
+/// ```
+/// use bytepack::pack_into_slice;
+///
+/// let mut packet = [0u8; 3];
+/// let rest = pack_into_slice(&mut packet, 7u16).unwrap();
+/// pack_into_slice(rest, 1u8).unwrap();
+/// assert_eq!(packet, [7u16.to_ne_bytes()[0], 7u16.to_ne_bytes()[1], 1]);
+/// ```
+pub fn pack_into_slice<T: Packed>(bytes: &mut [u8], t: T) -> Result<&mut [u8]> {
+    pack_all_into::<NativeEndian, T>(bytes, &[t])
+}
+
+/// Same as [`pack_into_slice`](fn.pack_into_slice.html) but `t` is stored in the byte order given
+/// by `endianness`.
+pub fn pack_into_slice_with<T: Packed>(bytes: &mut [u8], t: T, endianness: Endianness) -> Result<&mut [u8]> {
+    match endianness {
+        Endianness::Little => pack_all_into::<LittleEndian, T>(bytes, &[t]),
+        Endianness::Big => pack_all_into::<BigEndian, T>(bytes, &[t])
+    }
+}
+
+/// Pack all the values of `buf` at the front of `bytes` and return the remaining bytes. A
+/// `ShortWrite` error is returned, and nothing is written, if `bytes` is too short.
+///
+/// ```
+/// use bytepack::{pack_all_into_slice_with, Endianness};
+///
+/// let mut packet = [0u8; 4];
+/// pack_all_into_slice_with(&mut packet, &[0x0102u16, 0x0304], Endianness::Big).unwrap();
+/// assert_eq!(packet, [1, 2, 3, 4]);
+/// ```
+pub fn pack_all_into_slice<'a, T: Packed>(bytes: &'a mut [u8], buf: &[T]) -> Result<&'a mut [u8]> {
+    pack_all_into::<NativeEndian, T>(bytes, buf)
+}
+
+/// Same as [`pack_all_into_slice`](fn.pack_all_into_slice.html) but the values are stored in the
+/// byte order given by `endianness`.
+pub fn pack_all_into_slice_with<'a, T: Packed>(bytes: &'a mut [u8], buf: &[T], endianness: Endianness) -> Result<&'a mut [u8]> {
+    match endianness {
+        Endianness::Little => pack_all_into::<LittleEndian, T>(bytes, buf),
+        Endianness::Big => pack_all_into::<BigEndian, T>(bytes, buf)
+    }
+}
+
+fn pack_all_into<'a, O: Order, T: Packed>(bytes: &'a mut [u8], buf: &[T]) -> Result<&'a mut [u8]> {
+    if bytes.len() < buf.len() * T::SIZE {
+        return Err(Error::short_write::<T>(buf.len() * T::SIZE, bytes.len()));
+    }
+    let mut rest = bytes;
+    // values are converted one at a time on the stack so no buffer has to be allocated
+    for t in buf {
+        order::pack_ref::<O, T, _>(&mut rest, t)?;
+    }
+    Ok(rest)
+}
+
+/// An unpacker reading values straight out of an in-memory byte slice, so unpacking a small value
+/// is reduced to a bounds check and a copy instead of going through `io::Read`. It also
+/// implements `Read`, so it can be handed to the unpacker traits for the other methods.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{SliceUnpacker, Endianness};
+///
+/// let mut unpacker = SliceUnpacker::with_endianness(&[0, 0, 0, 42, 0, 1, 0, 2, 9], Endianness::Big);
+/// assert_eq!(unpacker.unpack::<u32>().unwrap(), 42);
+/// let mut pair = [0u16; 2];
+/// unpacker.unpack_exact(&mut pair).unwrap();
+/// assert_eq!(pair, [1, 2]);
+/// assert_eq!(unpacker.position(), 8);
+/// assert!(unpacker.unpack::<u16>().is_err());
+/// assert_eq!(unpacker.remaining(), &[9]);
+/// ```
+pub struct SliceUnpacker<'a> {
+    bytes: &'a [u8],
+    endianness: Endianness,
+    position: usize
+}
+
+impl<'a> SliceUnpacker<'a> {
+    /// Unpack values stored in the native byte order from `bytes`.
+    pub fn new(bytes: &'a [u8]) -> SliceUnpacker<'a> {
+        SliceUnpacker::with_endianness(bytes, Endianness::native())
+    }
+
+    /// Unpack values stored in the `endianness` byte order from `bytes`.
+    pub fn with_endianness(bytes: &'a [u8], endianness: Endianness) -> SliceUnpacker<'a> {
+        SliceUnpacker {
+            bytes,
+            endianness,
+            position: 0
+        }
+    }
+
+    /// Return the number of bytes already unpacked.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the bytes left to unpack.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+
+    /// Unpack a value of type `T`. A `ShortRead` error is returned, and nothing is consumed, if
+    /// not enough bytes remain.
+    #[inline]
+    pub fn unpack<T: Packed>(&mut self) -> Result<T> {
+        let rest = self.remaining();
+        if rest.len() < T::SIZE {
+            return Err(Error::short_read::<T>(T::SIZE, rest.len()).with_offset(self.position as u64));
+        }
+        // safe because rest holds at least T::SIZE bytes, which are read without any
+        // alignment requirement, and the bitwise copy of a packed value is a valid value.
+        let mut t = unsafe { ptr::read_unaligned(rest.as_ptr() as *const T) };
+        if !self.endianness.is_native() {
+            t.switch_endianness();
+        }
+        self.position += T::SIZE;
+        Ok(t)
+    }
+
+    /// Unpack exactly `buf.len()` values of type `T`. A `ShortRead` error is returned, and
+    /// nothing is consumed, if not enough bytes remain.
+    #[inline]
+    pub fn unpack_exact<T: Packed>(&mut self, buf: &mut [T]) -> Result<()> {
+        let (rest, size) = (self.remaining(), buf.len() * T::SIZE);
+        if rest.len() < size {
+            return Err(Error::short_read::<T>(size, rest.len()).with_offset(self.position as u64));
+        }
+        order::raw_bytes_mut(buf).copy_from_slice(&rest[..size]);
+        if !self.endianness.is_native() {
+            switch_endianness_slice(buf);
+        }
+        self.position += size;
+        Ok(())
+    }
+}
+
+impl<'a> Read for SliceUnpacker<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = min(buf.len(), self.remaining().len());
+        buf[..len].copy_from_slice(&self.remaining()[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
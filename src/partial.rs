@@ -0,0 +1,282 @@
+//! State machines packing and unpacking values over several I/O attempts, used by the
+//! asynchronous traits and by [`PartialUnpack`](../struct.PartialUnpack.html) for non-blocking
+//! readers.
+
+use std::io::{self, Read, ErrorKind};
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+use std::task::Poll;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+use std::mem::ManuallyDrop;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+use {order, switch_endianness_slice};
+use {Packed, Endianness, Error, Result};
+
+/// Read into `buf[*filled..]`, the memory of values of type `T`, with `read` until `buf` is full,
+/// `read` returns `Pending` or an error occurs. `filled` keeps track of the progress between
+/// calls.
+pub(crate) fn poll_fill<T, F>(buf: &mut [u8], filled: &mut usize, mut read: F) -> Poll<Result<()>>
+    where F: FnMut(&mut [u8]) -> Poll<io::Result<usize>> {
+    while *filled < buf.len() {
+        match read(&mut buf[*filled..]) {
+            Poll::Ready(Ok(0)) => return Poll::Ready(Err(Error::short_read::<T>(buf.len(), *filled))),
+            Poll::Ready(Ok(n)) => *filled += n,
+            Poll::Ready(Err(ref e)) if e.kind() == ErrorKind::Interrupted => {},
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+            Poll::Pending => return Poll::Pending
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Write `buf[*written..]`, the memory of values of type `T`, with `write` until all of `buf` is
+/// written, `write` returns `Pending` or an error occurs. `written` keeps track of the progress
+/// between calls.
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) fn poll_drain<T, F>(buf: &[u8], written: &mut usize, mut write: F) -> Poll<Result<()>>
+    where F: FnMut(&[u8]) -> Poll<io::Result<usize>> {
+    while *written < buf.len() {
+        match write(&buf[*written..]) {
+            Poll::Ready(Ok(0)) => return Poll::Ready(Err(Error::short_write::<T>(buf.len(), *written))),
+            Poll::Ready(Ok(n)) => *written += n,
+            Poll::Ready(Err(ref e)) if e.kind() == ErrorKind::Interrupted => {},
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+            Poll::Pending => return Poll::Pending
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Progress of the unpacking of a single value.
+pub(crate) struct UnpackState<T> {
+    value: MaybeUninit<T>,
+    filled: usize,
+    endianness: Endianness,
+    done: bool
+}
+
+impl<T: Packed> UnpackState<T> {
+    pub(crate) fn new(endianness: Endianness) -> UnpackState<T> {
+        UnpackState {
+            // zeroed so the value can be viewed as bytes before being read
+            value: MaybeUninit::zeroed(),
+            filled: 0,
+            endianness,
+            done: false
+        }
+    }
+
+    pub(crate) fn filled(&self) -> usize {
+        self.filled
+    }
+
+    pub(crate) fn poll<F>(&mut self, read: F) -> Poll<Result<T>> where F: FnMut(&mut [u8]) -> Poll<io::Result<usize>> {
+        assert!(!self.done, "unpacking polled after completion");
+        // safe because the value is zeroed and we build a slice of exactly T::SIZE bytes
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(self.value.as_mut_ptr() as *mut u8, T::SIZE)
+        };
+        match poll_fill::<T, F>(bytes, &mut self.filled, read) {
+            Poll::Ready(Ok(())) => {
+                self.done = true;
+                // safe because all the bytes of the value have been read and it is read only once
+                let mut t = unsafe { ptr::read(self.value.as_ptr()) };
+                if !self.endianness.is_native() {
+                    t.switch_endianness();
+                }
+                Poll::Ready(Ok(t))
+            },
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+/// Progress of the unpacking of values into a slice.
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) struct UnpackExactState {
+    filled: usize,
+    endianness: Endianness,
+    done: bool
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl UnpackExactState {
+    pub(crate) fn new(endianness: Endianness) -> UnpackExactState {
+        UnpackExactState {
+            filled: 0,
+            endianness,
+            done: false
+        }
+    }
+
+    pub(crate) fn poll<T: Packed, F>(&mut self, buf: &mut [T], read: F) -> Poll<Result<()>> where F: FnMut(&mut [u8]) -> Poll<io::Result<usize>> {
+        assert!(!self.done, "unpacking polled after completion");
+        match poll_fill::<T, F>(order::raw_bytes_mut(buf), &mut self.filled, read) {
+            Poll::Ready(Ok(())) => {
+                self.done = true;
+                if !self.endianness.is_native() {
+                    switch_endianness_slice(buf);
+                }
+                Poll::Ready(Ok(()))
+            },
+            other => other
+        }
+    }
+}
+
+/// Progress of the packing of a single value, stored already converted.
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) struct PackState<T> {
+    value: ManuallyDrop<T>,
+    written: usize
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl<T: Packed> PackState<T> {
+    pub(crate) fn new(t: T, endianness: Endianness) -> PackState<T> {
+        let mut t = t;
+        if !endianness.is_native() {
+            t.switch_endianness();
+        }
+        PackState {
+            // never dropped since the value is not valid in the system byte order
+            value: ManuallyDrop::new(t),
+            written: 0
+        }
+    }
+
+    pub(crate) fn poll<F>(&mut self, write: F) -> Poll<Result<()>> where F: FnMut(&[u8]) -> Poll<io::Result<usize>> {
+        poll_drain::<T, F>(order::raw_bytes(slice::from_ref(&*self.value)), &mut self.written, write)
+    }
+}
+
+/// Progress of the packing of a slice of values. When they need an endianness switch, the
+/// values are converted by chunks into a bounded buffer.
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) struct PackAllState {
+    endianness: Endianness,
+    // number of values converted into chunk
+    converted: usize,
+    chunk: Vec<u8>,
+    written: usize
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl PackAllState {
+    pub(crate) fn new(endianness: Endianness) -> PackAllState {
+        PackAllState {
+            endianness,
+            converted: 0,
+            chunk: Vec::new(),
+            written: 0
+        }
+    }
+
+    pub(crate) fn poll<T: Packed, F>(&mut self, buf: &[T], mut write: F) -> Poll<Result<()>> where F: FnMut(&[u8]) -> Poll<io::Result<usize>> {
+        if self.endianness.is_native() {
+            return poll_drain::<T, F>(order::raw_bytes(buf), &mut self.written, write);
+        }
+        loop {
+            match poll_drain::<T, _>(&self.chunk, &mut self.written, &mut write) {
+                Poll::Ready(Ok(())) => {},
+                other => return other
+            }
+            if self.converted == buf.len() {
+                return Poll::Ready(Ok(()));
+            }
+            self.chunk.clear();
+            self.written = 0;
+            let end = buf.len().min(self.converted + order::chunk_len::<T>());
+            for t in &buf[self.converted..end] {
+                // safe because the bitwise copy of a packed value is a valid value and the copy
+                // is never dropped.
+                let mut copy = ManuallyDrop::new(unsafe { ptr::read(t) });
+                copy.switch_endianness();
+                self.chunk.extend_from_slice(order::raw_bytes(slice::from_ref(&*copy)));
+            }
+            self.converted = end;
+        }
+    }
+}
+
+/// Unpacks a value of type `T` from a non-blocking reader over several attempts. When the reader
+/// returns a `WouldBlock` error in the middle of the value, the bytes already read are kept
+/// until the next attempt instead of being lost like with
+/// [`Unpacker::unpack`](trait.Unpacker.html#tymethod.unpack). This allows readiness based event
+/// loops to use bytepack.
+///
+/// Once a value has been returned, the `PartialUnpack` starts accumulating the next one. After an
+/// error other than `WouldBlock` or `Interrupted`, the stream should be considered corrupted.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::ErrorKind;
+/// use std::net::TcpStream;
+///
+/// use bytepack::{PartialUnpack, Endianness};
+///
+/// let mut stream = TcpStream::connect("127.0.0.1:4242").unwrap();
+/// stream.set_nonblocking(true).unwrap();
+/// let mut length = PartialUnpack::<u32>::with_endianness(Endianness::Big);
+/// loop {
+///     match length.unpack_from(&mut stream) {
+///         Ok(length) => println!("frame of {} bytes", length),
+///         Err(ref e) if e.kind() == ErrorKind::WouldBlock => {
+///             // wait for the socket to be readable
+///         },
+///         Err(e) => panic!("{}", e)
+///     }
+/// }
+/// ```
+pub struct PartialUnpack<T> {
+    state: UnpackState<T>,
+    endianness: Endianness
+}
+
+impl<T: Packed> PartialUnpack<T> {
+    /// Create a new `PartialUnpack` for a value stored in the system byte order.
+    pub fn new() -> PartialUnpack<T> {
+        PartialUnpack::with_endianness(Endianness::native())
+    }
+
+    /// Create a new `PartialUnpack` for a value stored in the byte order given by `endianness`.
+    pub fn with_endianness(endianness: Endianness) -> PartialUnpack<T> {
+        PartialUnpack {
+            state: UnpackState::new(endianness),
+            endianness
+        }
+    }
+
+    /// Read from `r` until the value is complete and return it. If `r` returns a `WouldBlock`
+    /// error first, that error is returned and the bytes read so far are kept for the next call.
+    pub fn unpack_from<R: Read + ?Sized>(&mut self, r: &mut R) -> Result<T> {
+        let result = self.state.poll(|buf| match r.read(buf) {
+            Err(ref e) if e.kind() == ErrorKind::WouldBlock => Poll::Pending,
+            other => Poll::Ready(other)
+        });
+        match result {
+            Poll::Ready(Ok(t)) => {
+                self.state = UnpackState::new(self.endianness);
+                Ok(t)
+            },
+            Poll::Ready(Err(e)) => Err(e),
+            Poll::Pending => Err(io::Error::new(ErrorKind::WouldBlock, "value is incomplete").into())
+        }
+    }
+
+    /// Return the number of bytes of the value read so far.
+    pub fn filled(&self) -> usize {
+        self.state.filled()
+    }
+}
+
+impl<T: Packed> Default for PartialUnpack<T> {
+    fn default() -> PartialUnpack<T> {
+        PartialUnpack::new()
+    }
+}
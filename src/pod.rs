@@ -0,0 +1,125 @@
+//! Interoperability with the `Pod` trait of the `bytemuck` crate.
+
+use bytemuck::{Pod, Zeroable};
+
+use {Packed, EndianSwitch, Order, Ordered};
+
+/// A wrapper making any `bytemuck::Pod` type usable with the packing and unpacking methods. It is
+/// only available with the `bytemuck` feature.
+///
+/// `Pod` says nothing about the byte order of a type, so the wrapped value is always transferred
+/// as is, whatever the trait family used. Use [`Le`](type.Le.html) and [`Be`](type.Be.html)
+/// fields, which implement `Pod`, to give the multi-byte fields of a structure a byte order.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{PodPacked, LEUnpacker};
+/// use bytemuck::{Pod, Zeroable};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Vertex {
+///     position: [f32; 3],
+///     color: [u8; 4]
+/// }
+///
+/// unsafe impl Zeroable for Vertex {}
+/// unsafe impl Pod for Vertex {}
+///
+/// let mut reader = &[0u8; 32][..];
+/// let vertices : Vec<PodPacked<Vertex>> = reader.unpack_n(2).unwrap();
+/// assert_eq!(vertices[1].0.color, [0; 4]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PodPacked<T: Pod>(pub T);
+
+impl<T: Pod> EndianSwitch for PodPacked<T> {
+    fn switch_endianness(&mut self) {
+        // The value is transferred as is.
+    }
+}
+
+// Safe because the contract of `Pod` is stricter than the one of `Packed`.
+unsafe impl<T: Pod> Packed for PodPacked<T> {}
+
+// Safe because `Ordered` has the layout of a `Pod` value, without its alignment.
+unsafe impl<T: Pod + Packed, O: Order + Copy + 'static> Zeroable for Ordered<T, O> {}
+
+// Safe because `Ordered` has the layout of a `Pod` value, without its alignment.
+unsafe impl<T: Pod + Packed, O: Order + Copy + 'static> Pod for Ordered<T, O> {}
+
+#[doc(hidden)]
+pub fn assert_pod<T: Pod>() {}
+
+/// Implement [`Packed`](trait.Packed.html) and [`EndianSwitch`](trait.EndianSwitch.html) for
+/// types already implementing `bytemuck::Pod`, so they go through the packing methods and
+/// `bytemuck` casts without being wrapped. It is only available with the `bytemuck` feature.
+///
+/// Like with [`PodPacked`](struct.PodPacked.html) the values are transferred as is, whatever the
+/// trait family used. The macro fails to compile for a type which does not implement `Pod`.
+///
+/// A structure deriving `Packed` can instead get `Zeroable` and `Pod` from the derive with the
+/// `#[packed(bytemuck)]` option, its multi-byte fields then keeping their byte order switch:
+///
+/// ```edition2018
+/// # #[macro_use] extern crate bytepack_derive;
+/// use bytepack::Be;
+///
+/// #[derive(Packed, Clone, Copy)]
+/// #[packed(bytemuck)]
+/// #[repr(C)]
+/// struct Point {
+///     x: Be<i16>,
+///     y: u16
+/// }
+///
+/// # fn main() {
+/// let points = [Point { x: Be::new(1), y: 2 }];
+/// assert_eq!(bytemuck::cast_slice::<Point, u8>(&points).len(), 4);
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{packed_pod, Be, BEPacker};
+/// use bytemuck::{Pod, Zeroable};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Header {
+///     magic: [u8; 4],
+///     length: Be<u32>
+/// }
+///
+/// unsafe impl Zeroable for Header {}
+/// unsafe impl Pod for Header {}
+///
+/// packed_pod!(Header);
+///
+/// let header = Header { magic: *b"BPK1", length: Be::new(16) };
+/// let mut buffer = Vec::new();
+/// buffer.pack(header).unwrap();
+/// assert_eq!(buffer, bytemuck::bytes_of(&header));
+/// assert_eq!(&buffer[4..], &[0, 0, 0, 16]);
+/// ```
+#[macro_export]
+macro_rules! packed_pod {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            const _: fn() = $crate::assert_pod::<$ty>;
+
+            impl $crate::EndianSwitch for $ty {
+                fn switch_endianness(&mut self) {
+                    // The value is transferred as is.
+                }
+            }
+
+            // Safe because the contract of `Pod`, checked above, is stricter than the one of
+            // `Packed`.
+            unsafe impl $crate::Packed for $ty {}
+        )+
+    };
+}
@@ -0,0 +1,218 @@
+//! Positioned I/O on files, reading and writing at an offset without moving the file cursor so
+//! the same `File` can be shared between threads.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::FileExt;
+#[cfg(windows)]
+use std::os::windows::fs::FileExt;
+
+use {order, Packed, Endianness, NativeEndian, LittleEndian, BigEndian, Result};
+
+/// Size in bytes of the chunks read concurrently by
+/// [`unpack_parallel_at`](trait.UnpackAt.html#tymethod.unpack_parallel_at).
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 4 << 20;
+
+/// `UnpackAt` provides the [`Unpacker`](trait.Unpacker.html) API for files but reads at a given
+/// offset from the start of the file using `pread` (or its Windows equivalent) instead of the file
+/// cursor. Because it only needs a shared reference, a single `File` can be read from several
+/// threads at once.
+///
+/// On Windows, the file cursor is moved by the underlying system call.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::thread;
+///
+/// use bytepack::UnpackAt;
+///
+/// let file = File::open("test").unwrap();
+/// thread::scope(|scope| {
+///     for i in 0..4u64 {
+///         let file = &file;
+///         scope.spawn(move || {
+///             let mut block = [0u32; 256];
+///             file.unpack_exact_at(i * 1024, &mut block).unwrap();
+///         });
+///     }
+/// });
+/// ```
+pub trait UnpackAt {
+    /// Unpack a value of type `T` located `offset` bytes from the start of the file.
+    fn unpack_at<T: Packed>(&self, offset: u64) -> Result<T>;
+
+    /// Unpack exactly `buf.len()` values of type `T` located `offset` bytes from the start of the
+    /// file. An error is returned if not enough bytes could be read.
+    fn unpack_exact_at<T: Packed>(&self, offset: u64, buf: &mut [T]) -> Result<()>;
+
+    /// Same as [`unpack_at`](#tymethod.unpack_at) but in the byte order given by `endianness`.
+    fn unpack_at_with<T: Packed>(&self, offset: u64, endianness: Endianness) -> Result<T>;
+
+    /// Same as [`unpack_exact_at`](#tymethod.unpack_exact_at) but in the byte order given by
+    /// `endianness`.
+    fn unpack_exact_at_with<T: Packed>(&self, offset: u64, buf: &mut [T], endianness: Endianness) -> Result<()>;
+
+    /// Unpack `count` values of type `T` in the byte order given by `endianness`, located
+    /// `offset` bytes from the start of the file. The range is split in chunks which are read
+    /// and converted concurrently on the rayon thread pool, to make the most of fast storage.
+    /// It is only available with the `rayon` feature.
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use bytepack::{UnpackAt, Endianness};
+    ///
+    /// let file = File::open("test").unwrap();
+    /// let records : Vec<u64> = file.unpack_parallel_at(0, 1 << 28, Endianness::Little).unwrap();
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn unpack_parallel_at<T: Packed + Send>(&self, offset: u64, count: usize, endianness: Endianness) -> Result<Vec<T>>;
+}
+
+/// `PackAt` provides the [`Packer`](trait.Packer.html) API for files but writes at a given offset
+/// from the start of the file using `pwrite` (or its Windows equivalent) instead of the file
+/// cursor. Because it only needs a shared reference, a single `File` can be written from several
+/// threads at once.
+///
+/// On Windows, the file cursor is moved by the underlying system call.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use bytepack::PackAt;
+///
+/// let file = File::create("test").unwrap();
+/// file.pack_all_at(8, &[1u32, 2, 3, 4]).unwrap();
+/// file.pack_at(0, 16u64).unwrap();
+/// ```
+pub trait PackAt {
+    /// Pack `t` at `offset` bytes from the start of the file.
+    fn pack_at<T: Packed>(&self, offset: u64, t: T) -> Result<()>;
+
+    /// Pack all the values of `buf` starting `offset` bytes from the start of the file.
+    fn pack_all_at<T: Packed>(&self, offset: u64, buf: &[T]) -> Result<()>;
+
+    /// Same as [`pack_at`](#tymethod.pack_at) but in the byte order given by `endianness`.
+    fn pack_at_with<T: Packed>(&self, offset: u64, t: T, endianness: Endianness) -> Result<()>;
+
+    /// Same as [`pack_all_at`](#tymethod.pack_all_at) but in the byte order given by
+    /// `endianness`.
+    fn pack_all_at_with<T: Packed>(&self, offset: u64, buf: &[T], endianness: Endianness) -> Result<()>;
+}
+
+/// Adapter implementing `Read` and `Write` with positioned I/O, so the generic implementations of
+/// the unpacker and packer traits can be reused.
+struct FileAt<'a> {
+    file: &'a File,
+    offset: u64
+}
+
+impl<'a> FileAt<'a> {
+    fn new(file: &'a File, offset: u64) -> FileAt<'a> {
+        FileAt {
+            file,
+            offset
+        }
+    }
+}
+
+impl<'a> Read for FileAt<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        #[cfg(unix)]
+        let size = self.file.read_at(buf, self.offset)?;
+        #[cfg(windows)]
+        let size = self.file.seek_read(buf, self.offset)?;
+        self.offset += size as u64;
+        Ok(size)
+    }
+}
+
+impl<'a> Write for FileAt<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(unix)]
+        let size = self.file.write_at(buf, self.offset)?;
+        #[cfg(windows)]
+        let size = self.file.seek_write(buf, self.offset)?;
+        self.offset += size as u64;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl UnpackAt for File {
+    fn unpack_at<T: Packed>(&self, offset: u64) -> Result<T> {
+        order::unpack::<NativeEndian, T, _>(&mut FileAt::new(self, offset)).map_err(|e| e.with_offset(offset))
+    }
+
+    fn unpack_exact_at<T: Packed>(&self, offset: u64, buf: &mut [T]) -> Result<()> {
+        order::unpack_exact::<NativeEndian, T, _>(&mut FileAt::new(self, offset), buf).map_err(|e| e.with_offset(offset))
+    }
+
+    fn unpack_at_with<T: Packed>(&self, offset: u64, endianness: Endianness) -> Result<T> {
+        match endianness {
+            Endianness::Little => order::unpack::<LittleEndian, T, _>(&mut FileAt::new(self, offset)),
+            Endianness::Big => order::unpack::<BigEndian, T, _>(&mut FileAt::new(self, offset))
+        }.map_err(|e| e.with_offset(offset))
+    }
+
+    fn unpack_exact_at_with<T: Packed>(&self, offset: u64, buf: &mut [T], endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::unpack_exact::<LittleEndian, T, _>(&mut FileAt::new(self, offset), buf),
+            Endianness::Big => order::unpack_exact::<BigEndian, T, _>(&mut FileAt::new(self, offset), buf)
+        }.map_err(|e| e.with_offset(offset))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn unpack_parallel_at<T: Packed + Send>(&self, offset: u64, count: usize, endianness: Endianness) -> Result<Vec<T>> {
+        use rayon::prelude::*;
+
+        let mut buf = order::with_capacity::<T>(count)?;
+        let chunk_len = (PARALLEL_CHUNK_SIZE / T::SIZE.max(1)).max(1);
+        buf.spare_capacity_mut()[..count].par_chunks_mut(chunk_len).enumerate().try_for_each(|(i, chunk)| {
+            let offset = offset + (i * chunk_len * T::SIZE) as u64;
+            let mut file = FileAt::new(self, offset);
+            match endianness {
+                Endianness::Little => order::unpack_exact_uninit::<LittleEndian, T, _>(&mut file, chunk),
+                Endianness::Big => order::unpack_exact_uninit::<BigEndian, T, _>(&mut file, chunk)
+            }.map(|_| ()).map_err(|e| e.with_offset(offset))
+        })?;
+        // safe because all the chunks of the first count values have been read
+        unsafe {
+            buf.set_len(count);
+        }
+        Ok(buf)
+    }
+}
+
+impl PackAt for File {
+    fn pack_at<T: Packed>(&self, offset: u64, t: T) -> Result<()> {
+        order::pack::<NativeEndian, T, _>(&mut FileAt::new(self, offset), t)
+    }
+
+    fn pack_all_at<T: Packed>(&self, offset: u64, buf: &[T]) -> Result<()> {
+        order::pack_all::<NativeEndian, T, _>(&mut FileAt::new(self, offset), buf)
+    }
+
+    fn pack_at_with<T: Packed>(&self, offset: u64, t: T, endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::pack::<LittleEndian, T, _>(&mut FileAt::new(self, offset), t),
+            Endianness::Big => order::pack::<BigEndian, T, _>(&mut FileAt::new(self, offset), t)
+        }
+    }
+
+    fn pack_all_at_with<T: Packed>(&self, offset: u64, buf: &[T], endianness: Endianness) -> Result<()> {
+        match endianness {
+            Endianness::Little => order::pack_all::<LittleEndian, T, _>(&mut FileAt::new(self, offset), buf),
+            Endianness::Big => order::pack_all::<BigEndian, T, _>(&mut FileAt::new(self, offset), buf)
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Reader and writer wrapper reporting the progress of long operations.
+
+use std::convert::TryFrom;
+use std::io::{self, Read, Write, Seek, SeekFrom};
+
+/// A reader or writer wrapper calling a function every `interval` bytes with the number of bytes
+/// processed so far and the total number of bytes expected, if known. It allows a long
+/// `unpack_exact` or `pack_all` to drive a progress bar or a log.
+///
+/// The reads and writes going through the wrapper are split in pieces of at most `interval`
+/// bytes, so the function is called regularly even when a single method transfers a huge buffer.
+/// The function is also called for the bytes processed since its last call when `EOF` is reached
+/// or the writer is flushed.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Progress, Unpacker};
+///
+/// let data = vec![0u8; 64 * 1024];
+/// let mut steps = Vec::new();
+/// {
+///     let mut reader = Progress::new(&data[..], 16 * 1024, |done, total| steps.push((done, total)))
+///         .with_total(data.len() as u64);
+///     let mut samples = vec![0f32; 16 * 1024];
+///     reader.unpack_exact(&mut samples[..]).unwrap();
+/// }
+/// assert_eq!(steps.len(), 4);
+/// assert_eq!(steps[3], (65536, Some(65536)));
+/// ```
+pub struct Progress<T, F> {
+    inner: T,
+    interval: u64,
+    callback: F,
+    processed: u64,
+    reported: u64,
+    total: Option<u64>
+}
+
+impl<T, F: FnMut(u64, Option<u64>)> Progress<T, F> {
+    /// Wrap `inner`, calling `callback` every `interval` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn new(inner: T, interval: u64, callback: F) -> Progress<T, F> {
+        assert!(interval != 0, "the progress interval cannot be zero");
+        Progress {
+            inner,
+            interval,
+            callback,
+            processed: 0,
+            reported: 0,
+            total: None
+        }
+    }
+
+    /// Set the total number of bytes expected, given to the callback.
+    pub fn with_total(self, total: u64) -> Progress<T, F> {
+        let mut progress = self;
+        progress.total = Some(total);
+        progress
+    }
+
+    /// Return the number of bytes processed so far.
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Return a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader or writer. Bytes read or written through
+    /// it are not reported.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Return the size of the next piece, so it ends on the next reporting point.
+    fn piece(&self, len: usize) -> usize {
+        let remaining = self.interval - (self.processed - self.reported);
+        usize::try_from(remaining).map_or(len, |remaining| len.min(remaining))
+    }
+
+    fn advance(&mut self, size: usize) {
+        self.processed += size as u64;
+        if self.processed - self.reported >= self.interval {
+            self.report();
+        }
+    }
+
+    fn report(&mut self) {
+        if self.processed != self.reported {
+            self.reported = self.processed;
+            (self.callback)(self.processed, self.total);
+        }
+    }
+}
+
+impl<R: Read, F: FnMut(u64, Option<u64>)> Read for Progress<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let piece = self.piece(buf.len());
+        let size = self.inner.read(&mut buf[..piece])?;
+        if size == 0 && piece != 0 {
+            self.report();
+        }
+        self.advance(size);
+        Ok(size)
+    }
+}
+
+impl<W: Write, F: FnMut(u64, Option<u64>)> Write for Progress<W, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let piece = self.piece(buf.len());
+        let size = self.inner.write(&buf[..piece])?;
+        self.advance(size);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        self.report();
+        Ok(())
+    }
+}
+
+impl<S: Seek, F: FnMut(u64, Option<u64>)> Seek for Progress<S, F> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -0,0 +1,30 @@
+//! Generation of packed values for property testing with the `proptest` crate.
+
+use std::fmt::Debug;
+
+use proptest::prelude::*;
+
+use {order, PackedValidate, NativeEndian};
+
+/// Return a `proptest` strategy generating values of type `T` from random bytes, only keeping
+/// the values accepted by [`PackedValidate`](trait.PackedValidate.html), so any packed type can
+/// be used in property tests without writing an `Arbitrary` implementation. It is only available
+/// with the `proptest` feature.
+///
+/// ```edition2018
+/// use proptest::prelude::*;
+/// use bytepack::{any_packed, roundtrip};
+///
+/// proptest! {
+///     fn samples_roundtrip(sample in any_packed::<[i16; 4]>()) {
+///         roundtrip(&sample);
+///     }
+/// }
+///
+/// samples_roundtrip();
+/// ```
+pub fn any_packed<T: PackedValidate + Debug>() -> impl Strategy<Value = T> {
+    proptest::collection::vec(any::<u8>(), T::SIZE).prop_filter_map("invalid packed value", |bytes| {
+        order::from_bytes_slice::<NativeEndian, T>(&bytes).ok().filter(|t| t.validate().is_ok())
+    })
+}
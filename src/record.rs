@@ -0,0 +1,118 @@
+//! Random access to files made of fixed-size records.
+
+use std::io::{Read, Write, Seek, SeekFrom};
+use std::marker::PhantomData;
+
+use {order, Packed, Order, NativeEndian, Error, Result};
+
+/// A file made of consecutive records of type `T` stored in the `O` byte order, accessed by
+/// index. `RecordFile` works on top of any `Read + Write + Seek` type, typically a `File`, and
+/// takes care of the offset arithmetic.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::OpenOptions;
+///
+/// use bytepack::{RecordFile, LittleEndian};
+///
+/// let file = OpenOptions::new().read(true).write(true).create(true).open("records").unwrap();
+/// let mut records = RecordFile::<[u32; 4], _, LittleEndian>::new(file).unwrap();
+/// let index = records.append([1, 2, 3, 4]).unwrap();
+/// let mut record = records.get(index).unwrap();
+/// record[0] = 10;
+/// records.set(index, record).unwrap();
+/// ```
+pub struct RecordFile<T: Packed, F, O: Order = NativeEndian> {
+    inner: F,
+    len: u64,
+    marker: PhantomData<(T, O)>
+}
+
+impl<T: Packed, F: Read + Write + Seek, O: Order> RecordFile<T, F, O> {
+    /// Create a new `RecordFile` from `inner`. An error is returned if the size of `inner` is not
+    /// a multiple of the size of `T`.
+    pub fn new(inner: F) -> Result<RecordFile<T, F, O>> {
+        let mut inner = inner;
+        if T::SIZE == 0 {
+            return Err(Error::invalid_input::<T, _>("records cannot be zero-sized"));
+        }
+        let size = inner.seek(SeekFrom::End(0))?;
+        if size % T::SIZE as u64 != 0 {
+            return Err(Error::trailing_bytes::<T>((size % T::SIZE as u64) as usize).with_offset(0));
+        }
+        Ok(RecordFile {
+            inner,
+            len: size / T::SIZE as u64,
+            marker: PhantomData
+        })
+    }
+
+    /// Return the number of records.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Return `true` if there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the record at `index`. An error is returned if `index` is out of bounds.
+    pub fn get(&mut self, index: u64) -> Result<T> {
+        self.seek_to(index, 1)?;
+        order::unpack::<O, T, F>(&mut self.inner)
+    }
+
+    /// Read the `buf.len()` records starting at `index` into `buf`. An error is returned if some
+    /// of them are out of bounds.
+    pub fn get_range(&mut self, index: u64, buf: &mut [T]) -> Result<()> {
+        self.seek_to(index, buf.len() as u64)?;
+        order::unpack_exact::<O, T, F>(&mut self.inner, buf)
+    }
+
+    /// Replace the record at `index` with `value`. An error is returned if `index` is out of
+    /// bounds.
+    pub fn set(&mut self, index: u64, value: T) -> Result<()> {
+        self.seek_to(index, 1)?;
+        order::pack::<O, T, F>(&mut self.inner, value)
+    }
+
+    /// Replace the `buf.len()` records starting at `index` with the values of `buf`. An error is
+    /// returned if some of them are out of bounds.
+    pub fn set_range(&mut self, index: u64, buf: &[T]) -> Result<()> {
+        self.seek_to(index, buf.len() as u64)?;
+        order::pack_all::<O, T, F>(&mut self.inner, buf)
+    }
+
+    /// Add `value` after the last record and return its index.
+    pub fn append(&mut self, value: T) -> Result<u64> {
+        self.inner.seek(SeekFrom::Start(self.len * T::SIZE as u64))?;
+        order::pack::<O, T, F>(&mut self.inner, value)?;
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    /// Return a reference to the underlying file.
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    /// Consume the `RecordFile` and return the underlying file.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+
+    /// Position the underlying file on the record at `index`, checking that `count` records
+    /// starting there exist.
+    fn seek_to(&mut self, index: u64, count: u64) -> Result<()> {
+        match index.checked_add(count) {
+            Some(end) if end <= self.len => {},
+            _ => return Err(Error::invalid_input::<T, _>(
+                format!("records {} to {} are out of bounds ({} records)", index, index.saturating_add(count), self.len)
+            ))
+        }
+        self.inner.seek(SeekFrom::Start(index * T::SIZE as u64))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Reusable buffer for the methods converting values through a temporary buffer.
+
+use std::alloc::{self, Layout};
+use std::cmp::max;
+use std::mem::{align_of, size_of, MaybeUninit};
+use std::ptr::{self, NonNull};
+use std::slice;
+
+/// A buffer which can be passed to the `*_with_scratch` methods of the packer and unpacker traits
+/// instead of letting them allocate a temporary buffer on every call, so long-running services can
+/// keep packing and unpacking values without any allocation in steady state. The buffer grows as
+/// needed and is suitably aligned for any type.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{ScratchBuffer, BEPacker};
+///
+/// let mut scratch = ScratchBuffer::new();
+/// let mut buffer = Vec::new();
+/// for i in 0..10u32 {
+///     buffer.clear();
+///     buffer.pack_all_with_scratch(&[i; 100][..], &mut scratch).unwrap();
+/// }
+/// assert!(scratch.capacity() >= 400);
+/// ```
+pub struct ScratchBuffer {
+    ptr: NonNull<u8>,
+    layout: Layout
+}
+
+// Safe because the buffer owns its memory and only hands out borrows of it.
+unsafe impl Send for ScratchBuffer {}
+unsafe impl Sync for ScratchBuffer {}
+
+impl ScratchBuffer {
+    /// Create an empty buffer, which does not allocate until it is first used.
+    pub fn new() -> ScratchBuffer {
+        ScratchBuffer::with_capacity(0)
+    }
+
+    /// Create a buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> ScratchBuffer {
+        let mut scratch = ScratchBuffer {
+            ptr: NonNull::dangling(),
+            layout: Layout::new::<()>()
+        };
+        scratch.reserve(capacity, 1);
+        scratch
+    }
+
+    /// Return the size of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Grow the buffer so it is at least `size` bytes long and aligned on `align` bytes.
+    fn reserve(&mut self, size: usize, align: usize) {
+        if size <= self.layout.size() && align <= self.layout.align() {
+            return;
+        }
+        let layout = Layout::from_size_align(max(size, self.layout.size()), max(align, self.layout.align()))
+            .expect("the scratch buffer size overflows the address space");
+        self.release();
+        self.ptr = if layout.size() == 0 {
+            // a well aligned dangling pointer, never dereferenced
+            NonNull::new(ptr::without_provenance_mut(layout.align())).unwrap()
+        }
+        else {
+            // safe because the layout has a non-zero size
+            NonNull::new(unsafe { alloc::alloc(layout) }).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+        };
+        self.layout = layout;
+    }
+
+    fn release(&mut self) {
+        if self.layout.size() != 0 {
+            // safe because the memory was allocated with this layout
+            unsafe {
+                alloc::dealloc(self.ptr.as_ptr(), self.layout);
+            }
+            self.layout = Layout::new::<()>();
+        }
+    }
+
+    /// Return the buffer as `len` uninitialized values of type `T`, growing it if needed.
+    pub(crate) fn chunk<T>(&mut self, len: usize) -> &mut [MaybeUninit<T>] {
+        let size = len.checked_mul(size_of::<T>()).expect("the scratch buffer size overflows the address space");
+        self.reserve(size, align_of::<T>());
+        // safe because the buffer is at least len * size_of::<T> bytes long, is aligned for T and
+        // is borrowed mutably as long as the slice
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr() as *mut MaybeUninit<T>, len) }
+    }
+}
+
+impl Default for ScratchBuffer {
+    fn default() -> ScratchBuffer {
+        ScratchBuffer::new()
+    }
+}
+
+impl Drop for ScratchBuffer {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
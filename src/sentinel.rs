@@ -0,0 +1,11 @@
+//! Handling of the values terminating arrays.
+
+/// Whether [`unpack_until`](trait.Unpacker.html#tymethod.unpack_until) appends the sentinel
+/// terminating the values to the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sentinel {
+    /// Append the sentinel after the values.
+    Include,
+    /// Only append the values, the sentinel is consumed but dropped.
+    Exclude
+}
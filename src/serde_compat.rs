@@ -0,0 +1,603 @@
+//! Serde serializer and deserializer writing and reading a fixed binary layout.
+
+use std::any::type_name;
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use serde::{ser, de, Serialize};
+use serde::de::{DeserializeOwned, DeserializeSeed, Visitor, IntoDeserializer};
+
+use {Packer, Unpacker, Endianness, Error, Result};
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Error {
+        Error::InvalidInput {
+            type_name: "",
+            reason: msg.to_string()
+        }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Error {
+        Error::InvalidValue {
+            type_name: "",
+            reason: msg.to_string(),
+            offset: None
+        }
+    }
+}
+
+/// Give the errors raised through serde, which do not know the type involved, the name of `T`.
+fn name_errors<T: ?Sized>(e: Error) -> Error {
+    match e {
+        Error::InvalidInput { type_name: "", reason } => Error::InvalidInput {
+            type_name: type_name::<T>(),
+            reason
+        },
+        Error::InvalidValue { type_name: "", reason, offset } => Error::InvalidValue {
+            type_name: type_name::<T>(),
+            reason,
+            offset
+        },
+        e => e
+    }
+}
+
+/// A serde `Serializer` writing values in a fixed binary layout, using the same representation as
+/// the packing methods for the primitive types. It is only available with the `serde` feature.
+///
+/// The layout is the following, every value being written in the byte order given to
+/// [`new`](#method.new):
+///
+/// * `bool` is a byte holding 0 or 1, `char` a `u32`, the other primitive types are packed as
+///   is.
+/// * structures, tuples and arrays are their fields one after the other, without padding, which is
+///   the layout of a `#[repr(packed)]` structure of the same fields.
+/// * unit values and unit structures are empty, newtype structures are their field.
+/// * `Option` is a byte holding 0 for `None` or 1 for `Some` followed by the value.
+/// * enums are the index of the variant as a `u32` followed by its fields.
+/// * strings, byte arrays, sequences and maps are their length as a `u32` followed by their
+///   elements.
+///
+/// The layout is not self-describing: the same Rust types are needed to read the data back with
+/// a [`PackedDeserializer`](struct.PackedDeserializer.html).
+pub struct PackedSerializer<W> {
+    writer: W,
+    endianness: Endianness
+}
+
+impl<W: Write> PackedSerializer<W> {
+    /// Create a serializer writing to `writer` in the byte order given by `endianness`.
+    pub fn new(writer: W, endianness: Endianness) -> PackedSerializer<W> {
+        PackedSerializer {
+            writer,
+            endianness
+        }
+    }
+
+    /// Consume the serializer and return the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_len(&mut self, len: Option<usize>) -> Result<()> {
+        let len = len.ok_or_else(|| <Error as ser::Error>::custom("lengths must be known in advance"))?;
+        let len = u32::try_from(len).map_err(|_| <Error as ser::Error>::custom("the length does not fit in a u32"))?;
+        self.writer.pack_with(len, self.endianness)
+    }
+}
+
+/// Serialize `value` to `writer` in the layout described in
+/// [`PackedSerializer`](struct.PackedSerializer.html). It is only available with the `serde`
+/// feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{serialize_into, deserialize_from, Endianness};
+///
+/// let record = (42u16, String::from("bytepack"), vec![1.5f32, 2.5]);
+/// let mut buffer = Vec::new();
+/// serialize_into(&mut buffer, &record, Endianness::Big).unwrap();
+/// assert_eq!(&buffer[..6], &[0, 42, 0, 0, 0, 8]);
+/// let copy : (u16, String, Vec<f32>) = deserialize_from(&buffer[..], Endianness::Big).unwrap();
+/// assert_eq!(copy, record);
+/// ```
+pub fn serialize_into<W: Write, T: Serialize + ?Sized>(writer: W, value: &T, endianness: Endianness) -> Result<()> {
+    let mut serializer = PackedSerializer::new(writer, endianness);
+    value.serialize(&mut serializer).map_err(name_errors::<T>)
+}
+
+/// A serde `Deserializer` reading values in the layout written by
+/// [`PackedSerializer`](struct.PackedSerializer.html). It is only available with the `serde`
+/// feature.
+///
+/// Since the layout is not self-describing, `deserialize_any` and `deserialize_ignored_any` are
+/// not supported.
+pub struct PackedDeserializer<R> {
+    reader: R,
+    endianness: Endianness
+}
+
+impl<R: Read> PackedDeserializer<R> {
+    /// Create a deserializer reading from `reader` in the byte order given by `endianness`.
+    pub fn new(reader: R, endianness: Endianness) -> PackedDeserializer<R> {
+        PackedDeserializer {
+            reader,
+            endianness
+        }
+    }
+
+    /// Consume the deserializer and return the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let len : u32 = self.reader.unpack_with(self.endianness)?;
+        Ok(len as usize)
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_len()?;
+        // read through take so a corrupted length cannot allocate more than the input holds
+        let mut bytes = Vec::new();
+        let size = (&mut self.reader).take(len as u64).read_to_end(&mut bytes)?;
+        if size != len {
+            return Err(Error::short_read::<[u8]>(len, size));
+        }
+        Ok(bytes)
+    }
+
+    fn read_tag<T: ?Sized>(&mut self) -> Result<bool> {
+        match self.reader.unpack::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(Error::InvalidValue {
+                type_name: type_name::<T>(),
+                reason: format!("{} is neither 0 nor 1", tag),
+                offset: None
+            })
+        }
+    }
+}
+
+/// Deserialize a value of type `T` from `reader`, in the layout described in
+/// [`PackedSerializer`](struct.PackedSerializer.html). It is only available with the `serde`
+/// feature.
+pub fn deserialize_from<R: Read, T: DeserializeOwned>(reader: R, endianness: Endianness) -> Result<T> {
+    let mut deserializer = PackedDeserializer::new(reader, endianness);
+    T::deserialize(&mut deserializer).map_err(name_errors::<T>)
+}
+
+macro_rules! serialize_packed {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<()> {
+                self.writer.pack_with(v, self.endianness)
+            }
+        )*
+    };
+}
+
+macro_rules! serialize_wide {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<()> {
+                let bytes = match self.endianness {
+                    Endianness::Little => v.to_le_bytes(),
+                    Endianness::Big => v.to_be_bytes()
+                };
+                self.writer.pack_all(&bytes[..])
+            }
+        )*
+    };
+}
+
+impl<W: Write> ser::Serializer for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    serialize_packed!(serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64);
+    serialize_packed!(serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64);
+    serialize_packed!(serialize_f32: f32, serialize_f64: f64);
+    serialize_wide!(serialize_i128: i128, serialize_u128: u128);
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.writer.pack(v as u8)
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.writer.pack_with(v as u32, self.endianness)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.write_len(Some(v.len()))?;
+        self.writer.pack_all(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.writer.pack(0u8)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        self.writer.pack(1u8)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<()> {
+        self.writer.pack_with(variant_index, self.endianness)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, variant_index: u32, _variant: &'static str, value: &T) -> Result<()> {
+        self.writer.pack_with(variant_index, self.endianness)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self> {
+        self.writer.pack_with(variant_index, self.endianness)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self> {
+        self.writer.pack_with(variant_index, self.endianness)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<W: Write> ser::SerializeSeq for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTuple for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTupleStruct for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTupleVariant for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeMap for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for &mut PackedSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+macro_rules! deserialize_packed {
+    ($($method:ident: $ty:ty => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                let v : $ty = self.reader.unpack_with(self.endianness)?;
+                visitor.$visit(v)
+            }
+        )*
+    };
+}
+
+macro_rules! deserialize_wide {
+    ($($method:ident: $ty:ty => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                let mut bytes = [0u8; 16];
+                self.reader.unpack_exact(&mut bytes)?;
+                visitor.$visit(match self.endianness {
+                    Endianness::Little => <$ty>::from_le_bytes(bytes),
+                    Endianness::Big => <$ty>::from_be_bytes(bytes)
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, R: Read> de::Deserializer<'de> for &mut PackedDeserializer<R> {
+    type Error = Error;
+
+    deserialize_packed!(deserialize_i8: i8 => visit_i8, deserialize_i16: i16 => visit_i16,
+                        deserialize_i32: i32 => visit_i32, deserialize_i64: i64 => visit_i64);
+    deserialize_packed!(deserialize_u8: u8 => visit_u8, deserialize_u16: u16 => visit_u16,
+                        deserialize_u32: u32 => visit_u32, deserialize_u64: u64 => visit_u64);
+    deserialize_packed!(deserialize_f32: f32 => visit_f32, deserialize_f64: f64 => visit_f64);
+    deserialize_wide!(deserialize_i128: i128 => visit_i128, deserialize_u128: u128 => visit_u128);
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(<Error as ser::Error>::custom("the packed layout is not self-describing"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v = self.read_tag::<bool>()?;
+        visitor.visit_bool(v)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let v : u32 = self.reader.unpack_with(self.endianness)?;
+        let c = char::from_u32(v).ok_or_else(|| Error::InvalidValue {
+            type_name: "char",
+            reason: format!("{:#x} is not a unicode scalar value", v),
+            offset: None
+        })?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes()?;
+        let s = String::from_utf8(bytes).map_err(|e| Error::InvalidValue {
+            type_name: "String",
+            reason: e.to_string(),
+            offset: None
+        })?;
+        visitor.visit_string(s)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes()?;
+        visitor.visit_byte_buf(bytes)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.read_tag::<Option<V::Value>>()? {
+            visitor.visit_some(self)
+        }
+        else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_seq(Elements { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Elements { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Elements { de: self, remaining: len })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_map(Elements { de: self, remaining: len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Elements { de: self, remaining: fields.len() })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(<Error as ser::Error>::custom("the packed layout is not self-describing"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Access to the elements of a sequence, tuple, structure or map.
+struct Elements<'a, R> {
+    de: &'a mut PackedDeserializer<R>,
+    remaining: usize
+}
+
+impl<'de, R: Read> de::SeqAccess<'de> for Elements<'_, R> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, R: Read> de::MapAccess<'de> for Elements<'_, R> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, R: Read> de::EnumAccess<'de> for &mut PackedDeserializer<R> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let index : u32 = self.reader.unpack_with(self.endianness)?;
+        let value = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, R: Read> de::VariantAccess<'de> for &mut PackedDeserializer<R> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
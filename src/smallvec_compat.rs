@@ -0,0 +1,110 @@
+//! Bulk unpacking into a `SmallVec` of the `smallvec` crate.
+
+use std::io::Read;
+use std::mem::MaybeUninit;
+use std::slice;
+
+use smallvec::{Array, SmallVec};
+
+use {order, switch_endianness_slice, Packed, Endianness, Error, Result};
+
+/// Unpacking methods filling a `smallvec::SmallVec`, whose values are stored inline up to the
+/// capacity of its array, so records which are usually tiny can be read without any heap
+/// allocation. It is implemented for all the types implementing `Read` and only available with
+/// the `smallvec` feature.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{SmallVecUnpacker, Endianness};
+/// use smallvec::SmallVec;
+///
+/// let mut data: &[u8] = &[0, 1, 0, 2, 0, 3];
+/// let values: SmallVec<[u16; 16]> = data.unpack_n_small(3, Endianness::Big).unwrap();
+/// assert_eq!(&values[..], &[1, 2, 3]);
+/// assert!(!values.spilled());
+/// ```
+pub trait SmallVecUnpacker {
+    /// Same as [`Unpacker::unpack_to_end_with`](trait.Unpacker.html#tymethod.unpack_to_end_with)
+    /// but appending the values to a `SmallVec`, which only spills to the heap once its inline
+    /// capacity is exhausted.
+    fn unpack_to_end_small<A: Array>(&mut self, buf: &mut SmallVec<A>, endianness: Endianness) -> Result<usize> where A::Item: Packed;
+    /// Same as [`Unpacker::unpack_n`](trait.Unpacker.html#tymethod.unpack_n) for values stored
+    /// in the `endianness` byte order but returning a `SmallVec`, which is only allocated on the
+    /// heap if `count` exceeds its inline capacity.
+    fn unpack_n_small<A: Array>(&mut self, count: usize, endianness: Endianness) -> Result<SmallVec<A>> where A::Item: Packed;
+}
+
+impl<R: Read> SmallVecUnpacker for R {
+    fn unpack_to_end_small<A: Array>(&mut self, buf: &mut SmallVec<A>, endianness: Endianness) -> Result<usize> where A::Item: Packed {
+        if <A::Item as Packed>::SIZE == 0 {
+            return Err(Error::invalid_input::<A::Item, _>("cannot read zero-sized values to the end"));
+        }
+        let start = buf.len();
+        loop {
+            let len = buf.len();
+            let spare = buf.capacity() - len;
+            // once the inline capacity is exhausted, only grow if there is another value so a
+            // SmallVec filled exactly does not spill
+            let (read, expected) = if spare == 0 {
+                let mut value = MaybeUninit::<A::Item>::zeroed();
+                // safe because the bytes of value are zeroed and it is only considered
+                // initialized once it has been entirely read.
+                let read = order::fill(self, unsafe { slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, <A::Item as Packed>::SIZE) });
+                if matches!(read, Ok(read) if read == <A::Item as Packed>::SIZE) {
+                    buf.push(unsafe { value.assume_init() });
+                }
+                (read, <A::Item as Packed>::SIZE)
+            }
+            else {
+                // safe because the spare capacity is zeroed before building a slice of exactly
+                // its size in bytes and the values are only considered initialized once they
+                // have been entirely read.
+                unsafe {
+                    let values = buf.as_mut_ptr().add(len) as *mut MaybeUninit<A::Item>;
+                    values.write_bytes(0, spare);
+                    let bytes = slice::from_raw_parts_mut(values as *mut u8, spare * <A::Item as Packed>::SIZE);
+                    let read = order::fill(self, bytes);
+                    if let Ok(read) = read {
+                        buf.set_len(len + read / <A::Item as Packed>::SIZE);
+                    }
+                    (read, bytes.len())
+                }
+            };
+            let read = match read {
+                Ok(read) => read,
+                Err(e) => {
+                    buf.truncate(start);
+                    return Err(e.into());
+                }
+            };
+            let partial = read % <A::Item as Packed>::SIZE;
+            if partial != 0 {
+                buf.truncate(start);
+                return Err(Error::trailing_bytes::<A::Item>(partial));
+            }
+            if read < expected {
+                break;
+            }
+        }
+        if !endianness.is_native() {
+            switch_endianness_slice(&mut buf[start..]);
+        }
+        Ok(buf.len() - start)
+    }
+
+    fn unpack_n_small<A: Array>(&mut self, count: usize, endianness: Endianness) -> Result<SmallVec<A>> where A::Item: Packed {
+        let mut buf = SmallVec::<A>::with_capacity(count);
+        // safe because the SmallVec has room for count values, which are only considered
+        // initialized once they have been read.
+        unsafe {
+            let values = slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut MaybeUninit<A::Item>, count);
+            order::read_raw_uninit(self, values)?;
+            buf.set_len(count);
+        }
+        if !endianness.is_native() {
+            switch_endianness_slice(&mut buf[..]);
+        }
+        Ok(buf)
+    }
+}
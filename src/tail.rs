@@ -0,0 +1,55 @@
+//! Records ending with a variable-length array, like C structures with a flexible array member.
+
+use Packed;
+
+/// A fixed-size header giving the number of values of the array following it, like the `count`
+/// of a C structure ending with a `data[]` flexible array member.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Packed, EndianSwitch, TailCount, LEUnpacker};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Header {
+///     kind: u16,
+///     count: u16
+/// }
+///
+/// impl EndianSwitch for Header {
+///     fn switch_endianness(&mut self) {
+///         self.kind.switch_endianness();
+///         self.count.switch_endianness();
+///     }
+/// }
+///
+/// unsafe impl Packed for Header {}
+///
+/// impl TailCount for Header {
+///     fn tail_count(&self) -> usize {
+///         self.count as usize
+///     }
+/// }
+///
+/// let mut data: &[u8] = &[7, 0, 2, 0, 1, 0, 0, 0, 2, 0, 0, 0];
+/// let record = data.unpack_with_tail::<Header, u32>(1024).unwrap();
+/// assert_eq!(record.header.kind, 7);
+/// assert_eq!(record.tail, [1, 2]);
+/// ```
+pub trait TailCount: Packed {
+    /// Return the number of values following the header.
+    fn tail_count(&self) -> usize;
+}
+
+/// A header of type `H` followed by the variable-length array of values of type `T` whose
+/// length it gives, as read by
+/// [`unpack_with_tail`](trait.Unpacker.html#tymethod.unpack_with_tail) and written by
+/// [`pack_with_tail`](trait.Packer.html#tymethod.pack_with_tail).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WithTail<H, T> {
+    /// The fixed-size header.
+    pub header: H,
+    /// The values following the header.
+    pub tail: Vec<T>
+}
@@ -0,0 +1,43 @@
+//! Helpers for testing the formats built on the crate.
+
+use std::fmt::Debug;
+
+use {order, Packed, Endianness, LittleEndian, BigEndian};
+
+/// Assert that `value` is unpacked identically after being packed, in both the little and big
+/// endian byte orders. This catches, for example, an [`EndianSwitch`](trait.EndianSwitch.html)
+/// implementation which is not its own inverse.
+///
+/// # Panics
+///
+/// Panics with a description of the failing byte order if the unpacked value differs.
+///
+/// ```
+/// use bytepack::roundtrip;
+///
+/// roundtrip(&0x12345678u32);
+/// roundtrip(&[1.5f64, -0.0]);
+/// ```
+pub fn roundtrip<T: Packed + PartialEq + Debug>(value: &T) {
+    for &endianness in &[Endianness::Little, Endianness::Big] {
+        let (bytes, unpacked) = match endianness {
+            Endianness::Little => {
+                let bytes = order::to_bytes_vec::<LittleEndian, T>(value);
+                let unpacked = order::from_bytes_slice::<LittleEndian, T>(&bytes);
+                (bytes, unpacked)
+            },
+            Endianness::Big => {
+                let bytes = order::to_bytes_vec::<BigEndian, T>(value);
+                let unpacked = order::from_bytes_slice::<BigEndian, T>(&bytes);
+                (bytes, unpacked)
+            }
+        };
+        assert!(bytes.len() == T::SIZE, "{:?} was packed in {} bytes instead of {} in the {:?} byte order",
+            value, bytes.len(), T::SIZE, endianness);
+        match unpacked {
+            Ok(ref unpacked) if unpacked == value => {},
+            Ok(unpacked) => panic!("{:?} was unpacked as {:?} in the {:?} byte order", value, unpacked, endianness),
+            Err(e) => panic!("{:?} could not be unpacked in the {:?} byte order: {}", value, endianness, e)
+        }
+    }
+}
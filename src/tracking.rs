@@ -0,0 +1,171 @@
+//! Reader and writer wrapper keeping track of the offset in the stream.
+
+use std::io::{self, Read, BufRead, Write, Seek, SeekFrom};
+
+use {order, Error, Result};
+
+/// A reader or writer wrapper counting the bytes consumed or produced, so the offset reached in
+/// the stream is always known with [`position`](#method.position), even for streams which cannot
+/// seek like sockets or pipes.
+///
+/// [`track`](#method.track) runs a parsing step and records in the errors it returns the offset
+/// of the values which failed to be read, so a failure deep into a large input can be located.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Tracking, Unpacker};
+///
+/// let mut reader = Tracking::new(&[1u8, 0, 2, 0, 0, 0, 3][..]);
+/// let kind : u16 = reader.unpack().unwrap();
+/// assert_eq!(reader.position(), 2);
+/// let e = reader.track(|r| {
+///     let length : u32 = r.unpack()?;
+///     let value : u64 = r.unpack()?;
+///     Ok((length, value))
+/// }).unwrap_err();
+/// assert_eq!(e.offset(), Some(6));
+/// ```
+pub struct Tracking<T> {
+    inner: T,
+    position: u64
+}
+
+impl<T> Tracking<T> {
+    /// Wrap `inner`, counting from offset 0.
+    pub fn new(inner: T) -> Tracking<T> {
+        Tracking::with_position(inner, 0)
+    }
+
+    /// Wrap `inner`, counting from `position`, for a stream which has already been partly
+    /// consumed.
+    pub fn with_position(inner: T, position: u64) -> Tracking<T> {
+        Tracking {
+            inner,
+            position
+        }
+    }
+
+    /// Return the offset reached in the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Call `f` with this wrapper and record an offset in the error it returns, unless the error
+    /// already carries one. For a `ShortRead` error it is the offset of the incomplete values,
+    /// otherwise the offset at which `f` started. Errors of the underlying reader or writer do not
+    /// carry offsets: [`position`](#method.position) tells where they occurred.
+    pub fn track<U, F: FnOnce(&mut Tracking<T>) -> Result<U>>(&mut self, f: F) -> Result<U> {
+        let start = self.position;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("track", offset = start).entered();
+        f(self).map_err(|e| match e {
+            Error::ShortRead { actual, .. } => e.with_offset(self.position.saturating_sub(actual as u64)),
+            e => e.with_offset(start)
+        })
+    }
+
+    /// Return a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader or writer. Bytes read or written through
+    /// it are not counted.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Tracking<T> {
+    /// Return the number of padding bytes needed to reach the next multiple of `align`.
+    fn padding(&self, align: usize) -> Result<usize> {
+        if align == 0 {
+            return Err(Error::invalid_input::<u8, _>("the alignment must not be zero"));
+        }
+        Ok(((align as u64 - self.position % align as u64) % align as u64) as usize)
+    }
+}
+
+impl<R: Read> Tracking<R> {
+    /// Skip the padding bytes up to the next offset which is a multiple of `align`, such as the
+    /// start of a section of a container format. Nothing is skipped if the offset is already
+    /// aligned. An `InvalidInput` error is returned if `align` is zero.
+    ///
+    /// ```
+    /// use bytepack::{Tracking, Unpacker};
+    ///
+    /// let mut reader = Tracking::new(&[1u8, 0, 0, 0, 2][..]);
+    /// let first : u8 = reader.unpack().unwrap();
+    /// reader.skip_to_alignment(4).unwrap();
+    /// let second : u8 = reader.unpack().unwrap();
+    /// assert_eq!((first, second), (1, 2));
+    /// ```
+    pub fn skip_to_alignment(&mut self, align: usize) -> Result<()> {
+        let padding = self.padding(align)?;
+        order::skip::<u8, Tracking<R>>(self, padding)
+    }
+}
+
+impl<W: Write> Tracking<W> {
+    /// Write zero bytes up to the next offset which is a multiple of `align`. Nothing is written
+    /// if the offset is already aligned. An `InvalidInput` error is returned if `align` is zero.
+    ///
+    /// ```
+    /// use bytepack::{Tracking, Packer};
+    ///
+    /// let mut writer = Tracking::new(Vec::new());
+    /// writer.pack(1u8).unwrap();
+    /// writer.pack_align_to(4).unwrap();
+    /// writer.pack(2u8).unwrap();
+    /// assert_eq!(writer.into_inner(), [1, 0, 0, 0, 2]);
+    /// ```
+    pub fn pack_align_to(&mut self, align: usize) -> Result<()> {
+        let padding = self.padding(align)?;
+        self.write_all(&vec![0u8; padding][..])?;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Tracking<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for Tracking<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.position += amt as u64;
+    }
+}
+
+impl<W: Write> Write for Tracking<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.position += size as u64;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: Seek> Seek for Tracking<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,27 @@
+//! Handling of the bytes following the last whole value of a stream.
+
+/// What [`unpack_to_end_with_trailing`](trait.Unpacker.html#tymethod.unpack_to_end_with_trailing)
+/// does when the stream does not end on a whole value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingPolicy {
+    /// Return a `TrailingBytes` error, like
+    /// [`unpack_to_end`](trait.Unpacker.html#tymethod.unpack_to_end), losing the values read.
+    Error,
+    /// Keep the whole values and report the number of trailing bytes, which are dropped.
+    TruncateAndReport,
+    /// Keep the whole values and return the trailing bytes, for example to resynchronize a
+    /// stream.
+    ReturnTrailingBytes
+}
+
+/// Outcome of [`unpack_to_end_with_trailing`](trait.Unpacker.html#tymethod.unpack_to_end_with_trailing).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ToEnd {
+    /// Number of values appended to the buffer.
+    pub count: usize,
+    /// Number of bytes following the last whole value.
+    pub trailing_len: usize,
+    /// The bytes following the last whole value, only kept with
+    /// [`TrailingPolicy::ReturnTrailingBytes`](enum.TrailingPolicy.html#variant.ReturnTrailingBytes).
+    pub trailing: Vec<u8>
+}
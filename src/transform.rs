@@ -0,0 +1,99 @@
+//! Reader and writer wrapper applying a byte transformation to the data going through it.
+
+use std::io::{self, Read, Write};
+
+/// A reader or writer wrapper applying a transformation to all the bytes going through it, such
+/// as the XOR with a rolling key used by some legacy formats to obfuscate their payload. The
+/// transformation is called with the offset in the stream of the first byte of each block of
+/// bytes, so it can be stateless and depend only on the offset of each byte, and the values
+/// packed or unpacked above see the plain data.
+///
+/// When writing, each block is transformed in an internal buffer and entirely written to the
+/// wrapped writer, so no byte is ever transformed twice.
+///
+/// # Example
+///
+/// ```
+/// use bytepack::{Transform, LEPacker, LEUnpacker};
+///
+/// let key = [0x5A, 0xA5, 0x3C];
+/// let xor = move |offset: u64, bytes: &mut [u8]| {
+///     for (i, byte) in bytes.iter_mut().enumerate() {
+///         *byte ^= key[(offset as usize + i) % key.len()];
+///     }
+/// };
+///
+/// let mut writer = Transform::new(Vec::new(), xor);
+/// writer.pack(0x01020304u32).unwrap();
+/// writer.pack(0x0506u16).unwrap();
+/// let scrambled = writer.into_inner();
+/// assert_eq!(scrambled[0], 0x04 ^ 0x5A);
+///
+/// let mut reader = Transform::new(&scrambled[..], xor);
+/// let first : u32 = reader.unpack().unwrap();
+/// let second : u16 = reader.unpack().unwrap();
+/// assert_eq!((first, second), (0x01020304, 0x0506));
+/// ```
+pub struct Transform<T, F> {
+    inner: T,
+    transform: F,
+    position: u64,
+    buf: Vec<u8>
+}
+
+impl<T, F: FnMut(u64, &mut [u8])> Transform<T, F> {
+    /// Wrap `inner`, calling `transform` with the offset of each block of bytes from 0.
+    pub fn new(inner: T, transform: F) -> Transform<T, F> {
+        Transform {
+            inner,
+            transform,
+            position: 0,
+            buf: Vec::new()
+        }
+    }
+
+    /// Return the offset reached in the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Return a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the wrapped reader or writer. Bytes read or written through
+    /// it are not transformed.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the wrapped reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: Read, F: FnMut(u64, &mut [u8])> Read for Transform<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        (self.transform)(self.position, &mut buf[..size]);
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<W: Write, F: FnMut(u64, &mut [u8])> Write for Transform<W, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        self.buf.extend_from_slice(buf);
+        (self.transform)(self.position, &mut self.buf[..]);
+        self.inner.write_all(&self.buf[..])?;
+        self.position += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,143 @@
+//! Asynchronous positioned I/O on files through io_uring, using `tokio-uring`.
+
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio_uring::fs::File;
+
+use {Packed, Endianness, Result, unpack_from_slice_with, unpack_exact_from_slice_with, pack_into_slice_with, pack_all_into_slice_with};
+
+/// Future returned by the methods of [`UringUnpackAt`](trait.UringUnpackAt.html) and
+/// [`UringPackAt`](trait.UringPackAt.html).
+pub type UringFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + 'a>>;
+
+/// `UringUnpackAt` provides the API of [`UnpackAt`](trait.UnpackAt.html) for the files of
+/// `tokio-uring`, issuing the positioned reads through io_uring. Because io_uring needs to own
+/// the buffers it reads into, the values go through an intermediate byte buffer. It is only
+/// available with the `tokio-uring` feature on Linux.
+///
+/// # Example
+///
+/// ```no_run,edition2018
+/// use bytepack::UringUnpackAt;
+/// use tokio_uring::fs::File;
+///
+/// tokio_uring::start(async {
+///     let file = File::open("records").await.unwrap();
+///     let mut records = [0u64; 512];
+///     file.unpack_exact_at(4096, &mut records).await.unwrap();
+/// });
+/// ```
+pub trait UringUnpackAt {
+    /// Unpack a value of type `T` located `offset` bytes from the start of the file.
+    fn unpack_at<'a, T: Packed + 'a>(&'a self, offset: u64) -> UringFuture<'a, T>;
+
+    /// Unpack exactly `buf.len()` values of type `T` located `offset` bytes from the start of the
+    /// file. An error is returned if not enough bytes could be read.
+    fn unpack_exact_at<'a, T: Packed>(&'a self, offset: u64, buf: &'a mut [T]) -> UringFuture<'a, ()>;
+
+    /// Same as [`unpack_at`](#tymethod.unpack_at) but in the byte order given by `endianness`.
+    fn unpack_at_with<'a, T: Packed + 'a>(&'a self, offset: u64, endianness: Endianness) -> UringFuture<'a, T>;
+
+    /// Same as [`unpack_exact_at`](#tymethod.unpack_exact_at) but in the byte order given by
+    /// `endianness`.
+    fn unpack_exact_at_with<'a, T: Packed>(&'a self, offset: u64, buf: &'a mut [T], endianness: Endianness) -> UringFuture<'a, ()>;
+}
+
+/// `UringPackAt` provides the API of [`PackAt`](trait.PackAt.html) for the files of
+/// `tokio-uring`, issuing the positioned writes through io_uring. Because io_uring needs to own
+/// the buffers it writes from, the values are first copied into a byte buffer. It is only
+/// available with the `tokio-uring` feature on Linux.
+pub trait UringPackAt {
+    /// Pack `t` at `offset` bytes from the start of the file.
+    fn pack_at<T: Packed>(&self, offset: u64, t: T) -> UringFuture<'_, ()>;
+
+    /// Pack all the values of `buf` starting `offset` bytes from the start of the file.
+    fn pack_all_at<T: Packed>(&self, offset: u64, buf: &[T]) -> UringFuture<'_, ()>;
+
+    /// Same as [`pack_at`](#tymethod.pack_at) but in the byte order given by `endianness`.
+    fn pack_at_with<T: Packed>(&self, offset: u64, t: T, endianness: Endianness) -> UringFuture<'_, ()>;
+
+    /// Same as [`pack_all_at`](#tymethod.pack_all_at) but in the byte order given by
+    /// `endianness`.
+    fn pack_all_at_with<T: Packed>(&self, offset: u64, buf: &[T], endianness: Endianness) -> UringFuture<'_, ()>;
+}
+
+/// Future applying `map` to the output of `future`.
+struct Map<F, G> {
+    future: Pin<Box<F>>,
+    map: Option<G>
+}
+
+// The map function is never pinned.
+impl<F, G> Unpin for Map<F, G> {}
+
+impl<U, F: Future, G: FnOnce(F::Output) -> Result<U>> Future for Map<F, G> {
+    type Output = Result<U>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<U>> {
+        let this = self.get_mut();
+        match this.future.as_mut().poll(cx) {
+            Poll::Ready(output) => Poll::Ready((this.map.take().expect("future polled after completion"))(output)),
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+fn map<'a, U, F: Future + 'a, G: FnOnce(F::Output) -> Result<U> + 'a>(future: F, map: G) -> UringFuture<'a, U> {
+    Box::pin(Map {
+        future: Box::pin(future),
+        map: Some(map)
+    })
+}
+
+impl UringUnpackAt for File {
+    fn unpack_at<'a, T: Packed + 'a>(&'a self, offset: u64) -> UringFuture<'a, T> {
+        self.unpack_at_with(offset, Endianness::native())
+    }
+
+    fn unpack_exact_at<'a, T: Packed>(&'a self, offset: u64, buf: &'a mut [T]) -> UringFuture<'a, ()> {
+        self.unpack_exact_at_with(offset, buf, Endianness::native())
+    }
+
+    fn unpack_at_with<'a, T: Packed + 'a>(&'a self, offset: u64, endianness: Endianness) -> UringFuture<'a, T> {
+        map(self.read_exact_at(vec![0u8; T::SIZE], offset), move |(result, bytes)| {
+            result?;
+            unpack_from_slice_with::<T>(&bytes, endianness).map(|(t, _)| t)
+        })
+    }
+
+    fn unpack_exact_at_with<'a, T: Packed>(&'a self, offset: u64, buf: &'a mut [T], endianness: Endianness) -> UringFuture<'a, ()> {
+        map(self.read_exact_at(vec![0u8; buf.len() * T::SIZE], offset), move |(result, bytes)| {
+            result?;
+            unpack_exact_from_slice_with(&bytes, buf, endianness).map(|_| ())
+        })
+    }
+}
+
+impl UringPackAt for File {
+    fn pack_at<T: Packed>(&self, offset: u64, t: T) -> UringFuture<'_, ()> {
+        self.pack_at_with(offset, t, Endianness::native())
+    }
+
+    fn pack_all_at<T: Packed>(&self, offset: u64, buf: &[T]) -> UringFuture<'_, ()> {
+        self.pack_all_at_with(offset, buf, Endianness::native())
+    }
+
+    fn pack_at_with<T: Packed>(&self, offset: u64, t: T, endianness: Endianness) -> UringFuture<'_, ()> {
+        let mut bytes = vec![0u8; T::SIZE];
+        if let Err(e) = pack_into_slice_with(&mut bytes, t, endianness) {
+            return Box::pin(future::ready(Err(e)));
+        }
+        map(self.write_all_at(bytes, offset), |(result, _)| Ok(result?))
+    }
+
+    fn pack_all_at_with<T: Packed>(&self, offset: u64, buf: &[T], endianness: Endianness) -> UringFuture<'_, ()> {
+        let mut bytes = vec![0u8; buf.len() * T::SIZE];
+        if let Err(e) = pack_all_into_slice_with(&mut bytes, buf, endianness) {
+            return Box::pin(future::ready(Err(e)));
+        }
+        map(self.write_all_at(bytes, offset), |(result, _)| Ok(result?))
+    }
+}
@@ -0,0 +1,102 @@
+//! Validation of values unpacked from untrusted input.
+
+use {Packed, Order, Ordered};
+
+/// A [`Packed`](trait.Packed.html) type whose values can be checked after being unpacked, for
+/// example for range checks, enum discriminants or fields which cannot be zero. The
+/// `unpack_validated` methods unpack a value and return an `InvalidValue` error carrying the
+/// description given by [`validate`](#tymethod.validate) if it is invalid.
+///
+/// It is implemented for the primitive types, which are always valid, for arrays of valid types
+/// and for [`Ordered`](struct.Ordered.html) values of valid types. Structures can derive it with
+/// `bytepack_derive`, which validates every field and takes additional checks from the
+/// `#[validate(non_zero)]` and `#[validate(range = "...")]` field attributes.
+///
+/// # Example
+///
+/// ```
+/// extern crate bytepack;
+/// #[macro_use]
+/// extern crate bytepack_derive;
+///
+/// use bytepack::{BEUnpacker, Error};
+///
+/// #[derive(Packed, PackedValidate)]
+/// #[repr(C)]
+/// struct Header {
+///     #[validate(range = "1..=1")]
+///     version: u8,
+///     #[validate(range = "0..=3")]
+///     kind: u8,
+///     #[validate(non_zero)]
+///     length: u16
+/// }
+///
+/// fn main() {
+///     let mut reader = &[1u8, 7, 0, 16][..];
+///     match reader.unpack_validated::<Header>() {
+///         Err(Error::InvalidValue { reason, .. }) => assert_eq!(reason, "field kind: 7 is not in 0..=3"),
+///         _ => unreachable!()
+///     }
+/// }
+/// ```
+///
+/// Checks the attributes can not express are written by hand:
+///
+/// ```
+/// use bytepack::{EndianSwitch, Packed, PackedValidate};
+///
+/// #[repr(transparent)]
+/// struct Even(u32);
+///
+/// impl EndianSwitch for Even {
+///     fn switch_endianness(&mut self) {
+///         self.0.switch_endianness();
+///     }
+/// }
+///
+/// unsafe impl Packed for Even {}
+///
+/// impl PackedValidate for Even {
+///     fn validate(&self) -> Result<(), String> {
+///         if self.0 % 2 != 0 {
+///             return Err(format!("{} is odd", self.0));
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait PackedValidate: Packed {
+    /// Check the value, in the system byte order, and return a description of the problem if it
+    /// is invalid.
+    fn validate(&self) -> Result<(), String>;
+}
+
+macro_rules! always_valid {
+    ($($ty:ty),*) => {
+        $(
+            impl PackedValidate for $ty {
+                fn validate(&self) -> Result<(), String> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+always_valid!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl<T: PackedValidate, const N: usize> PackedValidate for [T; N] where [T; N]: Packed {
+    fn validate(&self) -> Result<(), String> {
+        for (i, t) in self.iter().enumerate() {
+            t.validate().map_err(|reason| format!("element {}: {}", i, reason))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: PackedValidate, O: Order> PackedValidate for Ordered<T, O> {
+    fn validate(&self) -> Result<(), String> {
+        self.get().validate()
+    }
+}
@@ -0,0 +1,265 @@
+//! Views of byte buffers as [`Packed`](../trait.Packed.html) values, borrowed without any copy or
+//! converted on access.
+
+use std::any::type_name;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::mem::{align_of, MaybeUninit};
+use std::slice;
+
+use {order, Packed, Endianness, LittleEndian, BigEndian, Error, Result};
+
+/// Check that `size` bytes hold a whole number of values of type `T` and return that number.
+fn check_len<T: Packed>(size: usize) -> Result<usize> {
+    if T::SIZE == 0 {
+        return Err(Error::invalid_input::<T, _>("cannot view zero-sized values"));
+    }
+    if !size.is_multiple_of(T::SIZE) {
+        return Err(Error::trailing_bytes::<T>(size % T::SIZE));
+    }
+    Ok(size / T::SIZE)
+}
+
+/// Check that `size` bytes hold exactly one value of type `T`.
+fn check_size<T: Packed>(size: usize) -> Result<()> {
+    if size < T::SIZE {
+        return Err(Error::short_read::<T>(T::SIZE, size));
+    }
+    if size > T::SIZE {
+        return Err(Error::trailing_bytes::<T>(size - T::SIZE));
+    }
+    Ok(())
+}
+
+/// Check that `bytes` can be viewed as a slice of `T` and return its length in values of type
+/// `T`.
+pub(crate) fn check_view<T: Packed>(bytes: &[u8]) -> Result<usize> {
+    if !(bytes.as_ptr() as usize).is_multiple_of(align_of::<T>()) {
+        return Err(Error::misaligned::<T>());
+    }
+    check_len::<T>(bytes.len())
+}
+
+/// Borrow the content of `bytes` as a value of type `T`, without any copy. An error is returned
+/// if `bytes` is not exactly the size of `T` or is not aligned for `T`.
+///
+/// Because nothing is copied, the value cannot be converted and has to be stored in the system
+/// byte order.
+///
+/// ```
+/// use bytepack::unpack_ref;
+///
+/// let words = [0x01020304u32];
+/// let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 4) };
+/// assert_eq!(*unpack_ref::<u32>(bytes).unwrap(), 0x01020304);
+/// assert!(unpack_ref::<u16>(bytes).is_err());
+/// ```
+pub fn unpack_ref<T: Packed>(bytes: &[u8]) -> Result<&T> {
+    check_size::<T>(bytes.len())?;
+    unpack_slice_ref::<T>(bytes).map(|buf| &buf[0])
+}
+
+/// Borrow the content of `bytes` as a slice of values of type `T`, without any copy. An error is
+/// returned if the size of `bytes` is not a multiple of the size of `T` or if `bytes` is not
+/// aligned for `T`.
+///
+/// Because nothing is copied, the values cannot be converted and have to be stored in the system
+/// byte order.
+///
+/// ```
+/// use bytepack::unpack_slice_ref;
+///
+/// let words = [1u16, 2, 3, 4];
+/// let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 8) };
+/// assert_eq!(unpack_slice_ref::<u16>(bytes).unwrap(), &[1, 2, 3, 4]);
+/// assert!(unpack_slice_ref::<u16>(&bytes[1..]).is_err());
+/// ```
+pub fn unpack_slice_ref<T: Packed>(bytes: &[u8]) -> Result<&[T]> {
+    let len = check_view::<T>(bytes)?;
+    // safe because check_view validated the alignment and the size of bytes
+    unsafe {
+        Ok(slice::from_raw_parts(bytes.as_ptr() as *const T, len))
+    }
+}
+
+/// Same as [`unpack_ref`](fn.unpack_ref.html) but borrows `bytes` mutably, allowing the value to
+/// be modified in place.
+///
+/// ```
+/// use bytepack::unpack_mut;
+///
+/// let mut words = [0u32];
+/// let bytes = unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, 4) };
+/// *unpack_mut::<u32>(bytes).unwrap() = 42;
+/// assert_eq!(words, [42]);
+/// ```
+pub fn unpack_mut<T: Packed>(bytes: &mut [u8]) -> Result<&mut T> {
+    check_size::<T>(bytes.len())?;
+    unpack_slice_mut::<T>(bytes).map(|buf| &mut buf[0])
+}
+
+/// Same as [`unpack_slice_ref`](fn.unpack_slice_ref.html) but borrows `bytes` mutably, allowing
+/// the values to be modified in place.
+///
+/// ```
+/// use bytepack::{unpack_slice_mut, switch_endianness_slice};
+///
+/// let mut words = [0x0102u16, 0x0304];
+/// let bytes = unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, 4) };
+/// switch_endianness_slice(unpack_slice_mut::<u16>(bytes).unwrap());
+/// assert_eq!(words, [0x0201, 0x0403]);
+/// ```
+pub fn unpack_slice_mut<T: Packed>(bytes: &mut [u8]) -> Result<&mut [T]> {
+    let len = check_view::<T>(bytes)?;
+    // safe because check_view validated the alignment and the size of bytes
+    unsafe {
+        Ok(slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len))
+    }
+}
+
+/// Return the content of `bytes`, stored in the byte order `endianness`, as a slice of values of
+/// type `T`. The slice borrows `bytes` when it is aligned for `T` and in the system byte order,
+/// otherwise the values are copied and converted. An error is returned if the size of `bytes` is
+/// not a multiple of the size of `T`.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use bytepack::{unpack_cow, Endianness};
+///
+/// let values = unpack_cow::<u16>(&[0x12, 0x34, 0x56, 0x78], Endianness::Big).unwrap();
+/// assert_eq!(&values[..], &[0x1234, 0x5678]);
+/// ```
+pub fn unpack_cow<T: Packed + Clone>(bytes: &[u8], endianness: Endianness) -> Result<Cow<'_, [T]>> {
+    let len = check_len::<T>(bytes.len())?;
+    if endianness.is_native() && (bytes.as_ptr() as usize).is_multiple_of(align_of::<T>()) {
+        // safe because the alignment and the size of bytes have been checked
+        return unsafe {
+            Ok(Cow::Borrowed(slice::from_raw_parts(bytes.as_ptr() as *const T, len)))
+        };
+    }
+    let mut reader = bytes;
+    match endianness {
+        Endianness::Little => order::unpack_n::<LittleEndian, T, _>(&mut reader, len),
+        Endianness::Big => order::unpack_n::<BigEndian, T, _>(&mut reader, len)
+    }.map(Cow::Owned)
+}
+
+/// View of the bytes of a value of type `T`, stored in the byte order given by an
+/// [`Endianness`](enum.Endianness.html) tag. Nothing is converted when the view is created:
+/// [`get`](#method.get) and [`get_at`](#method.get_at) copy and convert a single field on access,
+/// which avoids converting a whole structure when only a few fields are read. The bytes do not
+/// need to be aligned.
+///
+/// Fields are most conveniently read with the [`view_field!`](macro.view_field.html) macro.
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate bytepack;
+/// # #[macro_use]
+/// # extern crate bytepack_derive;
+/// use bytepack::{PackedView, Endianness};
+///
+/// #[derive(Packed)]
+/// #[repr(C)]
+/// struct Header {
+///     length: u32,
+///     port: u16,
+///     flags: u16
+/// }
+///
+/// # fn main() {
+/// let packet = [0, 0, 0, 8, 0x1F, 0x90, 0, 1];
+/// let header = PackedView::<Header>::new(&packet, Endianness::Big).unwrap();
+/// assert_eq!(view_field!(header, port).unwrap(), 8080);
+/// assert_eq!(header.get_at::<u32>(0).unwrap(), 8);
+/// # }
+/// ```
+pub struct PackedView<'a, T> {
+    bytes: &'a [u8],
+    endianness: Endianness,
+    marker: PhantomData<T>
+}
+
+impl<'a, T: Packed> PackedView<'a, T> {
+    /// Create a view of `bytes`, which holds a value of type `T` stored in the byte order
+    /// `endianness`. An error is returned if `bytes` is not exactly the size of `T`.
+    pub fn new(bytes: &'a [u8], endianness: Endianness) -> Result<PackedView<'a, T>> {
+        check_size::<T>(bytes.len())?;
+        Ok(PackedView {
+            bytes,
+            endianness,
+            marker: PhantomData
+        })
+    }
+
+    /// Create a view of the first value of type `T` of `bytes` and return it with the remaining
+    /// bytes. A `ShortRead` error is returned if `bytes` is shorter than `T`.
+    pub fn from_front(bytes: &'a [u8], endianness: Endianness) -> Result<(PackedView<'a, T>, &'a [u8])> {
+        if bytes.len() < T::SIZE {
+            return Err(Error::short_read::<T>(T::SIZE, bytes.len()));
+        }
+        let (front, rest) = bytes.split_at(T::SIZE);
+        Ok((PackedView::new(front, endianness)?, rest))
+    }
+
+    /// Return the viewed bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Return the byte order of the viewed value.
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Copy and convert the whole value.
+    pub fn unpack(&self) -> T {
+        self.get_at::<T>(0).expect("the view has the size of T")
+    }
+
+    /// Copy and convert the field of type `F` starting `offset` bytes into the value. An
+    /// `InvalidInput` error is returned if the field does not fit in the value.
+    pub fn get_at<F: Packed>(&self, offset: usize) -> Result<F> {
+        let mut field = match self.bytes.get(offset..) {
+            Some(bytes) if bytes.len() >= F::SIZE => &bytes[..F::SIZE],
+            _ => return Err(Error::invalid_input::<F, _>(format!("field at offset {} does not fit in {}", offset, type_name::<T>())))
+        };
+        match self.endianness {
+            Endianness::Little => order::unpack::<LittleEndian, F, _>(&mut field),
+            Endianness::Big => order::unpack::<BigEndian, F, _>(&mut field)
+        }
+    }
+
+    /// Copy and convert the field of type `F` selected by `project`, which maps a pointer to a
+    /// value of type `T` to a pointer to the field. The pointer must not be dereferenced: use
+    /// `std::ptr::addr_of!((*t).field)`, or the [`view_field!`](macro.view_field.html) macro
+    /// which does it. An `InvalidInput` error is returned if the projected pointer does not point
+    /// inside the value.
+    pub fn get<F: Packed, P: FnOnce(*const T) -> *const F>(&self, project: P) -> Result<F> {
+        let value = MaybeUninit::<T>::uninit();
+        let base = value.as_ptr() as usize;
+        let field = project(value.as_ptr()) as usize;
+        if field < base {
+            return Err(Error::invalid_input::<F, _>(format!("field is outside of {}", type_name::<T>())));
+        }
+        self.get_at(field - base)
+    }
+}
+
+impl<'a, T> Clone for PackedView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for PackedView<'a, T> {}
+
+/// Copy and convert a field of the value viewed by a [`PackedView`](struct.PackedView.html),
+/// given its name. The field type is inferred and the result is a `bytepack::Result`. See
+/// [`PackedView`](struct.PackedView.html) for an example.
+#[macro_export]
+macro_rules! view_field {
+    ($view:expr, $field:ident) => {
+        $view.get(|t| unsafe { ::std::ptr::addr_of!((*t).$field) })
+    };
+}
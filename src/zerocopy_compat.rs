@@ -0,0 +1,90 @@
+//! Interoperability with the `FromBytes` and `AsBytes` traits of the `zerocopy` crate.
+
+use zerocopy::{FromBytes, AsBytes};
+
+use {Packed, EndianSwitch};
+
+/// A wrapper making any type implementing both `zerocopy::FromBytes` and `zerocopy::AsBytes`
+/// usable with the packing and unpacking methods. It is only available with the `zerocopy`
+/// feature.
+///
+/// The `zerocopy` traits say nothing about the byte order of a type, so the wrapped value is
+/// always transferred as is, whatever the trait family used. Multi-byte fields should use the
+/// types of `zerocopy::byteorder`, which fix their byte order.
+///
+/// # Example
+///
+/// ```edition2018
+/// use bytepack::{ZerocopyPacked, Unpacker};
+/// use zerocopy::byteorder::{U32, BigEndian};
+///
+/// let mut reader = &[0u8, 0, 0x1F, 0x90, 0, 0, 0, 1][..];
+/// let values : Vec<ZerocopyPacked<U32<BigEndian>>> = reader.unpack_n(2).unwrap();
+/// assert_eq!(values[0].0.get(), 8080);
+/// assert_eq!(values[1].0.get(), 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ZerocopyPacked<T: FromBytes + AsBytes>(pub T);
+
+impl<T: FromBytes + AsBytes> EndianSwitch for ZerocopyPacked<T> {
+    fn switch_endianness(&mut self) {
+        // The value is transferred as is.
+    }
+}
+
+// Safe because `FromBytes` guarantees any bit pattern is valid and `AsBytes` that there is no
+// padding, the remaining requirements of `Packed` follow from them.
+unsafe impl<T: FromBytes + AsBytes> Packed for ZerocopyPacked<T> {}
+
+#[doc(hidden)]
+pub fn assert_zerocopy<T: FromBytes + AsBytes>() {}
+
+/// Implement [`Packed`](trait.Packed.html) and [`EndianSwitch`](trait.EndianSwitch.html) for
+/// types already implementing `zerocopy::FromBytes` and `zerocopy::AsBytes`, so they go through
+/// the packing methods without being wrapped. It is only available with the `zerocopy` feature.
+///
+/// Like with [`ZerocopyPacked`](struct.ZerocopyPacked.html) the values are transferred as is,
+/// whatever the trait family used. The macro fails to compile for a type which does not
+/// implement both traits.
+///
+/// # Example
+///
+/// ```edition2018,ignore
+/// use bytepack::{packed_zerocopy, Packer};
+/// use zerocopy::{FromZeroes, FromBytes, AsBytes};
+/// use zerocopy::byteorder::{U16, U32, BigEndian};
+///
+/// #[derive(FromZeroes, FromBytes, AsBytes)]
+/// #[repr(C)]
+/// struct Header {
+///     magic: [u8; 4],
+///     version: U16<BigEndian>,
+///     flags: U16<BigEndian>,
+///     length: U32<BigEndian>
+/// }
+///
+/// packed_zerocopy!(Header);
+///
+/// fn write_header<W: std::io::Write>(w: &mut W, header: Header) -> bytepack::Result<()> {
+///     w.pack(header)
+/// }
+/// ```
+#[macro_export]
+macro_rules! packed_zerocopy {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            const _: fn() = $crate::assert_zerocopy::<$ty>;
+
+            impl $crate::EndianSwitch for $ty {
+                fn switch_endianness(&mut self) {
+                    // The value is transferred as is.
+                }
+            }
+
+            // Safe because the contracts of `FromBytes` and `AsBytes`, checked above, imply the
+            // one of `Packed`.
+            unsafe impl $crate::Packed for $ty {}
+        )+
+    };
+}
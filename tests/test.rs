@@ -1,6 +1,25 @@
+#![allow(clippy::approx_constant, clippy::op_ref)]
+
+#[macro_use]
 extern crate bytepack;
 #[macro_use]
 extern crate bytepack_derive;
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "byteorder")]
+extern crate byteorder;
+#[cfg(feature = "digest")]
+extern crate sha2;
+#[cfg(feature = "flate2")]
+extern crate flate2;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
 
 use std::io::Cursor;
 
@@ -72,18 +91,32 @@ fn multiple() {
 struct Foo {
     a: u16,
     b: f32,
-    c: i8
+    c: i8,
+    reserved: u8
+}
+
+#[derive(Packed)]
+#[repr(C, packed)]
+struct Tagged {
+    tag: u8,
+    value: u32
 }
 
 #[test]
 fn struct_unpack() {
     let mut buffer = Cursor::new(vec![0u8, 128]);
-    buffer.pack(Foo {a: 666u16, b: 3.14f32, c: -42i8}).unwrap();
+    buffer.pack(Foo {a: 666u16, b: 3.14f32, c: -42i8, reserved: 0}).unwrap();
     buffer.set_position(0);
     let foo : Foo = buffer.unpack().unwrap();
     assert!(foo.a == 666u16);
     assert!(foo.b == 3.14f32);
     assert!(foo.c == -42i8);
+
+    let mut buffer = Vec::new();
+    bytepack::BEPacker::pack(&mut buffer, Tagged {tag: 7, value: 0x12345678}).unwrap();
+    assert!(buffer == [7, 0x12, 0x34, 0x56, 0x78]);
+    let tagged : Tagged = bytepack::BEUnpacker::unpack(&mut &buffer[..]).unwrap();
+    assert!({tagged.value} == 0x12345678);
 }
 
 #[test]
@@ -106,3 +139,2209 @@ fn endianness() {
         assert!(LEUnpacker::unpack::<u32>(&mut buffer).unwrap() == 0x78563412);
     }
 }
+
+c_struct! {
+    #[derive(Packed)]
+    struct Bar {
+        a: u16,
+        b: [i8; 6],
+        c: [[f64; 3]; 2],
+    }
+}
+
+#[test]
+fn c_definition() {
+    use bytepack::CDecl;
+
+    assert!(Bar::c_decl("bar") == "struct Bar bar");
+    assert!(Bar::c_definition().unwrap() == "struct Bar {\n    uint16_t a;\n    int8_t b[6];\n    double c[2][3];\n};\n");
+    assert!(u64::c_definition().is_none());
+    assert!(bytepack::Le::<u16>::c_decl("port") == "uint8_t port[2]");
+    assert!(std::mem::offset_of!(Bar, b) == 2 && std::mem::offset_of!(Bar, c) == 8);
+}
+
+#[test]
+fn runtime_endianness() {
+    use bytepack::{Endianness, BEUnpacker, LEUnpacker};
+
+    let mut buffer = Cursor::new(vec![0u8, 128]);
+    buffer.pack_with::<u32>(0x12345678, Endianness::Little).unwrap();
+    buffer.pack_all_with::<u16>(&[0x1234, 0x5678], Endianness::Big).unwrap();
+    buffer.set_position(0);
+    assert!(LEUnpacker::unpack::<u32>(&mut buffer).unwrap() == 0x12345678);
+    assert!(BEUnpacker::unpack::<u16>(&mut buffer).unwrap() == 0x1234);
+    buffer.set_position(4);
+    let mut result = [0u16; 2];
+    buffer.unpack_exact_with(&mut result, Endianness::Big).unwrap();
+    assert!(result == [0x1234, 0x5678]);
+    buffer.set_position(0);
+    assert!(buffer.unpack_with::<u32>(Endianness::Little).unwrap() == 0x12345678);
+    assert!(Endianness::native().is_native());
+}
+
+#[test]
+fn generic_order() {
+    use bytepack::{Order, NativeEndian, LittleEndian, BigEndian, Endianness};
+
+    fn swapped<O: Order>(value: u16) -> u16 {
+        let mut value = value;
+        O::convert(&mut value);
+        value
+    }
+
+    assert!(swapped::<NativeEndian>(0x1234) == 0x1234);
+    assert!(swapped::<LittleEndian>(0x1234) == 0x1234u16.to_le());
+    assert!(swapped::<BigEndian>(0x1234) == 0x1234u16.to_be());
+    assert!(BigEndian::ENDIANNESS == Endianness::Big);
+}
+
+#[derive(Packed)]
+struct Mixed {
+    id: bytepack::Be<u32>,
+    length: bytepack::Le<u16>
+}
+
+#[test]
+fn mixed_endianness() {
+    use bytepack::{Be, Le, BEPacker, LEUnpacker};
+
+    let mut buffer = Cursor::new(vec![0u8, 128]);
+    BEPacker::pack(&mut buffer, Mixed {id: Be::new(0x12345678), length: Le::new(0x9abc)}).unwrap();
+    assert!(buffer.get_ref()[..6] == [0x12, 0x34, 0x56, 0x78, 0xbc, 0x9a]);
+    buffer.set_position(0);
+    let mixed : Mixed = LEUnpacker::unpack(&mut buffer).unwrap();
+    assert!(mixed.id.get() == 0x12345678);
+    assert!(mixed.length.into_inner() == 0x9abc);
+    assert!(std::mem::size_of::<Mixed>() == 6 && std::mem::align_of::<Be<u32>>() == 1);
+    assert!(Be::new(1.5f32) == Be::new(1.5f32) && Be::new(f32::NAN) != Be::new(f32::NAN));
+}
+
+#[test]
+fn network_order() {
+    use bytepack::{NetPacker, NetUnpacker};
+
+    let mut buffer = Cursor::new(vec![0u8, 128]);
+    NetPacker::pack::<u16>(&mut buffer, 8080).unwrap();
+    buffer.pack_u24(0x123456).unwrap();
+    assert!(buffer.pack_u24(0x1000000).is_err());
+    assert!(buffer.get_ref()[..5] == [0x1f, 0x90, 0x12, 0x34, 0x56]);
+    buffer.set_position(0);
+    assert!(NetUnpacker::unpack::<u16>(&mut buffer).unwrap() == 8080);
+    assert!(buffer.unpack_u24().unwrap() == 0x123456);
+}
+
+#[test]
+fn unpack_iter() {
+    use bytepack::BEUnpacker;
+
+    let mut buffer = Cursor::new(vec![0x12u8, 0x34, 0x56, 0x78]);
+    let values : Vec<u16> = BEUnpacker::unpack_iter::<u16>(&mut buffer).map(|v| v.unwrap()).collect();
+    assert!(values == [0x1234, 0x5678]);
+
+    let mut buffer = Cursor::new(vec![0x12u8, 0x34, 0x56]);
+    let mut iter = BEUnpacker::unpack_iter::<u16>(&mut buffer);
+    assert!(iter.next().unwrap().unwrap() == 0x1234);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn pack_iter() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let mut buffer = Cursor::new(Vec::new());
+    BEPacker::pack_iter(&mut buffer, 0..10000u32).unwrap();
+    assert!(buffer.get_ref().len() == 40000);
+    buffer.set_position(0);
+    let mut result = Vec::<u32>::new();
+    BEUnpacker::unpack_to_end(&mut buffer, &mut result).unwrap();
+    assert!(result == (0..10000u32).collect::<Vec<u32>>());
+}
+
+#[test]
+fn unpack_n() {
+    use bytepack::{LEPacker, LEUnpacker};
+
+    let mut buffer = Cursor::new(Vec::new());
+    LEPacker::pack(&mut buffer, 3u32).unwrap();
+    LEPacker::pack_all(&mut buffer, &[1.5f64, -2.0, 42.0]).unwrap();
+    buffer.set_position(0);
+    let count : u32 = LEUnpacker::unpack(&mut buffer).unwrap();
+    let values : Vec<f64> = LEUnpacker::unpack_n(&mut buffer, count as usize).unwrap();
+    assert!(values == [1.5, -2.0, 42.0]);
+    assert!(LEUnpacker::unpack_n::<f64>(&mut buffer, 1).is_err());
+}
+
+#[test]
+fn unpack_boxed_slice() {
+    let mut buffer = Cursor::new(Vec::new());
+    buffer.pack_all(&[1u16, 2, 3, 4]).unwrap();
+    buffer.set_position(0);
+    let values : Box<[u16]> = buffer.unpack_boxed_slice(3).unwrap();
+    assert!(*values == [1, 2, 3]);
+    assert!(buffer.unpack_boxed_slice::<u16>(2).is_err());
+}
+
+#[test]
+fn unpack_arc_slice() {
+    use std::sync::Arc;
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let mut buffer = Cursor::new(Vec::new());
+    BEPacker::pack_all(&mut buffer, &[1i32, -2, 3]).unwrap();
+    buffer.set_position(0);
+    let values : Arc<[i32]> = BEUnpacker::unpack_arc_slice(&mut buffer, 3).unwrap();
+    assert!(*values == [1, -2, 3]);
+    assert!(BEUnpacker::unpack_arc_slice::<i32>(&mut buffer, 1).is_err());
+}
+
+#[test]
+fn unpack_uninit() {
+    use std::mem::MaybeUninit;
+
+    let mut buffer = Cursor::new(Vec::new());
+    buffer.pack_all(&[1u32, 2, 3, 4, 5]).unwrap();
+    buffer.set_position(0);
+    let mut uninit = [MaybeUninit::<u32>::uninit(); 2];
+    assert!(buffer.unpack_exact_uninit(&mut uninit).unwrap() == [1, 2]);
+    let mut values = vec![0u32];
+    buffer.unpack_append(&mut values, 2).unwrap();
+    assert!(values == [0, 3, 4]);
+    assert!(buffer.unpack_append(&mut values, 2).is_err());
+    assert!(values == [0, 3, 4]);
+}
+
+#[test]
+fn unpack_to_end_sized() {
+    let mut buffer = Cursor::new(Vec::new());
+    buffer.pack_all(&[1u32, 2, 3, 4]).unwrap();
+    buffer.set_position(4);
+    let mut result = Vec::<u32>::new();
+    assert!(buffer.unpack_to_end_sized(&mut result).unwrap() == 3);
+    assert!(result == [2, 3, 4]);
+    assert!(result.capacity() == 3);
+}
+
+#[test]
+fn pack_all_without_clone() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    #[derive(Packed, PartialEq)]
+    struct NotClone(u32);
+
+    let case : Vec<NotClone> = (0..5000).map(NotClone).collect();
+    let mut buffer = Cursor::new(Vec::new());
+    BEPacker::pack_all(&mut buffer, &case[..]).unwrap();
+    BEPacker::pack_ref(&mut buffer, &NotClone(42)).unwrap();
+    assert!(buffer.get_ref()[4..8] == [0, 0, 0, 1]);
+    buffer.set_position(0);
+    let result : Vec<NotClone> = BEUnpacker::unpack_n(&mut buffer, 5001).unwrap();
+    assert!(result[..5000] == case[..]);
+    assert!(result[5000] == NotClone(42));
+}
+
+#[test]
+fn pack_all_vectored() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let mut buffer = Cursor::new(Vec::new());
+    Packer::pack_all_vectored(&mut buffer, &[&[1u16, 2][..], &[][..], &[3u16][..]]).unwrap();
+    BEPacker::pack_all_vectored(&mut buffer, &[&[4u16][..], &[5u16, 6][..]]).unwrap();
+    buffer.set_position(0);
+    let mut native = [0u16; 3];
+    Unpacker::unpack_exact(&mut buffer, &mut native).unwrap();
+    assert!(native == [1, 2, 3]);
+    let mut big = [0u16; 3];
+    BEUnpacker::unpack_exact(&mut buffer, &mut big).unwrap();
+    assert!(big == [4, 5, 6]);
+}
+
+#[test]
+fn large_endianness_switch() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let case : Vec<u32> = (0..1000000).collect();
+    let mut buffer = Cursor::new(Vec::new());
+    BEPacker::pack_all(&mut buffer, &case[..]).unwrap();
+    assert!(buffer.get_ref()[4..8] == [0, 0, 0, 1]);
+    buffer.set_position(0);
+    let mut result = Vec::<u32>::new();
+    BEUnpacker::unpack_to_end(&mut buffer, &mut result).unwrap();
+    assert!(result == case);
+}
+
+#[test]
+fn slice_endianness() {
+    use bytepack::{switch_endianness_slice, to_be_slice, from_be_slice, to_le_slice, from_le_slice};
+
+    let mut buf = [0x1234u16, 0x5678];
+    switch_endianness_slice(&mut buf);
+    assert!(buf == [0x3412, 0x7856]);
+    let mut buf = [1.5f32, -2.0];
+    to_be_slice(&mut buf);
+    assert!(buf[0].to_bits() == 1.5f32.to_bits().to_be());
+    from_be_slice(&mut buf);
+    assert!(buf == [1.5, -2.0]);
+    to_le_slice(&mut buf);
+    from_le_slice(&mut buf);
+    assert!(buf == [1.5, -2.0]);
+}
+
+#[test]
+fn peek() {
+    use bytepack::BEUnpacker;
+
+    let mut buffer = Cursor::new(vec![0x12u8, 0x34, 0x56]);
+    let tag : u16 = BEUnpacker::peek(&mut buffer).unwrap();
+    assert!(tag == 0x1234);
+    assert!(buffer.position() == 0);
+    buffer.set_position(2);
+    assert!(BEUnpacker::peek::<u16>(&mut buffer).is_err());
+    assert!(buffer.position() == 2);
+    let value : u8 = BEUnpacker::unpack(&mut buffer).unwrap();
+    assert!(value == 0x56);
+}
+
+#[test]
+fn skip() {
+    let mut buffer = Cursor::new(vec![1u8, 2, 3, 4, 5, 6, 7]);
+    buffer.skip::<u16>(2).unwrap();
+    assert!(buffer.position() == 4);
+    buffer.skip_seek::<u8>(1).unwrap();
+    assert!(buffer.position() == 5);
+    assert!(buffer.skip_seek::<u16>(2).is_err());
+    assert!(buffer.position() == 5);
+    assert!(buffer.skip::<u16>(2).is_err());
+}
+
+#[test]
+fn unpack_at() {
+    use bytepack::BEUnpacker;
+
+    let mut buffer = Cursor::new(vec![0u8, 4, 0xFF, 0xFF, 0, 1, 0, 2]);
+    let offset : u16 = BEUnpacker::unpack(&mut buffer).unwrap();
+    let first : u16 = BEUnpacker::unpack_at(&mut buffer, offset as u64).unwrap();
+    assert!(first == 1);
+    assert!(buffer.position() == 2);
+    let mut entries = [0u16; 2];
+    BEUnpacker::unpack_exact_at(&mut buffer, 4, &mut entries).unwrap();
+    assert!(entries == [1, 2]);
+    assert!(BEUnpacker::unpack_at::<u32>(&mut buffer, 6).is_err());
+    assert!(buffer.position() == 2);
+}
+
+#[test]
+fn pack_at() {
+    use bytepack::{LEPacker, LEUnpacker};
+
+    let mut buffer = Cursor::new(Vec::new());
+    LEPacker::pack(&mut buffer, 0u32).unwrap();
+    LEPacker::pack_all(&mut buffer, &[1u16, 2, 3]).unwrap();
+    let size = buffer.position() as u32;
+    LEPacker::pack_at(&mut buffer, 0, size).unwrap();
+    assert!(buffer.position() == 10);
+    buffer.set_position(0);
+    let size : u32 = LEUnpacker::unpack(&mut buffer).unwrap();
+    assert!(size == 10);
+}
+
+#[test]
+fn transaction() {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut buffer = Cursor::new(vec![1u8, 2, 3]);
+    let result = buffer.transaction(|r| {
+        let a : u16 = Unpacker::unpack(r)?;
+        let b : u16 = Unpacker::unpack(r)?;
+        Ok((a, b))
+    });
+    assert!(result.is_err());
+    assert!(buffer.position() == 0);
+    let result = buffer.transaction(Unpacker::unpack::<[u8; 2]>);
+    assert!(result.unwrap() == [1, 2]);
+    assert!(buffer.position() == 2);
+
+    // stream which cannot seek back to its start
+    struct Forward(Cursor<Vec<u8>>);
+    impl Read for Forward {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+    impl Seek for Forward {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            match pos {
+                SeekFrom::Start(0) => Err(std::io::Error::other("cannot rewind")),
+                pos => self.0.seek(pos)
+            }
+        }
+    }
+    let mut stream = Forward(Cursor::new(vec![1u8, 2, 3]));
+    let result = stream.transaction(Unpacker::unpack::<u32>);
+    assert!(matches!(result, Err(bytepack::Error::ShortRead { .. })));
+    let mut stream = Forward(Cursor::new(vec![1u8, 2, 3]));
+    assert!(matches!(Unpacker::unpack_at::<u32>(&mut stream, 1), Err(bytepack::Error::ShortRead { .. })));
+    let mut stream = Forward(Cursor::new(vec![1u8, 2, 3]));
+    assert!(matches!(Unpacker::unpack_at::<u8>(&mut stream, 1), Err(bytepack::Error::Io(_))));
+}
+
+#[test]
+fn positioned_file() {
+    use std::fs::{self, OpenOptions};
+    use bytepack::{UnpackAt, PackAt, Endianness};
+
+    let path = std::env::temp_dir().join(format!("bytepack-positioned-{}", std::process::id()));
+    let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path).unwrap();
+    file.pack_all_at(4, &[1u16, 2, 3]).unwrap();
+    file.pack_at_with(0, 10u32, Endianness::Big).unwrap();
+    let size : u32 = file.unpack_at_with(0, Endianness::Big).unwrap();
+    assert!(size == 10);
+    let mut values = [0u16; 3];
+    file.unpack_exact_at(4, &mut values).unwrap();
+    assert!(values == [1, 2, 3]);
+    assert!(file.unpack_at::<u32>(8).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn packed_mmap() {
+    use std::fs::{self, File};
+    use bytepack::{PackedMmap, Endianness, BEPacker};
+
+    let path = std::env::temp_dir().join(format!("bytepack-mmap-{}", std::process::id()));
+    BEPacker::pack_all(&mut File::create(&path).unwrap(), &[1u32, 2, 3]).unwrap();
+    let file = File::open(&path).unwrap();
+    let map = unsafe { PackedMmap::<u32>::map(&file).unwrap() };
+    assert!(map.len() == 3);
+    assert!(map.get_with(1, Endianness::Big) == Some(2));
+    assert!(map.get_with(3, Endianness::Big).is_none());
+    assert!(map.to_vec_with(Endianness::Big) == [1, 2, 3]);
+    assert!(unsafe { PackedMmap::<[u8; 8]>::map(&file).is_err() });
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn packed_mmap_mut() {
+    use std::fs::{self, File, OpenOptions};
+    use bytepack::{PackedMmapMut, Endianness, LEPacker, LEUnpacker};
+
+    let path = std::env::temp_dir().join(format!("bytepack-mmap-mut-{}", std::process::id()));
+    LEPacker::pack_all(&mut File::create(&path).unwrap(), &[1u16, 2, 3, 4]).unwrap();
+    let file = OpenOptions::new().read(true).write(true).open(&path).unwrap();
+    {
+        let mut map = unsafe { PackedMmapMut::<u16>::map_mut(&file).unwrap() };
+        let value = map.get_with(2, Endianness::Little).unwrap();
+        map.set_with(2, value * 10, Endianness::Little);
+        map.flush().unwrap();
+    }
+    let mut result = [0u16; 4];
+    LEUnpacker::unpack_exact(&mut File::open(&path).unwrap(), &mut result).unwrap();
+    assert!(result == [1, 2, 30, 4]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn record_file() {
+    use bytepack::{RecordFile, BigEndian};
+
+    let mut records = RecordFile::<[u16; 2], _, BigEndian>::new(Cursor::new(vec![0u8, 1, 0, 2])).unwrap();
+    assert!(records.len() == 1);
+    assert!(records.append([3, 4]).unwrap() == 1);
+    assert!(records.append([5, 6]).unwrap() == 2);
+    records.set(1, [7, 8]).unwrap();
+    assert!(records.get(1).unwrap() == [7, 8]);
+    let mut range = [[0u16; 2]; 2];
+    records.get_range(1, &mut range).unwrap();
+    assert!(range == [[7, 8], [5, 6]]);
+    assert!(records.get(3).is_err());
+    assert!(records.get_range(2, &mut range).is_err());
+    assert!(records.into_inner().into_inner()[..4] == [0, 1, 0, 2]);
+    assert!(RecordFile::<u32, _>::new(Cursor::new(vec![0u8; 3])).is_err());
+}
+
+#[test]
+fn records() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let case : Vec<u32> = (0..1000).collect();
+    let mut buffer = Cursor::new(Vec::new());
+    BEPacker::pack_all(&mut buffer, &case[..]).unwrap();
+    buffer.set_position(0);
+    let result : Vec<u32> = BEUnpacker::records(&mut buffer).map(|r| r.unwrap()).collect();
+    assert!(result == case);
+    // a small capacity forces many batches and the trailing byte is an incomplete value
+    buffer.get_mut().push(0);
+    buffer.set_position(0);
+    let result : Vec<_> = BEUnpacker::records_with_capacity::<u32>(&mut buffer, 10).collect();
+    assert!(result.len() == 1001);
+    assert!(result[..1000].iter().map(|r| *r.as_ref().unwrap()).eq(0..1000));
+    assert!(result[1000].is_err());
+}
+
+#[test]
+fn zero_copy_ref() {
+    use bytepack::{unpack_ref, unpack_slice_ref};
+
+    let words = [0x0102u16, 0x0304, 0x0506];
+    let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 6) };
+    assert!(unpack_slice_ref::<u16>(bytes).unwrap() == words);
+    assert!(*unpack_ref::<u16>(&bytes[2..4]).unwrap() == 0x0304);
+    assert!(unpack_ref::<u16>(&bytes[1..3]).is_err());
+    assert!(unpack_ref::<u16>(&bytes[..4]).is_err());
+    assert!(unpack_slice_ref::<u16>(&bytes[..5]).is_err());
+}
+
+#[test]
+fn zero_copy_mut() {
+    use bytepack::{unpack_mut, unpack_slice_mut};
+
+    let mut words = [1u32, 2, 3];
+    {
+        let bytes = unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, 12) };
+        *unpack_mut::<u32>(&mut bytes[4..8]).unwrap() = 20;
+        unpack_slice_mut::<u32>(&mut bytes[8..]).unwrap()[0] += 27;
+        assert!(unpack_mut::<u32>(&mut bytes[1..5]).is_err());
+        assert!(unpack_slice_mut::<u32>(&mut bytes[..6]).is_err());
+    }
+    assert!(words == [1, 20, 30]);
+}
+
+#[test]
+fn aligned_vec() {
+    use bytepack::{AlignedVec, LEPacker, unpack_slice_ref};
+
+    let mut buffer = AlignedVec::<u64>::from_bytes(&[1, 2, 3]);
+    assert!((buffer.as_ptr() as usize).is_multiple_of(std::mem::align_of::<u64>()));
+    buffer.resize(8, 0xFF);
+    assert!(buffer[..] == [1, 2, 3, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    buffer.clear();
+    LEPacker::pack_all(&mut buffer, &[1u64, 2, 3]).unwrap();
+    assert!(buffer.len() == 24);
+    let words : Vec<u64> = unpack_slice_ref::<u64>(&buffer).unwrap().iter().map(|w| u64::from_le(*w)).collect();
+    assert!(words == [1, 2, 3]);
+    assert!(AlignedVec::<u32>::zeroed(6)[..] == [0; 6]);
+}
+
+#[test]
+fn unpack_cow() {
+    use std::borrow::Cow;
+    use bytepack::{unpack_cow, Endianness};
+
+    let words = [0x0102u16, 0x0304];
+    let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 4) };
+    let native = Endianness::native();
+    let other = match native {
+        Endianness::Little => Endianness::Big,
+        Endianness::Big => Endianness::Little
+    };
+    match unpack_cow::<u16>(bytes, native).unwrap() {
+        Cow::Borrowed(values) => assert!(values == words),
+        Cow::Owned(_) => panic!("aligned native data should be borrowed")
+    }
+    match unpack_cow::<u16>(bytes, other).unwrap() {
+        Cow::Owned(values) => assert!(values == [0x0201, 0x0403]),
+        Cow::Borrowed(_) => panic!("foreign data should be copied")
+    }
+    let unaligned = unpack_cow::<u16>(&[0, 0x12, 0x34][1..], Endianness::Big).unwrap();
+    assert!(unaligned[..] == [0x1234]);
+    assert!(unpack_cow::<u16>(&bytes[..3], native).is_err());
+}
+
+#[test]
+fn unpack_from_slice() {
+    use bytepack::{unpack_from_slice, unpack_from_slice_with, Endianness};
+
+    let packet = [0u8, 0x12, 0x34, 0x56, 0x78, 9];
+    let (value, rest) = unpack_from_slice_with::<u32>(&packet[1..], Endianness::Big).unwrap();
+    assert!(value == 0x12345678);
+    let (last, rest) = unpack_from_slice::<u8>(rest).unwrap();
+    assert!(last == 9 && rest.is_empty());
+    assert!(unpack_from_slice::<u16>(rest).is_err());
+}
+
+#[test]
+fn slice_writers() {
+    use bytepack::{pack_into_slice_with, pack_all_into_slice, unpack_exact_from_slice_with, Endianness};
+
+    let mut packet = [0u8; 7];
+    {
+        let rest = pack_into_slice_with(&mut packet, 0x0102u16, Endianness::Big).unwrap();
+        let rest = pack_all_into_slice(rest, &[3u8, 4, 5, 6]).unwrap();
+        assert!(rest.len() == 1);
+        assert!(pack_all_into_slice(rest, &[7u8, 8]).is_err());
+        assert!(rest[0] == 0);
+    }
+    assert!(packet[..6] == [1, 2, 3, 4, 5, 6]);
+    let mut words = [0u16; 3];
+    let rest = unpack_exact_from_slice_with(&packet, &mut words, Endianness::Little).unwrap();
+    assert!(words == [0x0201, 0x0403, 0x0605] && rest == [0]);
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(Noop));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_async() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use bytepack::{AsyncUnpacker, AsyncPacker, Endianness};
+
+    // reader returning a single byte every other poll
+    struct Trickle(Vec<u8>, bool);
+    impl tokio::io::AsyncRead for Trickle {
+        fn poll_read(self: Pin<&mut Self>, _cx: &mut Context, buf: &mut tokio::io::ReadBuf) -> Poll<std::io::Result<()>> {
+            let this = self.get_mut();
+            this.1 = !this.1;
+            if this.1 {
+                return Poll::Pending;
+            }
+            if !this.0.is_empty() {
+                buf.put_slice(&[this.0.remove(0)]);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let mut buffer = Vec::new();
+    block_on(AsyncPacker::pack_with(&mut buffer, 0x0102u16, Endianness::Big)).unwrap();
+    block_on(AsyncPacker::pack_all_with(&mut buffer, &[3u32, 4], Endianness::Little)).unwrap();
+    assert!(buffer == [1, 2, 3, 0, 0, 0, 4, 0, 0, 0]);
+    let mut by_ref = Vec::new();
+    block_on(AsyncPacker::pack_ref_with(&mut by_ref, &[0x0506u16; 2], Endianness::Big)).unwrap();
+    assert!(by_ref == [5, 6, 5, 6]);
+    let mut reader = Trickle(buffer, false);
+    let value : u16 = block_on(reader.unpack_with(Endianness::Big)).unwrap();
+    assert!(value == 0x0102);
+    let mut values = [0u32; 2];
+    block_on(reader.unpack_exact_with(&mut values, Endianness::Little)).unwrap();
+    assert!(values == [3, 4]);
+    assert!(block_on(reader.unpack::<u8>()).is_err());
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn futures_async() {
+    use bytepack::futures::{AsyncUnpacker, AsyncPacker};
+    use bytepack::Endianness;
+
+    let mut buffer = Vec::new();
+    block_on(AsyncPacker::pack_with(&mut buffer, 0x0102u16, Endianness::Big)).unwrap();
+    block_on(AsyncPacker::pack_ref(&mut buffer, &[3u8, 4])).unwrap();
+    let mut reader = &buffer[..];
+    let value : u16 = block_on(AsyncUnpacker::unpack_with(&mut reader, Endianness::Big)).unwrap();
+    assert!(value == 0x0102);
+    let mut values = [0u8; 2];
+    block_on(AsyncUnpacker::unpack_exact(&mut reader, &mut values)).unwrap();
+    assert!(values == [3, 4]);
+    assert!(block_on(AsyncUnpacker::unpack::<u8>(&mut reader)).is_err());
+}
+
+#[test]
+fn partial_unpack() {
+    use std::io::{Read, ErrorKind};
+    use bytepack::{PartialUnpack, Endianness};
+
+    // reader returning WouldBlock after every byte
+    struct NonBlocking(Vec<u8>, bool);
+    impl Read for NonBlocking {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(std::io::Error::new(ErrorKind::WouldBlock, "not ready"));
+            }
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0.remove(0);
+            Ok(1)
+        }
+    }
+
+    let mut reader = NonBlocking(vec![0, 0, 1, 2, 0, 0, 0, 3, 4], false);
+    let mut partial = PartialUnpack::<u32>::with_endianness(Endianness::Big);
+    let mut values = Vec::new();
+    let mut would_block = 0;
+    loop {
+        match partial.unpack_from(&mut reader) {
+            Ok(value) => values.push(value),
+            Err(ref e) if e.kind() == ErrorKind::WouldBlock => would_block += 1,
+            Err(e) => {
+                assert!(e.kind() == ErrorKind::UnexpectedEof);
+                break;
+            }
+        }
+    }
+    assert!(values == [0x0102, 3]);
+    assert!(would_block == 10);
+    assert!(partial.filled() == 1);
+}
+
+#[test]
+fn error_details() {
+    use std::io::ErrorKind;
+    use bytepack::{Error, unpack_slice_ref};
+
+    let mut buffer = Cursor::new(vec![1u8, 2, 3, 4, 5, 6]);
+    match Unpacker::unpack_at::<u64>(&mut buffer, 2) {
+        Err(Error::ShortRead { type_name, expected, actual, offset }) => {
+            assert!(type_name == "u64");
+            assert!((expected, actual, offset) == (8, 4, Some(2)));
+        },
+        _ => panic!("expected a short read")
+    }
+    let mut values = Vec::<u32>::new();
+    let e = Unpacker::unpack_to_end(&mut buffer, &mut values).unwrap_err();
+    assert!(e.kind() == ErrorKind::InvalidData);
+    match e {
+        Error::TrailingBytes { size, trailing, .. } => assert!((size, trailing) == (4, 2)),
+        _ => panic!("expected trailing bytes")
+    }
+    let words = [0u32; 2];
+    let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 8) };
+    let e = unpack_slice_ref::<u32>(&bytes[1..5]).unwrap_err();
+    assert!(e.type_name() == Some("u32"));
+    let e : std::io::Error = e.into();
+    assert!(e.kind() == ErrorKind::InvalidData);
+    assert!(matches!(Error::from(e), Error::Misaligned { align: 4, .. }));
+}
+
+#[test]
+fn tracking() {
+    use std::io::{Seek, SeekFrom};
+    use bytepack::{Tracking, BEPacker};
+
+    let mut writer = Tracking::new(Vec::new());
+    BEPacker::pack_all(&mut writer, &[1u32, 2, 3]).unwrap();
+    assert!(writer.position() == 12);
+    let mut reader = Tracking::new(Cursor::new(writer.into_inner()));
+    reader.seek(SeekFrom::Start(4)).unwrap();
+    let e = reader.track(|r| {
+        let values : [u32; 2] = Unpacker::unpack(r)?;
+        Unpacker::unpack::<u16>(r)?;
+        Ok(values)
+    }).unwrap_err();
+    assert!(e.offset() == Some(12));
+    assert!(e.to_string().ends_with("at offset 12"));
+    reader.seek(SeekFrom::Start(10)).unwrap();
+    let e = reader.track(Unpacker::unpack::<u64>).unwrap_err();
+    assert!(e.offset() == Some(10));
+    assert!(reader.position() == 12);
+}
+
+#[test]
+fn counting() {
+    use bytepack::{Counting, NetPacker, NetUnpacker};
+
+    let mut writer = Counting::new(Vec::new());
+    writer.count::<u16, _, _>(|w| NetPacker::pack(w, 3u16)).unwrap();
+    writer.count::<u32, _, _>(|w| NetPacker::pack_all(w, &[1u32, 2, 3])).unwrap();
+    assert!(writer.write_counts().bytes == 14);
+    assert!(writer.type_counts("u32").unwrap().items == 3);
+    let mut reader = Counting::new(&writer.get_ref()[..]);
+    let count = reader.count::<u16, _, _>(NetUnpacker::unpack::<u16>).unwrap();
+    let mut values = vec![0u32; count as usize];
+    reader.count::<u32, _, _>(|r| NetUnpacker::unpack_exact(r, &mut values)).unwrap();
+    assert!(values == [1, 2, 3]);
+    assert!(reader.count::<u32, _, _>(NetUnpacker::unpack::<u32>).is_err());
+    assert!(reader.read_counts().bytes == 14);
+    assert!(reader.type_counts("u32").unwrap() == bytepack::Counts { calls: 2, items: 3, bytes: 12 });
+    assert!(reader.types().count() == 2);
+}
+
+#[test]
+fn progress() {
+    use std::io::Write;
+    use bytepack::Progress;
+
+    let mut steps = Vec::new();
+    {
+        let mut writer = Progress::new(Vec::new(), 8, |done, total| steps.push((done, total)));
+        writer.pack_all(&[1u32; 5]).unwrap();
+        writer.flush().unwrap();
+        assert!(writer.processed() == 20);
+    }
+    assert!(steps == [(8, None), (16, None), (20, None)]);
+    steps.clear();
+    {
+        let data = [0u8; 10];
+        let mut reader = Progress::new(&data[..], 4, |done, total| steps.push((done, total))).with_total(10);
+        let mut values = Vec::<u16>::new();
+        reader.unpack_to_end(&mut values).unwrap();
+    }
+    assert!(steps == [(4, Some(10)), (8, Some(10)), (10, Some(10))]);
+}
+
+#[test]
+fn crc32() {
+    use bytepack::{Crc32Reader, Crc32Writer, LEPacker, LEUnpacker};
+
+    let mut writer = Crc32Writer::new(Vec::new());
+    LEPacker::pack(&mut writer, 0x01020304u32).unwrap();
+    LEPacker::pack_all(&mut writer, &[5u16, 6]).unwrap();
+    let crc = writer.digest();
+    let mut frame = writer.into_inner();
+    LEPacker::pack(&mut frame, crc).unwrap();
+    let mut reader = Crc32Reader::new(&frame[..]);
+    let header : u32 = LEUnpacker::unpack(&mut reader).unwrap();
+    let mut values = [0u16; 2];
+    LEUnpacker::unpack_exact(&mut reader, &mut values).unwrap();
+    let computed = reader.digest();
+    let stored : u32 = LEUnpacker::unpack(&mut reader).unwrap();
+    assert!(header == 0x01020304 && values == [5, 6]);
+    assert!(computed == stored);
+    reader.reset();
+    assert!(reader.digest() == 0);
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn digest_wrappers() {
+    use bytepack::{DigestReader, DigestWriter, BEPacker, BEUnpacker};
+    use sha2::{Sha256, Digest};
+
+    let mut writer = DigestWriter::<_, Sha256>::new(Vec::new());
+    BEPacker::pack_all(&mut writer, &[1u32, 2, 3]).unwrap();
+    let hash = writer.finalize();
+    let payload = writer.into_inner();
+    assert!(hash == Sha256::digest(&payload));
+    let mut reader = DigestReader::<_, Sha256>::new(&payload[..]);
+    let mut values = [0u32; 3];
+    BEUnpacker::unpack_exact(&mut reader, &mut values).unwrap();
+    assert!(values == [1, 2, 3]);
+    assert!(reader.finalize() == hash);
+    assert!(reader.finalize() == Sha256::digest([]));
+}
+
+#[test]
+fn limits() {
+    use bytepack::{Limited, LEUnpacker, Error};
+
+    let data = [1u8, 0, 2, 0, 3, 0];
+    let mut values = vec![7u16];
+    let mut reader = &data[..];
+    match LEUnpacker::unpack_to_end_limited(&mut reader, &mut values, 2) {
+        Err(Error::LimitExceeded { limit: 2, .. }) => {},
+        _ => panic!("expected the limit to be exceeded")
+    }
+    assert!(values == [7]);
+    let mut reader = &data[..];
+    assert!(LEUnpacker::unpack_to_end_limited(&mut reader, &mut values, 3).unwrap() == 3);
+    assert!(values == [7, 1, 2, 3]);
+    let mut reader = &data[..];
+    assert!(LEUnpacker::unpack_n_limited::<u16>(&mut reader, 1000, 3).is_err());
+    assert!(LEUnpacker::unpack_n_limited::<u16>(&mut reader, 3, 3).unwrap() == [1, 2, 3]);
+    let mut reader = Limited::new(&data[..], 4);
+    assert!(LEUnpacker::unpack::<u32>(&mut reader).unwrap() == 0x00020001);
+    let e = LEUnpacker::unpack::<u16>(&mut reader).unwrap_err();
+    assert!(e.offset() == Some(4));
+    let mut reader = Limited::new(&data[..], 6);
+    let mut values = Vec::<u16>::new();
+    LEUnpacker::unpack_to_end(&mut reader, &mut values).unwrap();
+    assert!(values == [1, 2, 3] && reader.remaining() == 0);
+}
+
+#[test]
+fn trailing_policy() {
+    use bytepack::{BEUnpacker, TrailingPolicy, Error};
+
+    let data = [0u8, 1, 0, 2, 0xAA, 0xBB, 0xCC];
+    let mut values = vec![9u32];
+    let mut reader = &data[..];
+    match BEUnpacker::unpack_to_end_with_trailing(&mut reader, &mut values, TrailingPolicy::Error) {
+        Err(Error::TrailingBytes { trailing: 3, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    assert!(values == [9]);
+    let mut values = Vec::<u16>::new();
+    let mut reader = &data[..];
+    let end = BEUnpacker::unpack_to_end_with_trailing(&mut reader, &mut values, TrailingPolicy::TruncateAndReport).unwrap();
+    assert!(end.count == 3 && end.trailing_len == 1 && end.trailing.is_empty());
+    assert!(values == [1, 2, 0xAABB]);
+    let mut values = vec![7u32];
+    let mut reader = &data[..];
+    let end = BEUnpacker::unpack_to_end_with_trailing(&mut reader, &mut values, TrailingPolicy::ReturnTrailingBytes).unwrap();
+    assert!(end.count == 1 && end.trailing == [0xAA, 0xBB, 0xCC]);
+    assert!(values == [7, 0x00010002]);
+    let mut data = Vec::new();
+    for i in 0..5000u32 {
+        data.extend_from_slice(&i.to_be_bytes());
+    }
+    data.push(0xAA);
+    let mut values = Vec::<u32>::with_capacity(1 << 20);
+    let mut reader = &data[..];
+    let end = BEUnpacker::unpack_to_end_with_trailing(&mut reader, &mut values, TrailingPolicy::ReturnTrailingBytes).unwrap();
+    assert!(end.count == 5000 && end.trailing == [0xAA]);
+    assert!(values.iter().enumerate().all(|(i, &value)| value == i as u32));
+}
+
+#[test]
+fn manual_packed() {
+    use bytepack::{BEUnpacker, BEPacker, EndianSwitch};
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[repr(transparent)]
+    struct Port(u16);
+
+    impl EndianSwitch for Port {
+        fn switch_endianness(&mut self) {
+            self.0.switch_endianness();
+        }
+    }
+
+    unsafe impl Packed for Port {}
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[repr(transparent)]
+    struct Id([u8; 4]);
+
+    impl EndianSwitch for Id {
+        fn switch_endianness(&mut self) {}
+    }
+
+    unsafe impl Packed for Id {}
+
+    let mut buffer = Vec::new();
+    BEPacker::pack(&mut buffer, Port(8080)).unwrap();
+    assert!(buffer == [0x1F, 0x90]);
+    let port : Port = BEUnpacker::unpack(&mut &buffer[..]).unwrap();
+    assert!(port == Port(8080));
+    let id : Id = BEUnpacker::unpack(&mut &[1u8, 2, 3, 4][..]).unwrap();
+    assert!(id == Id([1, 2, 3, 4]));
+}
+
+#[test]
+fn packed_size() {
+    use bytepack::{packed_len, Be};
+
+    fn size<T: Packed>() -> usize {
+        T::SIZE
+    }
+
+    assert!(size::<u8>() == 1 && size::<f64>() == 8 && size::<[u16; 5]>() == 10);
+    assert!(size::<Be<u32>>() == 4);
+    assert!(size::<Foo>() == 8 && size::<Tagged>() == 5 && size::<Mixed>() == 6);
+    assert!(0x1234u16.packed_size() == 2);
+    assert!(packed_len(&[0u32; 7]) == 28);
+    assert!(packed_len::<u64>(&[]) == 0);
+}
+
+#[test]
+fn bytes_conversions() {
+    use bytepack::Error;
+
+    assert!(0x01020304u32.to_le_bytes_vec() == [4, 3, 2, 1]);
+    assert!(0x01020304u32.to_be_bytes_vec() == [1, 2, 3, 4]);
+    assert!(1.5f64.to_ne_bytes_vec() == 1.5f64.to_ne_bytes());
+    assert!(<[u16; 2]>::from_le_bytes_slice(&[1, 0, 2, 0]).unwrap() == [1, 2]);
+    assert!(i16::from_be_bytes_slice(&[0xFF, 0xFE]).unwrap() == -2);
+    assert!(f32::from_ne_bytes_slice(&2.5f32.to_ne_bytes()).unwrap() == 2.5);
+    match u16::from_le_bytes_slice(&[1, 2, 3]) {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    match u64::from_le_bytes_slice(&[1, 2, 3]) {
+        Err(Error::ShortRead { expected: 8, actual: 3, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck_interop() {
+    use bytemuck::{Pod, Zeroable};
+    use bytepack::{PodPacked, Le, Be, LEPacker, BEUnpacker};
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[repr(C)]
+    struct Sample {
+        time: Le<u32>,
+        value: Be<u16>,
+        flags: [u8; 2]
+    }
+
+    unsafe impl Zeroable for Sample {}
+    unsafe impl Pod for Sample {}
+
+    packed_pod!(Sample);
+
+    let sample = Sample { time: Le::new(7), value: Be::new(0x1234), flags: [1, 2] };
+    let mut buffer = Vec::new();
+    LEPacker::pack(&mut buffer, sample).unwrap();
+    LEPacker::pack(&mut buffer, PodPacked(sample)).unwrap();
+    assert!(buffer[..8] == [7, 0, 0, 0, 0x12, 0x34, 1, 2]);
+    assert!(buffer[..8] == *bytemuck::bytes_of(&sample));
+    let mut reader = &buffer[..];
+    let first : Sample = BEUnpacker::unpack(&mut reader).unwrap();
+    let second : PodPacked<Sample> = BEUnpacker::unpack(&mut reader).unwrap();
+    assert!(first == sample && second.0 == sample);
+    let times = [Le::<u32>::new(7), Le::new(8)];
+    assert!(bytemuck::cast_slice::<Le<u32>, u8>(&times) == [7, 0, 0, 0, 8, 0, 0, 0]);
+
+    #[derive(Packed, Clone, Copy)]
+    #[packed(bytemuck)]
+    #[repr(C)]
+    struct Point {
+        x: Be<i16>,
+        y: Be<i16>
+    }
+
+    let points = [Point { x: Be::new(1), y: Be::new(-2) }];
+    assert!(bytemuck::cast_slice::<Point, u8>(&points) == [0, 1, 0xff, 0xfe]);
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn zerocopy_interop() {
+    use zerocopy::AsBytes;
+    use zerocopy::byteorder::{U16, U64, BigEndian, LittleEndian};
+    use bytepack::{ZerocopyPacked, LEUnpacker, BEPacker};
+
+    let mut buffer = Vec::new();
+    BEPacker::pack(&mut buffer, ZerocopyPacked(U16::<LittleEndian>::new(0x1234))).unwrap();
+    BEPacker::pack_all(&mut buffer, &[ZerocopyPacked(U64::<BigEndian>::new(5))]).unwrap();
+    assert!(buffer == [0x34, 0x12, 0, 0, 0, 0, 0, 0, 0, 5]);
+    let mut reader = &buffer[..];
+    let first : ZerocopyPacked<U16<LittleEndian>> = LEUnpacker::unpack(&mut reader).unwrap();
+    let second : ZerocopyPacked<U64<BigEndian>> = LEUnpacker::unpack(&mut reader).unwrap();
+    assert!(first.0.get() == 0x1234 && second.0.get() == 5);
+    assert!(second.0.as_bytes() == &buffer[2..]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_layout() {
+    use bytepack::{serialize_into, deserialize_from, Endianness, Error};
+
+    let value = (true, 'é', Some(7u32), vec![-1i16, 2], String::from("ok"), None::<u8>);
+    let mut buffer = Vec::new();
+    serialize_into(&mut buffer, &value, Endianness::Little).unwrap();
+    assert!(buffer == [1, 0xE9, 0, 0, 0, 1, 7, 0, 0, 0, 2, 0, 0, 0, 0xFF, 0xFF, 2, 0, 2, 0, 0, 0, b'o', b'k', 0]);
+    let copy : (bool, char, Option<u32>, Vec<i16>, String, Option<u8>) = deserialize_from(&buffer[..], Endianness::Little).unwrap();
+    assert!(copy == value);
+    let mut buffer = Vec::new();
+    serialize_into(&mut buffer, &(0x0102u16, 3u128), Endianness::Big).unwrap();
+    assert!(buffer[..2] == [1, 2] && buffer[17] == 3);
+    match deserialize_from::<_, bool>(&[2u8][..], Endianness::Big) {
+        Err(Error::InvalidValue { type_name: "bool", .. }) => {},
+        _ => panic!("expected an invalid bool")
+    }
+    match deserialize_from::<_, String>(&[0u8, 0, 0, 9, b'a'][..], Endianness::Big) {
+        Err(Error::ShortRead { expected: 9, actual: 1, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+#[cfg(feature = "byteorder")]
+#[test]
+fn byteorder_interop() {
+    use byteorder::{ByteOrder, BigEndian, LittleEndian};
+    use bytepack::{ByteOrderUnpacker, ByteOrderPacker};
+
+    fn round_trip<B: ByteOrder>(values: &[u32]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        buffer.pack_with_order::<u16, B>(values.len() as u16).unwrap();
+        buffer.pack_all_with_order::<u32, B>(values).unwrap();
+        let mut reader = &buffer[..];
+        let len = reader.unpack_with_order::<u16, B>().unwrap();
+        let mut copy = vec![0u32; len as usize];
+        reader.unpack_exact_with_order::<u32, B>(&mut copy).unwrap();
+        assert!(copy == values);
+        assert!(B::read_u32(&buffer[2..]) == values[0]);
+        buffer
+    }
+
+    assert!(round_trip::<BigEndian>(&[1, 2]) == [0, 2, 0, 0, 0, 1, 0, 0, 0, 2]);
+    assert!(round_trip::<LittleEndian>(&[1]) == [1, 0, 1, 0, 0, 0]);
+}
+
+#[test]
+fn validated() {
+    use bytepack::{EndianSwitch, PackedValidate, LEUnpacker, NetUnpacker, Error};
+
+    #[derive(Clone, Copy)]
+    #[repr(transparent)]
+    struct Percent(u8);
+
+    impl EndianSwitch for Percent {
+        fn switch_endianness(&mut self) {}
+    }
+
+    unsafe impl Packed for Percent {}
+
+    impl PackedValidate for Percent {
+        fn validate(&self) -> Result<(), String> {
+            if self.0 > 100 {
+                return Err(format!("{} is above 100", self.0));
+            }
+            Ok(())
+        }
+    }
+
+    let mut reader = &[50u8, 101][..];
+    assert!(LEUnpacker::unpack_validated::<Percent>(&mut reader).unwrap().0 == 50);
+    match LEUnpacker::unpack_validated::<Percent>(&mut reader) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "101 is above 100"),
+        _ => panic!("expected an invalid value")
+    }
+    let mut reader = &[10u8, 20, 200][..];
+    match NetUnpacker::unpack_validated::<[Percent; 3]>(&mut reader) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "element 2: 200 is above 100"),
+        _ => panic!("expected an invalid value")
+    }
+    let mut reader = &[0u8, 1][..];
+    assert!(NetUnpacker::unpack_validated::<u16>(&mut reader).unwrap() == 1);
+
+    #[derive(Packed, PackedValidate)]
+    #[repr(C, packed)]
+    struct Reading {
+        sensor: Percent,
+        #[validate(non_zero, range = "1..=1000")]
+        scale: u16
+    }
+
+    let mut reader = &[10u8, 0, 2, 120, 0, 2, 10, 0, 0][..];
+    let reading = NetUnpacker::unpack_validated::<Reading>(&mut reader).unwrap();
+    assert!(reading.sensor.0 == 10 && {reading.scale} == 2);
+    match NetUnpacker::unpack_validated::<Reading>(&mut reader) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "field sensor: 120 is above 100"),
+        _ => panic!("expected an invalid value")
+    }
+    match NetUnpacker::unpack_validated::<Reading>(&mut reader) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "field scale: must not be zero"),
+        _ => panic!("expected an invalid value")
+    }
+}
+
+#[test]
+fn strict_floats() {
+    use std::io::ErrorKind;
+    use bytepack::{Finite, BEUnpacker, BEPacker};
+
+    let mut buffer = Vec::new();
+    BEPacker::pack_all(&mut buffer, &[2.5f64, f64::INFINITY]).unwrap();
+    let mut reader = &buffer[..];
+    let value : Finite<f64> = BEUnpacker::unpack_validated(&mut reader).unwrap();
+    assert!(value.get() == 2.5);
+    let e = BEUnpacker::unpack_validated::<Finite<f64>>(&mut reader).unwrap_err();
+    assert!(e.kind() == ErrorKind::InvalidData);
+    let mut reader = &buffer[..];
+    let mut values = [Finite::new(0f64).unwrap(); 2];
+    assert!(BEUnpacker::unpack_exact_validated(&mut reader, &mut values).is_err());
+    let mut reader = &buffer[..8];
+    BEUnpacker::unpack_exact_validated(&mut reader, &mut values[..1]).unwrap();
+    assert!(values[0].get() == 2.5);
+    assert!(Finite::new(f32::NAN).is_none() && Finite::new(-1f32).is_some());
+}
+
+#[test]
+fn strided_packing() {
+    use bytepack::{LEPacker, NetPacker};
+
+    struct Point {
+        x: u16,
+        _y: u16
+    }
+
+    let xyz = [1u16, 2, 3, 4, 5, 6, 7];
+    let mut buffer = Vec::new();
+    LEPacker::pack_strided(&mut buffer, &xyz[..], 1, 3).unwrap();
+    assert!(buffer == [2, 0, 5, 0]);
+    buffer.clear();
+    NetPacker::pack_strided(&mut buffer, &xyz[..], 9, 3).unwrap();
+    assert!(buffer.is_empty());
+    assert!(NetPacker::pack_strided(&mut buffer, &xyz[..], 0, 0).is_err());
+    let points = [Point { x: 1, _y: 2 }, Point { x: 3, _y: 4 }];
+    NetPacker::pack_field(&mut buffer, &points[..], |p| &p.x).unwrap();
+    assert!(buffer == [0, 1, 0, 3]);
+}
+
+#[test]
+fn strided_unpacking() {
+    use bytepack::{BEUnpacker, Unpacker};
+
+    let mut reader = &[0u8, 1, 0, 2, 0, 3][..];
+    let mut planar = [0u16; 7];
+    BEUnpacker::unpack_strided(&mut reader, &mut planar[..], 1, 3).unwrap();
+    assert!(planar == [0, 1, 0, 0, 2, 0, 0]);
+    assert!(reader.len() == 2);
+    let mut empty = &[][..];
+    Unpacker::unpack_strided(&mut empty, &mut planar[..], 7, 1).unwrap();
+    assert!(Unpacker::unpack_strided(&mut reader, &mut planar[..], 0, 0).is_err());
+    assert!(Unpacker::unpack_strided(&mut reader, &mut planar[..], 0, 2).is_err());
+
+    let values : Vec<u32> = (0..5000).collect();
+    let mut buffer = Vec::new();
+    Packer::pack_all(&mut buffer, &values[..]).unwrap();
+    let mut copy = vec![0u32; 5000];
+    Unpacker::unpack_strided(&mut &buffer[..], &mut copy[..], 0, 1).unwrap();
+    assert!(copy == values);
+    let mut interleaved = vec![0u32; 10000];
+    Unpacker::unpack_strided(&mut &buffer[..], &mut interleaved[..], 1, 2).unwrap();
+    assert!(interleaved.chunks(2).zip(values.iter()).all(|(pair, &v)| pair == [0, v]));
+}
+
+#[test]
+fn interleaving() {
+    use bytepack::{LEPacker, LEUnpacker};
+
+    let left = [1i16, -2, 3];
+    let right = [4i16, 5, -6];
+    let mut buffer = Vec::new();
+    LEPacker::pack_interleaved(&mut buffer, &[&left[..], &right[..]]).unwrap();
+    assert!(buffer.len() == 12 && buffer[2..4] == [4, 0]);
+    let mut reader = &buffer[..];
+    let channels = LEUnpacker::unpack_deinterleaved::<i16>(&mut reader, 2, 3).unwrap();
+    assert!(channels == [left, right]);
+    let mut reader = &buffer[..];
+    let channels = LEUnpacker::unpack_deinterleaved::<i16>(&mut reader, 3, 2).unwrap();
+    assert!(channels == [[1, 5], [4, 3], [-2, -6]]);
+    assert!(LEPacker::pack_interleaved(&mut Vec::new(), &[&left[..], &right[1..]]).is_err());
+    assert!(LEUnpacker::unpack_deinterleaved::<i16>(&mut reader, 2, usize::MAX).is_err());
+}
+
+#[test]
+fn row_padding() {
+    use bytepack::{BEPacker, BEUnpacker};
+
+    let pixels = [1u16, 2, 3, 4, 5, 6];
+    let mut buffer = Vec::new();
+    BEPacker::pack_rows(&mut buffer, &pixels[..], 3, 8).unwrap();
+    assert!(buffer == [0, 1, 0, 2, 0, 3, 0, 0, 0, 4, 0, 5, 0, 6, 0, 0]);
+    let mut reader = &buffer[..];
+    let mut rows = [0u16; 6];
+    BEUnpacker::unpack_rows(&mut reader, &mut rows[..], 3, 8).unwrap();
+    assert!(rows == pixels && reader.is_empty());
+    let mut reader = &buffer[..];
+    assert!(BEUnpacker::unpack_rows(&mut reader, &mut rows[..], 4, 8).is_err());
+    assert!(BEUnpacker::unpack_rows(&mut reader, &mut rows[..], 3, 4).is_err());
+    assert!(BEUnpacker::unpack_rows(&mut reader, &mut rows[..], 2, 6).is_err());
+}
+
+#[test]
+fn transposition() {
+    use bytepack::{Packer, Unpacker};
+
+    let column_major = [1u32, 4, 2, 5, 3, 6];
+    let mut buffer = Vec::new();
+    Packer::pack_all(&mut buffer, &column_major[..]).unwrap();
+    let mut reader = &buffer[..];
+    let mut row_major = [0u32; 6];
+    Unpacker::unpack_transposed(&mut reader, 2, 3, &mut row_major[..]).unwrap();
+    assert!(row_major == [1, 2, 3, 4, 5, 6]);
+    let mut transposed = Vec::new();
+    Packer::pack_transposed(&mut transposed, 2, 3, &row_major[..]).unwrap();
+    assert!(transposed == buffer);
+    assert!(Packer::pack_transposed(&mut transposed, 2, 2, &row_major[..]).is_err());
+
+    let row_major : Vec<u32> = (0..6000).collect();
+    let mut column_major = Vec::new();
+    Packer::pack_transposed(&mut column_major, 3000, 2, &row_major[..]).unwrap();
+    let mut copy = vec![0u32; 6000];
+    Unpacker::unpack_transposed(&mut &column_major[..], 3000, 2, &mut copy[..]).unwrap();
+    assert!(copy == row_major);
+}
+
+#[test]
+fn chunks() {
+    use bytepack::{Error, NetUnpacker};
+
+    let mut reader = &[0u8, 1, 0, 2, 0, 3, 0, 4, 0, 5][..];
+    let mut chunks = NetUnpacker::unpack_chunks::<u16>(&mut reader, 2);
+    let mut lengths = Vec::new();
+    let mut sum = 0;
+    while let Some(chunk) = chunks.next_chunk() {
+        let chunk = chunk.unwrap();
+        lengths.push(chunk.len());
+        sum += chunk.iter().sum::<u16>();
+    }
+    assert!(lengths == [2, 2, 1] && sum == 15);
+    let mut reader = &[0u8, 1, 0][..];
+    let mut chunks = NetUnpacker::unpack_chunks::<u16>(&mut reader, 4);
+    match chunks.next_chunk() {
+        Some(Err(Error::ShortRead { expected: 2, actual: 1, .. })) => {},
+        _ => panic!("expected a short read")
+    }
+    assert!(chunks.next_chunk().is_none());
+}
+
+#[test]
+fn packed_reader() {
+    use std::io::Read;
+    use bytepack::{PackedReader, Endianness, Unpacker};
+
+    let values = [0x0102u16, 0x0304, 0x0506];
+    let mut reader = PackedReader::with_endianness(&values[..], Endianness::Big);
+    let mut bytes = [0u8; 3];
+    reader.read_exact(&mut bytes).unwrap();
+    assert!(bytes == [1, 2, 3] && reader.remaining() == 3);
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert!(rest == [4, 5, 6]);
+    let mut reader = PackedReader::new(&values[..]);
+    assert!(Unpacker::unpack_n::<u16>(&mut reader, 3).unwrap() == values);
+    assert!(reader.remaining() == 0);
+}
+
+#[test]
+fn packed_sink() {
+    use std::io::Write;
+    use bytepack::{PackedSink, Endianness, Error, BEPacker};
+
+    let mut sink = PackedSink::<u32>::with_endianness(Endianness::Big);
+    sink.write_all(&[0, 0]).unwrap();
+    sink.write_all(&[0, 1, 0, 0, 0, 2, 0]).unwrap();
+    assert!(sink.values() == [1, 2] && sink.partial_len() == 1);
+    match sink.into_values() {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    let mut sink = PackedSink::<u32>::with_endianness(Endianness::Big);
+    BEPacker::pack_all(&mut sink, &[3u32, 4, 5]).unwrap();
+    assert!(sink.into_values().unwrap() == [3, 4, 5]);
+}
+
+#[test]
+fn byte_order_adapter() {
+    use std::io::{Read, Write};
+    use bytepack::{ByteOrderAdapter, Endianness, Packer, Unpacker};
+
+    let mut writer = ByteOrderAdapter::<_, u16>::new(Vec::new(), Endianness::Big);
+    Packer::pack_all(&mut writer, &[0x0102u16, 0x0304]).unwrap();
+    writer.write_all(&0x0506u16.to_ne_bytes()[..1]).unwrap();
+    assert!(writer.get_ref()[..] == [1, 2, 3, 4]);
+    assert!(writer.flush().is_err());
+    writer.write_all(&0x0506u16.to_ne_bytes()[1..]).unwrap();
+    writer.flush().unwrap();
+    let data = writer.into_inner();
+    assert!(data == [1, 2, 3, 4, 5, 6]);
+    let mut reader = ByteOrderAdapter::<_, u16>::new(&data[..], Endianness::Big);
+    assert!(Unpacker::unpack_n::<u16>(&mut reader, 3).unwrap() == [0x0102, 0x0304, 0x0506]);
+    assert!(reader.read(&mut [0u8; 2]).unwrap() == 0);
+    let mut reader = ByteOrderAdapter::<_, u16>::new(&data[..5], Endianness::Big);
+    assert!(Unpacker::unpack_n::<u16>(&mut reader, 3).is_err());
+}
+
+#[test]
+fn tlv() {
+    use bytepack::{LEPacker, LEUnpacker, Error};
+
+    let mut buffer = Vec::new();
+    LEPacker::pack_tlv::<u8, u16>(&mut buffer, 7, b"abc").unwrap();
+    LEPacker::pack_tlv_value::<u16, u8, u32>(&mut buffer, 9, &0x01020304).unwrap();
+    assert!(buffer[..6] == [7, 3, 0, b'a', b'b', b'c'] && buffer[6..] == [9, 0, 4, 4, 3, 2, 1]);
+    let mut reader = &buffer[..];
+    assert!(LEUnpacker::unpack_tlv::<u8, u16>(&mut reader).unwrap() == (7, b"abc".to_vec()));
+    assert!(LEUnpacker::unpack_tlv_value::<u16, u8, u32>(&mut reader).unwrap() == (9, 0x01020304));
+    let mut reader = &buffer[..];
+    match LEUnpacker::unpack_tlv_value::<u8, u16, u16>(&mut reader) {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => assert!(reader[0] == 9),
+        _ => panic!("expected trailing bytes")
+    }
+    let mut reader = &[1u8, 0xFF, 0xFF, 0xFF, 0x7F, 0][..];
+    assert!(LEUnpacker::unpack_tlv::<u8, u32>(&mut reader).is_err());
+    assert!(LEPacker::pack_tlv::<u8, u8>(&mut buffer, 0, &[0u8; 256]).is_err());
+}
+
+#[test]
+fn framing() {
+    use bytepack::{NetPacker, NetUnpacker, Error};
+
+    let mut buffer = Vec::new();
+    NetPacker::pack_framed::<u16, u16>(&mut buffer, &[1, 2]).unwrap();
+    NetPacker::pack_framed::<u8, u32>(&mut buffer, &[3]).unwrap();
+    assert!(buffer == [0, 4, 0, 1, 0, 2, 4, 0, 0, 0, 3]);
+    let mut reader = &buffer[..];
+    assert!(NetUnpacker::unpack_framed::<u16, u16>(&mut reader, 4).unwrap() == [1, 2]);
+    assert!(NetUnpacker::unpack_framed_value::<u8, u32>(&mut reader).unwrap() == 3);
+    let mut reader = &buffer[..];
+    match NetUnpacker::unpack_framed::<u16, u16>(&mut reader, 3) {
+        Err(Error::LimitExceeded { limit: 3, .. }) => assert!(reader.len() == 9),
+        _ => panic!("expected an exceeded limit")
+    }
+    let mut reader = &[0u8, 3, 0, 1, 2, 7][..];
+    match NetUnpacker::unpack_framed::<u16, u16>(&mut reader, 4) {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => assert!(reader == [7]),
+        _ => panic!("expected trailing bytes")
+    }
+    let mut reader = &[2u8, 0, 1, 7][..];
+    match NetUnpacker::unpack_framed_value::<u8, u32>(&mut reader) {
+        Err(Error::ShortRead { expected: 4, actual: 2, .. }) => assert!(reader == [7]),
+        _ => panic!("expected a short read")
+    }
+    let mut reader = &[6u8, 0, 0, 0, 3, 8, 9, 7][..];
+    match NetUnpacker::unpack_framed_value::<u8, u32>(&mut reader) {
+        Err(Error::TrailingBytes { size: 4, trailing: 2, .. }) => assert!(reader == [7]),
+        _ => panic!("expected trailing bytes")
+    }
+    assert!(NetPacker::pack_framed::<u8, u32>(&mut buffer, &[0; 64]).is_err());
+}
+
+#[test]
+fn magic() {
+    use bytepack::{BEUnpacker, LEUnpacker, Error};
+
+    let mut reader = &[b'R', b'I', b'F', b'F', 0xCA, 0xFE, 0xBA, 0xBE][..];
+    BEUnpacker::expect_magic(&mut reader, b"RIFF").unwrap();
+    BEUnpacker::expect_magic_value(&mut reader, 0xCAFEBABEu32).unwrap();
+    let mut reader = &[b'R', b'I', b'F', b'X', 0xCA, 0xFE, 0xBA, 0xBE][..];
+    match LEUnpacker::expect_magic(&mut reader, b"RIFF") {
+        Err(Error::InvalidValue { reason, .. }) => {
+            assert!(reason == "expected magic [52, 49, 46, 46], found [52, 49, 46, 58]")
+        },
+        _ => panic!("expected an invalid value")
+    }
+    match LEUnpacker::expect_magic_value(&mut reader, 0xCAFEBABEu32) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "expected magic CAFEBABE, found BEBAFECA"),
+        _ => panic!("expected an invalid value")
+    }
+    assert!(LEUnpacker::expect_magic(&mut reader, b"RIFF").is_err());
+}
+
+#[test]
+fn alignment_padding() {
+    use bytepack::{Tracking, LEPacker, LEUnpacker};
+
+    let mut writer = Tracking::with_position(Vec::new(), 6);
+    writer.pack_align_to(4).unwrap();
+    LEPacker::pack(&mut writer, 1u32).unwrap();
+    writer.pack_align_to(4).unwrap();
+    LEPacker::pack(&mut writer, 2u16).unwrap();
+    writer.pack_align_to(16).unwrap();
+    assert!(writer.position() == 16 && writer.get_ref().len() == 10);
+    assert!(writer.pack_align_to(0).is_err());
+    let data = writer.into_inner();
+    let mut reader = Tracking::with_position(&data[..], 6);
+    reader.skip_to_alignment(4).unwrap();
+    assert!(LEUnpacker::unpack::<u32>(&mut reader).unwrap() == 1);
+    reader.skip_to_alignment(4).unwrap();
+    assert!(LEUnpacker::unpack::<u16>(&mut reader).unwrap() == 2);
+    reader.skip_to_alignment(16).unwrap();
+    assert!(reader.position() == 16 && reader.skip_to_alignment(32).is_err());
+}
+
+#[test]
+fn checksum_trailer() {
+    use bytepack::{BEPacker, BEUnpacker, Error};
+
+    let mut buffer = Vec::new();
+    BEPacker::pack_with_checksum(&mut buffer, |w| BEPacker::pack_all(w, &b"123456789"[..])).unwrap();
+    assert!(buffer[9..] == [0xCB, 0xF4, 0x39, 0x26]);
+    let mut reader = &buffer[..];
+    let payload = BEUnpacker::unpack_with_checksum(&mut reader, |r| BEUnpacker::unpack::<[u8; 9]>(r)).unwrap();
+    assert!(&payload == b"123456789" && reader.is_empty());
+    buffer[0] = b'0';
+    let mut reader = &buffer[..];
+    match BEUnpacker::unpack_with_checksum(&mut reader, |r| BEUnpacker::unpack::<[u8; 9]>(r)) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason.starts_with("the checksum CBF43926")),
+        _ => panic!("expected an invalid value")
+    }
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn deflate_wrappers() {
+    use bytepack::{pack_deflated, unpack_deflated, Error, BEPacker, BEUnpacker};
+    use flate2::Compression;
+
+    let values = [7u32; 100];
+    let ((), mut compressed) = pack_deflated(Vec::new(), Compression::best(), |w| {
+        BEPacker::pack_all(w, &values[..])
+    }).unwrap();
+    compressed.push(42);
+    let mut reader = &compressed[..];
+    let unpacked = unpack_deflated(&mut reader, |r| BEUnpacker::unpack_n::<u32>(r, 100)).unwrap();
+    assert!(unpacked[..] == values[..] && reader == [42]);
+    match unpack_deflated(&compressed[..], |r| BEUnpacker::unpack_n::<u32>(r, 99)) {
+        Err(Error::TrailingBytes { trailing: 4, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    assert!(unpack_deflated(&compressed[..], |r| BEUnpacker::unpack_n::<u32>(r, 101)).is_err());
+}
+
+#[test]
+fn transform_wrapper() {
+    use std::io::Read;
+    use bytepack::{Transform, BEPacker, BEUnpacker};
+
+    let add_offset = |offset: u64, bytes: &mut [u8]| {
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = byte.wrapping_add((offset as usize + i) as u8);
+        }
+    };
+    let mut writer = Transform::new(Vec::new(), add_offset);
+    BEPacker::pack_all(&mut writer, &[0u16, 0]).unwrap();
+    BEPacker::pack(&mut writer, 0u8).unwrap();
+    assert!(writer.position() == 5 && writer.into_inner() == [0, 1, 2, 3, 4]);
+    let sub_offset = |offset: u64, bytes: &mut [u8]| {
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = byte.wrapping_sub((offset as usize + i) as u8);
+        }
+    };
+    let mut reader = Transform::new(&[1u8, 3, 5, 7][..], sub_offset);
+    let mut first = [0u8; 1];
+    reader.read_exact(&mut first).unwrap();
+    assert!(first == [1] && BEUnpacker::unpack::<[u8; 3]>(&mut reader).unwrap() == [2, 3, 4]);
+}
+
+#[test]
+fn ip_addresses() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use bytepack::{PackedIpv4, PackedIpv6, LEPacker, LEUnpacker, BEUnpacker};
+
+    let v4 = Ipv4Addr::new(10, 0, 0, 1);
+    let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let mut buffer = Vec::new();
+    LEPacker::pack(&mut buffer, PackedIpv4::from(v4)).unwrap();
+    LEPacker::pack(&mut buffer, PackedIpv6::from(v6)).unwrap();
+    assert!(buffer[..4] == [10, 0, 0, 1] && buffer[4..6] == [0x20, 0x01] && buffer.len() == 20);
+    let mut reader = &buffer[..];
+    assert!(Ipv4Addr::from(LEUnpacker::unpack::<PackedIpv4>(&mut reader).unwrap()) == v4);
+    assert!(BEUnpacker::unpack::<PackedIpv6>(&mut reader).unwrap().get() == v6);
+    assert!(format!("{}", PackedIpv4::new(v4)) == "10.0.0.1");
+}
+
+#[test]
+fn fixed_point() {
+    use bytepack::{Fixed, Q15, Q31, BEPacker, BEUnpacker};
+
+    let mut buffer = Vec::new();
+    BEPacker::pack(&mut buffer, Q15::from_f32(0.25)).unwrap();
+    BEPacker::pack(&mut buffer, Q31::from_f64(-1.0)).unwrap();
+    BEPacker::pack(&mut buffer, Fixed::<u16, 8>::from_f64(300.0)).unwrap();
+    assert!(buffer == [0x20, 0, 0x80, 0, 0, 0, 0xFF, 0xFF]);
+    let mut reader = &buffer[..];
+    assert!(BEUnpacker::unpack::<Q15>(&mut reader).unwrap().to_f32() == 0.25);
+    assert!(BEUnpacker::unpack::<Q31>(&mut reader).unwrap().to_bits() == i32::MIN);
+    assert!(BEUnpacker::unpack::<Fixed<u16, 8>>(&mut reader).unwrap().to_f64() == 65535.0 / 256.0);
+    assert!(Q15::from_f32(1.0).to_bits() == i16::MAX && Q15::from_bits(-16384).to_f64() == -0.5);
+}
+
+#[test]
+fn converted_samples() {
+    use bytepack::{LEPacker, LEUnpacker};
+
+    let samples = [0.5f32, -1.0, 0.0];
+    let mut buffer = Vec::new();
+    LEPacker::pack_converted(&mut buffer, &samples[..], |&s| (s * 32768.) as i16).unwrap();
+    assert!(buffer == [0, 0x40, 0, 0x80, 0, 0]);
+    let mut reader = &buffer[..];
+    let mut normalized = [1f32; 3];
+    LEUnpacker::unpack_converted(&mut reader, &mut normalized[..], |s: i16| s as f32 / 32768.).unwrap();
+    assert!(normalized == samples);
+    let mut reader = &buffer[..5];
+    assert!(LEUnpacker::unpack_converted(&mut reader, &mut normalized[..], |s: i16| s as f32).is_err());
+}
+
+#[test]
+fn integer_conversions() {
+    use bytepack::{Overflow, Error, BEUnpacker};
+
+    let buffer = [0x01u8, 0x2C, 0xFF, 0xFF, 0x00, 0x05];
+    let mut reader = &buffer[..];
+    assert!(BEUnpacker::unpack_as::<u16, u64>(&mut reader, Overflow::Error).unwrap() == 300);
+    assert!(BEUnpacker::unpack_as::<i16, u8>(&mut reader, Overflow::Saturate).unwrap() == 0);
+    assert!(BEUnpacker::unpack_as::<u16, i8>(&mut reader, Overflow::Wrap).unwrap() == 5);
+    let mut reader = &buffer[..];
+    assert!(BEUnpacker::unpack_as::<u16, u8>(&mut reader, Overflow::Error).is_err());
+    let mut reader = &buffer[..];
+    let mut values = [0i8; 3];
+    match BEUnpacker::unpack_exact_as::<u16, i8>(&mut reader, &mut values[..], Overflow::Error) {
+        Err(Error::InvalidValue { reason, .. }) => assert!(reason == "value 0: 300 is out of range"),
+        _ => panic!("expected an invalid value")
+    }
+    assert!(values == [0, 0, 5]);
+    let mut reader = &buffer[..];
+    BEUnpacker::unpack_exact_as::<i16, i8>(&mut reader, &mut values[..], Overflow::Saturate).unwrap();
+    assert!(values == [127, -1, 5]);
+}
+
+#[test]
+fn size_planning() {
+    use bytepack::{packed_size_of_val, packed_size_of_iter, Ordered, LittleEndian};
+
+    assert!(packed_size_of_val(&[0u16; 3]) == 6);
+    assert!(packed_size_of_val(&Ordered::<u64, LittleEndian>::new(1)) == 8);
+    assert!(packed_size_of_iter((0..10u32).map(|i| i * 2)) == 40);
+    assert!(packed_size_of_iter((0..10u32).filter(|i| i % 2 == 0)) == 20);
+    assert!(packed_size_of_iter(Vec::<u8>::new()) == 0);
+}
+
+#[test]
+fn scratch_buffer() {
+    use bytepack::{ScratchBuffer, BEPacker, LEPacker, BEUnpacker};
+
+    #[repr(align(32))]
+    #[derive(Clone, Copy)]
+    struct Wide(#[allow(dead_code)] [u8; 32]);
+    impl bytepack::EndianSwitch for Wide {
+        fn switch_endianness(&mut self) {}
+    }
+    unsafe impl Packed for Wide {}
+
+    let mut scratch = ScratchBuffer::with_capacity(16);
+    let mut buffer = Vec::new();
+    BEPacker::pack_all_with_scratch(&mut buffer, &[1u16, 2], &mut scratch).unwrap();
+    BEPacker::pack_iter_with_scratch(&mut buffer, (3..5u32).map(|i| i * 2), &mut scratch).unwrap();
+    assert!(buffer == [0, 1, 0, 2, 0, 0, 0, 6, 0, 0, 0, 8]);
+    LEPacker::pack_all_with_scratch(&mut buffer, &[Wide([7; 32]); 3], &mut scratch).unwrap();
+    BEPacker::pack_all_with_scratch(&mut buffer, &[Wide([9; 32]); 3], &mut scratch).unwrap();
+    assert!(buffer.len() == 12 + 192 && buffer[12] == 7 && buffer[200] == 9);
+    assert!(scratch.capacity() >= 96);
+
+    let mut reader = &[0u8, 1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6][..];
+    let mut values = [0u16; 4];
+    BEUnpacker::unpack_strided_with_scratch(&mut reader, &mut values, 1, 2, &mut scratch).unwrap();
+    assert!(values == [0, 1, 0, 2]);
+    let planar = BEUnpacker::unpack_deinterleaved_with_scratch::<u16>(&mut reader, 2, 1, &mut scratch).unwrap();
+    assert!(planar == [vec![3], vec![4]]);
+    let mut converted = [0u32; 2];
+    BEUnpacker::unpack_converted_with_scratch(&mut reader, &mut converted, |v: u16| v as u32 * 10, &mut scratch).unwrap();
+    assert!(converted == [50, 60] && reader.is_empty());
+
+    let values : Vec<u32> = (0..5000).collect();
+    let mut buffer = Vec::new();
+    BEPacker::pack_all_with_scratch(&mut buffer, &values[..], &mut scratch).unwrap();
+    let mut copy = vec![0u32; 5000];
+    BEUnpacker::unpack_strided_with_scratch(&mut &buffer[..], &mut copy[..], 0, 1, &mut scratch).unwrap();
+    assert!(copy == values);
+    let planar = BEUnpacker::unpack_deinterleaved_with_scratch::<u32>(&mut &buffer[..], 2, 2500, &mut scratch).unwrap();
+    assert!(planar[1].iter().enumerate().all(|(i, &v)| v == 2 * i as u32 + 1));
+    let mut wide = vec![0u64; 5000];
+    BEUnpacker::unpack_converted_with_scratch(&mut &buffer[..], &mut wide[..], |v: u32| v as u64, &mut scratch).unwrap();
+    assert!(wide.iter().zip(values.iter()).all(|(&w, &v)| w == v as u64));
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn smallvec_unpacking() {
+    use bytepack::{SmallVecUnpacker, Endianness, Error};
+    use smallvec::SmallVec;
+
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0, 3, 0, 4]);
+    let mut values = SmallVec::<[u16; 4]>::new();
+    assert!(reader.unpack_to_end_small(&mut values, Endianness::Big).unwrap() == 4);
+    assert!(values[..] == [1, 2, 3, 4] && !values.spilled());
+
+    let mut reader = Cursor::new((0..40u8).collect::<Vec<u8>>());
+    let mut values = SmallVec::<[u32; 4]>::new();
+    assert!(reader.unpack_to_end_small(&mut values, Endianness::Little).unwrap() == 10);
+    assert!(values[9] == 0x27262524 && values.spilled());
+
+    let mut reader = Cursor::new(vec![1u8, 2, 3]);
+    let mut values = SmallVec::<[u16; 4]>::new();
+    match reader.unpack_to_end_small(&mut values, Endianness::Little) {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    assert!(values.is_empty());
+
+    struct Failing;
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disconnected"))
+        }
+    }
+    let bytes = (0..40u8).collect::<Vec<u8>>();
+    let mut reader = std::io::Read::chain(&bytes[..], Failing);
+    let mut values = SmallVec::<[u32; 4]>::new();
+    values.push(7);
+    assert!(reader.unpack_to_end_small(&mut values, Endianness::Little).is_err());
+    assert!(values[..] == [7]);
+
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0, 3]);
+    let values : SmallVec<[u16; 2]> = reader.unpack_n_small(2, Endianness::Big).unwrap();
+    assert!(values[..] == [1, 2] && !values.spilled());
+    assert!(reader.unpack_n_small::<[u16; 2]>(2, Endianness::Big).is_err());
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn fixed_capacity() {
+    use arrayvec::ArrayVec;
+    use bytepack::{BEUnpacker, EndianSwitch, Error};
+
+    let mut values = ArrayVec::<u16, 4>::new();
+    values.push(7);
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0, 3]);
+    assert!(BEUnpacker::unpack_to_end_fixed(&mut reader, &mut values).unwrap() == 3);
+    assert!(values[..] == [7, 1, 2, 3]);
+
+    let mut values = ArrayVec::<u16, 2>::new();
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0, 3]);
+    match BEUnpacker::unpack_to_end_fixed(&mut reader, &mut values) {
+        Err(Error::LimitExceeded { limit: 2, .. }) => {},
+        _ => panic!("expected the capacity to be exceeded")
+    }
+    assert!(values.is_empty());
+    let mut reader = Cursor::new(vec![0u8, 1, 0]);
+    match BEUnpacker::unpack_to_end_fixed(&mut reader, &mut values) {
+        Err(Error::TrailingBytes { trailing: 1, .. }) => {},
+        _ => panic!("expected trailing bytes")
+    }
+    assert!(values.is_empty());
+
+    struct Empty;
+    impl EndianSwitch for Empty {
+        fn switch_endianness(&mut self) {}
+    }
+    unsafe impl Packed for Empty {}
+    let mut empty = ArrayVec::<Empty, 2>::new();
+    match BEUnpacker::unpack_to_end_fixed(&mut reader, &mut empty) {
+        Err(Error::InvalidInput { .. }) => {},
+        _ => panic!("expected an invalid input")
+    }
+
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0, 3]);
+    let values : ArrayVec<u16, 4> = BEUnpacker::unpack_n_fixed(&mut reader, 2).unwrap();
+    assert!(values[..] == [1, 2]);
+    match BEUnpacker::unpack_n_fixed::<ArrayVec<u16, 4>>(&mut reader, 5) {
+        Err(Error::LimitExceeded { limit: 4, .. }) => {},
+        _ => panic!("expected the capacity to be exceeded")
+    }
+}
+
+#[test]
+fn extend_targets() {
+    use std::collections::{VecDeque, BTreeSet};
+    use bytepack::{BEPacker, BEUnpacker};
+
+    #[repr(align(16))]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Wide(u32);
+    impl bytepack::EndianSwitch for Wide {
+        fn switch_endianness(&mut self) {
+            self.0.switch_endianness();
+        }
+    }
+    unsafe impl Packed for Wide {}
+
+    let mut buffer = Vec::new();
+    BEPacker::pack_iter(&mut buffer, 0..5000u32).unwrap();
+    let mut reader = Cursor::new(buffer);
+    let mut ring = VecDeque::from(vec![7u32]);
+    BEUnpacker::unpack_extend::<u32, _>(&mut reader, &mut ring, 4000).unwrap();
+    assert!(ring.len() == 4001 && ring[0] == 7 && ring[4000] == 3999);
+    let mut set = BTreeSet::new();
+    BEUnpacker::unpack_extend::<u32, _>(&mut reader, &mut set, 2).unwrap();
+    assert!(set.into_iter().collect::<Vec<u32>>() == [4000, 4001]);
+    let mut rest = Vec::new();
+    assert!(BEUnpacker::unpack_extend::<u32, _>(&mut reader, &mut rest, 1000).is_err());
+    assert!(rest.is_empty());
+
+    let mut data = vec![0u8; 32];
+    data[3] = 1;
+    data[19] = 2;
+    let mut reader = Cursor::new(data);
+    let mut wide = VecDeque::new();
+    BEUnpacker::unpack_extend::<Wide, _>(&mut reader, &mut wide, 2).unwrap();
+    assert!(wide == [Wide(1), Wide(2)]);
+}
+
+#[test]
+fn unpack_maybe() {
+    use bytepack::{BEUnpacker, NetUnpacker, Error};
+
+    let mut reader = Cursor::new(vec![0u8, 0, 0, 1, 0, 0, 0, 2]);
+    assert!(BEUnpacker::unpack_maybe::<u32>(&mut reader).unwrap() == Some(1));
+    assert!(NetUnpacker::unpack_maybe::<u32>(&mut reader).unwrap() == Some(2));
+    assert!(BEUnpacker::unpack_maybe::<u32>(&mut reader).unwrap().is_none());
+
+    let mut reader = Cursor::new(vec![0u8, 0, 0, 1, 0, 0]);
+    assert!(Unpacker::unpack_maybe::<u32>(&mut reader).unwrap().is_some());
+    match Unpacker::unpack_maybe::<u32>(&mut reader) {
+        Err(Error::ShortRead { expected: 4, actual: 2, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+#[test]
+fn sentinel() {
+    use bytepack::{LEUnpacker, Sentinel, Error};
+
+    let mut reader = Cursor::new(vec![1u8, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0]);
+    let mut values = vec![9u16];
+    assert!(LEUnpacker::unpack_until(&mut reader, 0, &mut values, Sentinel::Include, 2).unwrap() == 3);
+    assert!(values == [9, 1, 2, 0]);
+    match LEUnpacker::unpack_until(&mut reader, 0, &mut values, Sentinel::Exclude, 2) {
+        Ok(1) => {},
+        _ => panic!("expected a single value")
+    }
+    assert!(values == [9, 1, 2, 0, 3]);
+    match LEUnpacker::unpack_until(&mut reader, 0, &mut values, Sentinel::Exclude, 2) {
+        Err(Error::ShortRead { expected: 2, actual: 0, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+    assert!(values.len() == 5);
+
+    let mut reader = Cursor::new(vec![1u8, 0, 2, 0, 3, 0, 0, 0]);
+    match LEUnpacker::unpack_until(&mut reader, 0u16, &mut values, Sentinel::Exclude, 2) {
+        Err(Error::LimitExceeded { limit: 2, .. }) => {},
+        _ => panic!("expected the limit to be exceeded")
+    }
+    assert!(values.len() == 5);
+}
+
+#[test]
+fn unpack_while() {
+    use bytepack::{BEUnpacker, Error};
+
+    let mut reader = Cursor::new(vec![0u8, 1, 0, 2, 0x80, 0, 0, 3]);
+    let mut chunk = Vec::<u16>::new();
+    assert!(BEUnpacker::unpack_while(&mut reader, &mut chunk, 8, |&t| t < 0x8000).unwrap() == Some(0x8000));
+    assert!(chunk == [1, 2]);
+    assert!(BEUnpacker::unpack_while(&mut reader, &mut chunk, 8, |&t| t < 0x8000).unwrap().is_none());
+    assert!(chunk == [1, 2, 3]);
+
+    reader.set_position(0);
+    chunk.clear();
+    assert!(BEUnpacker::unpack_while_put_back(&mut reader, &mut chunk, 8, |&t| t < 0x8000).unwrap() == 2);
+    assert!(reader.position() == 4 && BEUnpacker::unpack::<u16>(&mut reader).unwrap() == 0x8000);
+
+    reader.set_position(0);
+    match BEUnpacker::unpack_while(&mut reader, &mut chunk, 1, |&t| t < 0x8000) {
+        Err(Error::LimitExceeded { limit: 1, .. }) => {},
+        _ => panic!("expected the limit to be exceeded")
+    }
+    assert!(chunk == [1, 2]);
+}
+
+#[test]
+fn pack_repeat() {
+    use bytepack::{LEPacker, BEPacker, BEUnpacker};
+
+    let mut buffer = Vec::new();
+    LEPacker::pack_repeat(&mut buffer, &0x0102u16, 3).unwrap();
+    BEPacker::pack_repeat(&mut buffer, &0x0102u16, 2).unwrap();
+    BEPacker::pack_repeat(&mut buffer, &7u8, 0).unwrap();
+    assert!(buffer == [2, 1, 2, 1, 2, 1, 1, 2, 1, 2]);
+
+    let mut reader = Cursor::new(Vec::new());
+    BEPacker::pack_repeat(&mut reader, &0xDEADBEEFu32, 5000).unwrap();
+    reader.set_position(0);
+    let values : Vec<u32> = BEUnpacker::unpack_n(&mut reader, 5000).unwrap();
+    assert!(values.iter().all(|&v| v == 0xDEADBEEF));
+
+    struct Block(#[allow(dead_code)] [u8; 10000]);
+    impl bytepack::EndianSwitch for Block {
+        fn switch_endianness(&mut self) {}
+    }
+    unsafe impl Packed for Block {}
+    let mut buffer = Vec::new();
+    BEPacker::pack_repeat(&mut buffer, &Block([3; 10000]), 2).unwrap();
+    assert!(buffer.len() == 20000 && buffer.iter().all(|&b| b == 3));
+}
+
+#[test]
+fn compare_streams() {
+    use bytepack::{compare_packed, compare_packed_with, Difference, Endianness, Error, LEPacker};
+
+    let mut golden = Vec::new();
+    LEPacker::pack_iter(&mut golden, 0..1000u32).unwrap();
+    let mut generated = golden.clone();
+    assert!(compare_packed_with::<u32, _, _>(&golden[..], &generated[..], Endianness::Little).unwrap().is_none());
+    generated[4 * 700 + 3] = 0x80;
+    let difference = compare_packed_with::<u32, _, _>(&golden[..], &generated[..], Endianness::Little).unwrap();
+    assert!(difference == Some(Difference { index: 700, a: Some(700), b: Some(0x800002BC) }));
+    let difference = compare_packed::<[u8; 4], _, _>(&golden[..400], &golden[..]).unwrap();
+    assert!(difference == Some(Difference { index: 100, a: None, b: Some([100, 0, 0, 0]) }));
+    match compare_packed::<u32, _, _>(&golden[..], &golden[..3998]) {
+        Err(Error::ShortRead { offset: Some(3996), .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+#[test]
+fn remaining_items() {
+    use bytepack::{LEUnpacker, NetUnpacker};
+
+    let mut reader = Cursor::new(vec![0u8; 14]);
+    assert!(Unpacker::remaining_items::<u32>(&mut reader).unwrap() == (3, 2));
+    reader.set_position(6);
+    assert!(LEUnpacker::remaining_items::<u32>(&mut reader).unwrap() == (2, 0));
+    assert!(NetUnpacker::remaining_items::<u64>(&mut reader).unwrap() == (1, 0));
+    assert!(reader.position() == 6);
+    reader.set_position(20);
+    assert!(Unpacker::remaining_items::<u16>(&mut reader).unwrap() == (0, 0));
+}
+
+#[cfg(all(feature = "rayon", any(unix, windows)))]
+#[test]
+fn parallel_loading() {
+    use std::fs::{self, File};
+    use bytepack::{UnpackAt, Endianness, Error, BEPacker};
+
+    let path = std::env::temp_dir().join(format!("bytepack-parallel-{}", std::process::id()));
+    let count = 3 << 20;
+    let mut writer = std::io::BufWriter::new(File::create(&path).unwrap());
+    BEPacker::pack_all(&mut writer, &[0u8; 2]).unwrap();
+    BEPacker::pack_iter(&mut writer, 0..count as u32).unwrap();
+    drop(writer);
+    let file = File::open(&path).unwrap();
+    let values : Vec<u32> = file.unpack_parallel_at(2, count, Endianness::Big).unwrap();
+    assert!(values.len() == count && values.iter().enumerate().all(|(i, &v)| v == i as u32));
+    match file.unpack_parallel_at::<u32>(6, count, Endianness::Big) {
+        Err(Error::ShortRead { offset: Some(8388614), .. }) => {},
+        _ => panic!("expected a short read in the last chunk")
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn buffered_unpacking() {
+    use std::io::BufReader;
+    use bytepack::{LEPacker, LEUnpacker, Error};
+
+    let mut buffer = Vec::new();
+    LEPacker::pack(&mut buffer, 1u8).unwrap();
+    LEPacker::pack_iter(&mut buffer, 0..100u32).unwrap();
+    buffer.extend_from_slice(&[1, 2]);
+    // a capacity which is not a multiple of the values makes some of them straddle refills
+    let mut reader = BufReader::with_capacity(7, &buffer[..]);
+    assert!(LEUnpacker::unpack_buffered::<u8>(&mut reader).unwrap() == 1);
+    for i in 0..100u32 {
+        assert!(LEUnpacker::unpack_buffered::<u32>(&mut reader).unwrap() == i);
+    }
+    match LEUnpacker::unpack_buffered::<u32>(&mut reader) {
+        Err(Error::ShortRead { expected: 4, actual: 2, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+#[test]
+fn slice_unpacker() {
+    use bytepack::{SliceUnpacker, Endianness, Error, BEPacker, LEUnpacker};
+
+    let mut buffer = Vec::new();
+    BEPacker::pack_iter(&mut buffer, 0..1000u32).unwrap();
+    buffer.push(0xAB);
+    let mut unpacker = SliceUnpacker::with_endianness(&buffer, Endianness::Big);
+    for i in 0..500u32 {
+        assert!(unpacker.unpack::<u32>().unwrap() == i);
+    }
+    let mut values = [0u32; 499];
+    unpacker.unpack_exact(&mut values).unwrap();
+    assert!(values[498] == 998);
+    match unpacker.unpack_exact(&mut values) {
+        Err(Error::ShortRead { expected: 1996, actual: 5, offset: Some(3996), .. }) => {},
+        _ => panic!("expected a short read")
+    }
+    assert!(unpacker.position() == 3996);
+    assert!(LEUnpacker::unpack::<u32>(&mut unpacker).unwrap() == 0xE7030000);
+    assert!(unpacker.remaining() == [0xAB]);
+
+    let mut unpacker = SliceUnpacker::new(&[1, 2]);
+    assert!(unpacker.unpack::<u16>().unwrap() == u16::from_ne_bytes([1, 2]));
+    assert!(unpacker.unpack::<u8>().is_err());
+}
+
+#[test]
+fn flexible_array_member() {
+    use bytepack::{TailCount, WithTail, BEPacker, BEUnpacker, Error};
+
+    #[derive(Packed, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Samples {
+        rate: u32,
+        count: u32
+    }
+
+    impl TailCount for Samples {
+        fn tail_count(&self) -> usize {
+            self.count as usize
+        }
+    }
+
+    let record = WithTail { header: Samples { rate: 8000, count: 3 }, tail: vec![-1i16, 0, 1] };
+    let mut buffer = Vec::new();
+    BEPacker::pack_with_tail(&mut buffer, &record).unwrap();
+    assert!(buffer == [0, 0, 0x1F, 0x40, 0, 0, 0, 3, 0xFF, 0xFF, 0, 0, 0, 1]);
+    let mut reader = Cursor::new(buffer);
+    assert!(BEUnpacker::unpack_with_tail::<Samples, i16>(&mut reader, 3).unwrap() == record);
+    reader.set_position(0);
+    match BEUnpacker::unpack_with_tail::<Samples, i16>(&mut reader, 2) {
+        Err(Error::LimitExceeded { limit: 2, .. }) => {},
+        _ => panic!("expected the limit to be exceeded")
+    }
+
+    let mismatched = WithTail { header: Samples { rate: 8000, count: 4 }, tail: vec![0i16] };
+    let mut buffer = Vec::new();
+    assert!(BEPacker::pack_with_tail(&mut buffer, &mismatched).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn header_packed() {
+    use bytepack::{HeaderPacked, SliceUnpacker, Endianness, Error, Result, LEPacker, LEUnpacker};
+
+    #[derive(Debug, PartialEq)]
+    struct Polygon {
+        id: u16,
+        points: Vec<(i16, i16)>
+    }
+
+    impl HeaderPacked for Polygon {
+        // the id and the number of points
+        type Header = [u16; 2];
+
+        fn body_len(header: &[u16; 2]) -> usize {
+            header[1] as usize * 4
+        }
+
+        fn header(&self) -> [u16; 2] {
+            [self.id, self.points.len() as u16]
+        }
+
+        fn unpack_body(header: [u16; 2], body: &mut SliceUnpacker) -> Result<Polygon> {
+            let mut points = Vec::new();
+            for _ in 0..header[1] {
+                points.push((body.unpack()?, body.unpack()?));
+            }
+            Ok(Polygon { id: header[0], points })
+        }
+
+        fn pack_body(&self, body: &mut Vec<u8>, endianness: Endianness) -> Result<()> {
+            for &(x, y) in &self.points {
+                Packer::pack_with(body, x, endianness)?;
+                Packer::pack_with(body, y, endianness)?;
+            }
+            Ok(())
+        }
+    }
+
+    let polygon = Polygon { id: 3, points: vec![(0, 0), (1, -1)] };
+    let mut buffer = Vec::new();
+    LEPacker::pack_header_packed(&mut buffer, &polygon).unwrap();
+    assert!(buffer == [3, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0xFF, 0xFF]);
+    let mut reader = Cursor::new(buffer);
+    assert!(LEUnpacker::unpack_header_packed::<Polygon>(&mut reader, 8).unwrap() == polygon);
+    reader.set_position(0);
+    match LEUnpacker::unpack_header_packed::<Polygon>(&mut reader, 4) {
+        Err(e @ Error::LimitExceeded { limit: 4, in_bytes: true, .. }) => {
+            assert!(e.type_name().unwrap().ends_with("Polygon"));
+            assert!(e.to_string().ends_with("is longer than the limit of 4 bytes"));
+        },
+        _ => panic!("expected the limit to be exceeded")
+    }
+    reader.get_mut().truncate(10);
+    reader.set_position(0);
+    match LEUnpacker::unpack_header_packed::<Polygon>(&mut reader, 8) {
+        Err(Error::ShortRead { expected: 8, actual: 6, .. }) => {},
+        _ => panic!("expected a short read")
+    }
+}
+
+ks_struct! {
+    #[derive(Packed)]
+    struct KsPoint {
+        x: bytepack::Be<i16>,
+        y: bytepack::Be<i16>,
+    }
+}
+
+ks_struct! {
+    #[derive(Packed)]
+    struct KsShape {
+        kind: u8,
+        flags: [u8; 3],
+        corners: [KsPoint; 2],
+        origin: KsPoint,
+        scale: [[f32; 2]; 2],
+    }
+}
+
+#[test]
+fn kaitai_definition() {
+    use bytepack::{ksy_definition, KaitaiType, Endianness};
+
+    assert!(KsShape::ks_type() == "ks_shape");
+    assert!(ksy_definition::<[u32; 3]>("samples", Endianness::Big) ==
+        "meta:\n  id: samples\n  endian: be\nseq:\n  - id: value\n    type: u4\n    repeat: expr\n    repeat-expr: 3\n");
+    let ksy = ksy_definition::<KsShape>("shape", Endianness::Little);
+    assert!(ksy.starts_with("meta:\n  id: shape\n  endian: le\nseq:\n  - id: ks_shape\n    type: ks_shape\ntypes:\n"));
+    // the point type is described once, before the shape using it
+    assert!(ksy.contains("  ks_point:\n    seq:\n      - id: x\n        type: s2be\n      - id: y\n        type: s2be\n  ks_shape:\n"));
+    assert!(ksy.matches("ks_point:").count() == 1);
+    assert!(ksy.ends_with("      - id: scale\n        type: f4\n        repeat: expr\n        repeat-expr: 4\n"));
+    assert!(ksy.contains("      - id: corners\n        type: ks_point\n        repeat: expr\n        repeat-expr: 2\n"));
+    assert!(std::mem::offset_of!(KsShape, origin) == 12 && std::mem::offset_of!(KsShape, scale) == 16);
+}
+
+#[test]
+fn roundtrip() {
+    use std::panic;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Rotated(u32);
+    // not its own inverse, so the big endian byte order breaks
+    impl bytepack::EndianSwitch for Rotated {
+        fn switch_endianness(&mut self) {
+            self.0 = self.0.rotate_left(8);
+        }
+    }
+    unsafe impl Packed for Rotated {}
+
+    bytepack::roundtrip(&-2.5f32);
+    bytepack::roundtrip(&[0x0102u16, 0x0304]);
+    let failure = panic::catch_unwind(|| bytepack::roundtrip(&Rotated(0x11223344))).unwrap_err();
+    let message = failure.downcast_ref::<String>().unwrap();
+    assert!(message.contains("Big"), "unexpected message {}", message);
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn arbitrary_packed(value in bytepack::any_packed::<[bytepack::Finite<f64>; 2]>()) {
+        assert!(value.iter().all(|v| v.get().is_finite()));
+        bytepack::roundtrip(&value);
+    }
+}
+
+#[test]
+fn unpack_to_end_failure() {
+    use std::io::{Error, Read};
+
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(Error::other("disconnected"))
+        }
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    buffer.pack_all(&(0..5000u64).collect::<Vec<_>>()).unwrap();
+    let bytes = buffer.into_inner();
+    let mut values = vec![7u64];
+    let mut reader = (&bytes[..20003]).chain(Failing);
+    assert!(reader.unpack_to_end(&mut values).is_err());
+    assert!(values == [7]);
+    let mut reader = Cursor::new(bytes);
+    assert!(reader.unpack_to_end(&mut values).unwrap() == 5000);
+    assert!(values.len() == 5001 && values[5000] == 4999);
+}
+
+#[test]
+fn dyn_unpacker() {
+    use bytepack::{ReadUnpacker, WritePacker, Endianness, Error};
+    use bytepack::{unpack_dyn, unpack_dyn_with, unpack_exact_dyn, unpack_to_end_dyn, pack_dyn_with, pack_all_dyn};
+
+    let mut bytes = Vec::new();
+    {
+        let sink: &mut (dyn WritePacker + Send) = &mut bytes;
+        pack_dyn_with(sink, &0x0102u16, Endianness::Big).unwrap();
+        pack_all_dyn(sink, &[3u8, 4]).unwrap();
+        sink.pack_bytes(&[5, 6, 7, 8]).unwrap();
+    }
+    assert!(bytes == [1, 2, 3, 4, 5, 6, 7, 8]);
+    let mut sources: Vec<Box<dyn ReadUnpacker>> = vec![Box::new(Cursor::new(bytes.clone())), Box::new(&bytes[..])];
+    for source in sources.iter_mut() {
+        assert!(unpack_dyn_with::<u16>(&mut **source, Endianness::Big).unwrap() == 0x0102);
+        let mut pair = [0u8; 2];
+        unpack_exact_dyn(&mut **source, &mut pair).unwrap();
+        assert!(pair == [3, 4]);
+        source.unpack_bytes(&mut pair).unwrap();
+        assert!(pair == [5, 6]);
+        let mut rest = Vec::<u8>::new();
+        assert!(unpack_to_end_dyn(&mut **source, &mut rest).unwrap() == 2);
+        match unpack_dyn::<u8>(&mut **source) {
+            Err(Error::ShortRead { .. }) => {},
+            _ => panic!("expected a short read")
+        }
+    }
+}
+
+#[test]
+fn explicit_byte_order() {
+    use bytepack::{ExplicitUnpacker, ExplicitPacker, Packer};
+
+    let mut buffer = Vec::new();
+    buffer.pack_be(0x0102u16).unwrap();
+    buffer.pack_le(0x0304u16).unwrap();
+    buffer.pack_ref_be(&0x05060708u32).unwrap();
+    buffer.pack_all_le(&[0x0a09u16, 0x0c0b]).unwrap();
+    buffer.pack_ne(0x0d0eu16).unwrap();
+    let mut native = Vec::new();
+    Packer::pack(&mut native, 0x0d0eu16).unwrap();
+    assert!(buffer[..12] == [1, 2, 4, 3, 5, 6, 7, 8, 9, 10, 11, 12]);
+    assert!(buffer[12..] == native[..]);
+    let mut reader = &buffer[..];
+    assert!(reader.unpack_le::<u16>().unwrap() == 0x0201);
+    let mut pair = [0u8; 2];
+    reader.unpack_exact_be(&mut pair).unwrap();
+    assert!(pair == [4, 3]);
+    assert!(reader.unpack_be::<u32>().unwrap() == 0x05060708);
+    assert!(reader.unpack_n_be::<u16>(2).unwrap() == [0x090a, 0x0b0c]);
+    let mut rest = Vec::new();
+    assert!(reader.unpack_to_end_ne::<u16>(&mut rest).unwrap() == 1);
+    assert!(rest == [0x0d0e]);
+}
+
+#[test]
+fn packed_view() {
+    use bytepack::{PackedView, Endianness, Error};
+
+    #[derive(Packed)]
+    #[repr(C)]
+    struct Header {
+        length: u32,
+        port: u16,
+        flags: [u8; 2]
+    }
+
+    let packet = [0u8, 0, 0, 8, 0x1F, 0x90, 1, 2, 0xFF];
+    assert!(PackedView::<Header>::new(&packet, Endianness::Big).is_err());
+    let (header, rest) = PackedView::<Header>::from_front(&packet, Endianness::Big).unwrap();
+    assert!(rest == [0xFF]);
+    assert!(header.as_bytes() == &packet[..8]);
+    assert!(bytepack::view_field!(header, port).unwrap() == 8080);
+    assert!(bytepack::view_field!(header, flags).unwrap() == [1, 2]);
+    assert!(header.get_at::<u16>(4).unwrap() == 8080);
+    match header.get_at::<u32>(6) {
+        Err(Error::InvalidInput { .. }) => {},
+        _ => panic!("expected an invalid input")
+    }
+    let whole = header.unpack();
+    assert!((whole.length, whole.port) == (8, 8080));
+    let little = PackedView::<Header>::new(&packet[..8], Endianness::Little).unwrap();
+    assert!(bytepack::view_field!(little, length).unwrap() == 0x08000000);
+    assert!(PackedView::<Header>::from_front(&packet[..7], Endianness::Big).is_err());
+}
+
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+#[test]
+fn uring_positioned() {
+    use std::fs::{self, OpenOptions};
+    use bytepack::{UringUnpackAt, UringPackAt, Endianness};
+
+    let path = std::env::temp_dir().join(format!("bytepack-uring-{}", std::process::id()));
+    let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path).unwrap();
+    let file = tokio_uring::fs::File::from_std(file);
+    tokio_uring::start(file.pack_all_at(4, &[1u16, 2, 3])).unwrap();
+    tokio_uring::start(file.pack_at_with(0, 10u32, Endianness::Big)).unwrap();
+    let size : u32 = tokio_uring::start(file.unpack_at_with(0, Endianness::Big)).unwrap();
+    assert!(size == 10);
+    let mut values = [0u16; 3];
+    tokio_uring::start(file.unpack_exact_at(4, &mut values)).unwrap();
+    assert!(values == [1, 2, 3]);
+    assert!(tokio_uring::start(file.unpack_at::<u32>(8)).is_err());
+    fs::remove_file(&path).unwrap();
+}